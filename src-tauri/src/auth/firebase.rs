@@ -1,5 +1,6 @@
 use super::{AuthSession, AuthTokens, User};
 use super::google::GoogleTokens;
+use crate::error::{AppError, AppResult, ErrorCode};
 use serde::{Deserialize, Serialize};
 
 /// Firebase Auth REST API endpoints
@@ -42,7 +43,7 @@ struct SignInWithIdpRequest {
 pub async fn sign_in_with_google_token(
     api_key: &str,
     google_tokens: &GoogleTokens,
-) -> Result<AuthSession, String> {
+) -> AppResult<AuthSession> {
     let client = reqwest::Client::new();
 
     let url = format!(
@@ -68,17 +69,20 @@ pub async fn sign_in_with_google_token(
         .json(&request)
         .send()
         .await
-        .map_err(|e| format!("Firebase auth request failed: {}", e))?;
+        .map_err(|e| AppError::network("Firebase auth request failed", e))?;
 
     if !response.status().is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Firebase auth failed: {}", error_text));
+        return Err(AppError::new(
+            ErrorCode::AuthFailed,
+            format!("Firebase auth failed: {}", error_text),
+        ));
     }
 
     let auth_response: SignInWithIdpResponse = response
         .json()
         .await
-        .map_err(|e| format!("Failed to parse Firebase response: {}", e))?;
+        .map_err(|e| AppError::parse("Failed to parse Firebase response", e))?;
 
     // Calculate expiration time
     let expires_in: i64 = auth_response
@@ -103,7 +107,7 @@ pub async fn sign_in_with_google_token(
 }
 
 /// Refresh the Firebase ID token using the refresh token
-pub async fn refresh_token(api_key: &str, refresh_token: &str) -> Result<AuthSession, String> {
+pub async fn refresh_token(api_key: &str, refresh_token: &str) -> AppResult<AuthSession> {
     let client = reqwest::Client::new();
 
     let url = format!("{}?key={}", FIREBASE_TOKEN_URL, api_key);
@@ -118,17 +122,20 @@ pub async fn refresh_token(api_key: &str, refresh_token: &str) -> Result<AuthSes
         .form(&params)
         .send()
         .await
-        .map_err(|e| format!("Token refresh request failed: {}", e))?;
+        .map_err(|e| AppError::network("Token refresh request failed", e))?;
 
     if !response.status().is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Token refresh failed: {}", error_text));
+        return Err(AppError::new(
+            ErrorCode::AuthExpired,
+            format!("Token refresh failed: {}", error_text),
+        ));
     }
 
     let refresh_response: RefreshTokenResponse = response
         .json()
         .await
-        .map_err(|e| format!("Failed to parse refresh response: {}", e))?;
+        .map_err(|e| AppError::parse("Failed to parse refresh response", e))?;
 
     // Calculate expiration time
     let expires_in: i64 = refresh_response
@@ -167,7 +174,7 @@ struct UserInfo {
 }
 
 /// Get user info from Firebase
-async fn get_user_info(api_key: &str, id_token: &str) -> Result<User, String> {
+async fn get_user_info(api_key: &str, id_token: &str) -> AppResult<User> {
     let client = reqwest::Client::new();
 
     let url = format!(
@@ -184,23 +191,26 @@ async fn get_user_info(api_key: &str, id_token: &str) -> Result<User, String> {
         .json(&body)
         .send()
         .await
-        .map_err(|e| format!("Get user info failed: {}", e))?;
+        .map_err(|e| AppError::network("Get user info failed", e))?;
 
     if !response.status().is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Get user info failed: {}", error_text));
+        return Err(AppError::new(
+            ErrorCode::AuthExpired,
+            format!("Get user info failed: {}", error_text),
+        ));
     }
 
     let account_response: GetAccountInfoResponse = response
         .json()
         .await
-        .map_err(|e| format!("Failed to parse user info: {}", e))?;
+        .map_err(|e| AppError::parse("Failed to parse user info", e))?;
 
     let user_info = account_response
         .users
         .into_iter()
         .next()
-        .ok_or("No user found")?;
+        .ok_or_else(|| AppError::not_found("No user found"))?;
 
     Ok(User {
         uid: user_info.local_id,
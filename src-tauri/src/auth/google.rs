@@ -1,3 +1,4 @@
+use crate::error::{AppError, AppResult, ErrorCode};
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use rand::Rng;
 use sha2::{Digest, Sha256};
@@ -50,6 +51,11 @@ pub struct GoogleTokens {
     pub refresh_token: Option<String>,
 }
 
+/// Error for a failed step of the OAuth flow
+fn auth_failed(message: impl Into<String>) -> AppError {
+    AppError::new(ErrorCode::AuthFailed, message)
+}
+
 /// Generate a random code verifier for PKCE
 fn generate_code_verifier() -> String {
     let mut rng = rand::thread_rng();
@@ -98,7 +104,7 @@ fn open_url_with_nsworkspace(url_string: &str) -> bool {
 }
 
 /// Start local server, open browser for OAuth, and wait for callback
-pub async fn start_google_sign_in(app: &AppHandle) -> Result<GoogleTokens, String> {
+pub async fn start_google_sign_in(app: &AppHandle) -> AppResult<GoogleTokens> {
     println!("[auth] ====== OAUTH FLOW START ======");
     println!("[auth] Step 1: Generating PKCE values...");
 
@@ -112,13 +118,13 @@ pub async fn start_google_sign_in(app: &AppHandle) -> Result<GoogleTokens, Strin
     let listener = TcpListener::bind("127.0.0.1:0")
         .map_err(|e| {
             println!("[auth] Step 2: FAILED - {}", e);
-            format!("Failed to bind local server: {}", e)
+            AppError::io("Failed to bind local server", e)
         })?;
     let port = listener
         .local_addr()
         .map_err(|e| {
             println!("[auth] Step 2: FAILED to get port - {}", e);
-            format!("Failed to get local address: {}", e)
+            AppError::io("Failed to get local address", e)
         })?
         .port();
     println!("[auth] Step 2: COMPLETE - Bound to port {}", port);
@@ -163,7 +169,7 @@ pub async fn start_google_sign_in(app: &AppHandle) -> Result<GoogleTokens, Strin
                             Ok(_) => println!("[auth] Tauri opener returned Ok"),
                             Err(e) => {
                                 println!("[auth] ERROR: All methods failed. Tauri error: {}", e);
-                                return Err(format!("Could not open browser. Check Console.app for [auth] logs."));
+                                return Err(AppError::platform("Could not open browser. Check Console.app for [auth] logs."));
                             }
                         }
                     }
@@ -175,7 +181,7 @@ pub async fn start_google_sign_in(app: &AppHandle) -> Result<GoogleTokens, Strin
                         Ok(_) => println!("[auth] Tauri opener returned Ok"),
                         Err(e2) => {
                             println!("[auth] ERROR: All methods failed: {}, {}", e, e2);
-                            return Err(format!("Could not open browser. Check Console.app for [auth] logs."));
+                            return Err(AppError::platform("Could not open browser. Check Console.app for [auth] logs."));
                         }
                     }
                 }
@@ -191,7 +197,7 @@ pub async fn start_google_sign_in(app: &AppHandle) -> Result<GoogleTokens, Strin
             Ok(_) => println!("[auth] Browser open command succeeded"),
             Err(e) => {
                 println!("[auth] ERROR: Failed to open browser: {}", e);
-                return Err(format!("Failed to open browser: {}", e));
+                return Err(AppError::platform(format!("Failed to open browser: {}", e)));
             }
         }
     }
@@ -200,8 +206,11 @@ pub async fn start_google_sign_in(app: &AppHandle) -> Result<GoogleTokens, Strin
     // This is critical: TcpListener::accept() is blocking and would freeze the app
     let code = tokio::task::spawn_blocking(move || wait_for_callback(listener))
         .await
-        .map_err(|e| format!("Task join error: {}", e))?
-        .map_err(|e| format!("OAuth callback error: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::Internal, format!("Task join error: {}", e)))?
+        .map_err(|e| AppError {
+            message: format!("OAuth callback error: {}", e.message),
+            ..e
+        })?;
 
     // Exchange the authorization code for tokens
     let tokens = exchange_code_for_tokens(&code, &code_verifier, &redirect_uri).await?;
@@ -210,8 +219,9 @@ pub async fn start_google_sign_in(app: &AppHandle) -> Result<GoogleTokens, Strin
 }
 
 /// Build the Google OAuth authorization URL
-fn build_auth_url(redirect_uri: &str, code_challenge: &str) -> Result<String, String> {
-    let mut url = Url::parse(GOOGLE_AUTH_URL).map_err(|e| e.to_string())?;
+fn build_auth_url(redirect_uri: &str, code_challenge: &str) -> AppResult<String> {
+    let mut url = Url::parse(GOOGLE_AUTH_URL)
+        .map_err(|e| AppError::new(ErrorCode::Internal, e.to_string()))?;
 
     url.query_pairs_mut()
         .append_pair("client_id", get_google_client_id())
@@ -227,18 +237,18 @@ fn build_auth_url(redirect_uri: &str, code_challenge: &str) -> Result<String, St
 }
 
 /// Wait for the OAuth callback on the local server
-fn wait_for_callback(listener: TcpListener) -> Result<String, String> {
+fn wait_for_callback(listener: TcpListener) -> AppResult<String> {
     // Accept one connection
     let (mut stream, _) = listener
         .accept()
-        .map_err(|e| format!("Failed to accept connection: {}", e))?;
+        .map_err(|e| AppError::io("Failed to accept connection", e))?;
 
     // Read the HTTP request
     let mut reader = BufReader::new(&stream);
     let mut request_line = String::new();
     reader
         .read_line(&mut request_line)
-        .map_err(|e| format!("Failed to read request: {}", e))?;
+        .map_err(|e| AppError::io("Failed to read request", e))?;
 
     // Parse the authorization code from the request
     let code = parse_auth_code(&request_line)?;
@@ -255,38 +265,40 @@ fn wait_for_callback(listener: TcpListener) -> Result<String, String> {
 
     stream
         .write_all(response.as_bytes())
-        .map_err(|e| format!("Failed to send response: {}", e))?;
+        .map_err(|e| AppError::io("Failed to send response", e))?;
 
     Ok(code)
 }
 
 /// Parse the authorization code from the HTTP request
-fn parse_auth_code(request_line: &str) -> Result<String, String> {
+fn parse_auth_code(request_line: &str) -> AppResult<String> {
     // Request line looks like: "GET /?code=abc123&scope=... HTTP/1.1"
     let parts: Vec<&str> = request_line.split_whitespace().collect();
     if parts.len() < 2 {
-        return Err("Invalid request".to_string());
+        return Err(auth_failed("Invalid request"));
     }
 
     let path = parts[1];
 
     // Check for error
     if path.contains("error=") {
-        let url = Url::parse(&format!("http://localhost{}", path)).map_err(|e| e.to_string())?;
+        let url = Url::parse(&format!("http://localhost{}", path))
+            .map_err(|e| auth_failed(e.to_string()))?;
         let error = url
             .query_pairs()
             .find(|(k, _)| k == "error")
             .map(|(_, v)| v.to_string())
             .unwrap_or_else(|| "Unknown error".to_string());
-        return Err(format!("OAuth error: {}", error));
+        return Err(auth_failed(format!("OAuth error: {}", error)).with_context("oauthError", error));
     }
 
     // Parse the code
-    let url = Url::parse(&format!("http://localhost{}", path)).map_err(|e| e.to_string())?;
+    let url = Url::parse(&format!("http://localhost{}", path))
+        .map_err(|e| auth_failed(e.to_string()))?;
     url.query_pairs()
         .find(|(k, _)| k == "code")
         .map(|(_, v)| v.to_string())
-        .ok_or_else(|| "Authorization code not found".to_string())
+        .ok_or_else(|| auth_failed("Authorization code not found"))
 }
 
 /// Exchange the authorization code for tokens
@@ -294,7 +306,7 @@ async fn exchange_code_for_tokens(
     code: &str,
     code_verifier: &str,
     redirect_uri: &str,
-) -> Result<GoogleTokens, String> {
+) -> AppResult<GoogleTokens> {
     let client = reqwest::Client::new();
 
     let params = [
@@ -311,26 +323,26 @@ async fn exchange_code_for_tokens(
         .form(&params)
         .send()
         .await
-        .map_err(|e| format!("Token request failed: {}", e))?;
+        .map_err(|e| AppError::network("Token request failed", e))?;
 
     if !response.status().is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Token exchange failed: {}", error_text));
+        return Err(auth_failed(format!("Token exchange failed: {}", error_text)));
     }
 
     let token_response: serde_json::Value = response
         .json()
         .await
-        .map_err(|e| format!("Failed to parse token response: {}", e))?;
+        .map_err(|e| AppError::parse("Failed to parse token response", e))?;
 
     let id_token = token_response["id_token"]
         .as_str()
-        .ok_or("Missing id_token")?
+        .ok_or_else(|| auth_failed("Missing id_token"))?
         .to_string();
 
     let access_token = token_response["access_token"]
         .as_str()
-        .ok_or("Missing access_token")?
+        .ok_or_else(|| auth_failed("Missing access_token"))?
         .to_string();
 
     let refresh_token = token_response["refresh_token"].as_str().map(|s| s.to_string());
@@ -8,6 +8,7 @@ pub use google::start_google_sign_in;
 #[allow(unused_imports)]
 pub use storage::{get_auth_state, clear_auth, load_auth_session, AuthState};
 
+use crate::error::AppResult;
 use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
 
@@ -46,7 +47,7 @@ pub struct AuthSession {
 pub async fn sign_in_with_google(
     app: AppHandle,
     api_key: String,
-) -> Result<AuthSession, String> {
+) -> AppResult<AuthSession> {
     println!("[auth] >>>>>> sign_in_with_google COMMAND CALLED <<<<<<");
     println!("[auth] API key length: {}", api_key.len());
 
@@ -64,7 +65,7 @@ pub async fn sign_in_with_google(
 
 /// Get the current auth state (cached or refreshed)
 #[tauri::command]
-pub async fn get_current_auth(api_key: String) -> Result<Option<AuthSession>, String> {
+pub async fn get_current_auth(api_key: String) -> AppResult<Option<AuthSession>> {
     match storage::load_auth_session() {
         Some(session) => {
             let now = chrono::Utc::now().timestamp();
@@ -91,6 +92,6 @@ pub async fn get_current_auth(api_key: String) -> Result<Option<AuthSession>, St
 
 /// Sign out and clear stored tokens
 #[tauri::command]
-pub async fn sign_out() -> Result<(), String> {
+pub async fn sign_out() -> AppResult<()> {
     storage::clear_auth()
 }
@@ -1,4 +1,5 @@
 use super::AuthSession;
+use crate::error::{AppError, AppResult};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -21,14 +22,16 @@ pub struct AuthState {
 }
 
 /// Save auth session to file storage
-pub fn save_auth_session(session: &AuthSession) -> Result<(), String> {
+pub fn save_auth_session(session: &AuthSession) -> AppResult<()> {
     let path = get_auth_file_path();
 
     let json = serde_json::to_string_pretty(session)
-        .map_err(|e| format!("Failed to serialize session: {}", e))?;
+        .map_err(|e| AppError::parse("Failed to serialize session", e))?;
 
     fs::write(&path, &json)
-        .map_err(|e| format!("Failed to save auth session: {}", e))?;
+        .map_err(|e| {
+            AppError::io("Failed to save auth session", e).with_context("path", path.display())
+        })?;
 
     Ok(())
 }
@@ -41,7 +44,7 @@ pub fn load_auth_session() -> Option<AuthSession> {
 }
 
 /// Clear auth session from file storage
-pub fn clear_auth() -> Result<(), String> {
+pub fn clear_auth() -> AppResult<()> {
     let path = get_auth_file_path();
     let _ = fs::remove_file(&path);
     Ok(())
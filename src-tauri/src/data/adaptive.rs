@@ -0,0 +1,217 @@
+//! Adaptive ranking from what was picked for what was typed.
+//!
+//! Each launcher paste records the query it was made from, so typing the same
+//! query (or the start of one used before) ranks the prompt picked then
+//! higher: after "gi" has led to "Git Commit Message" a few times, "gi" and
+//! "g" put it first. Weights decay with a half-life, so old habits fade.
+//!
+//! The history is saved to `selection_history.json` in the library's data
+//! directory and can be cleared independently of the usage log.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use super::lock::write_atomic;
+use super::normalize::fold;
+use crate::error::{AppError, AppResult};
+
+const FORMAT_VERSION: u32 = 1;
+
+/// A selection's weight halves every this many days
+const HALF_LIFE_DAYS: f64 = 14.0;
+
+/// Selections weaker than this are forgotten
+const MIN_WEIGHT: f64 = 0.05;
+
+/// Most distinct queries kept; the least recently used are dropped first
+const MAX_QUERIES: usize = 500;
+
+/// Longest query recorded, in characters
+const MAX_QUERY_CHARS: usize = 64;
+
+/// Weight of one prompt picked for one query
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Selection {
+    /// Weight as of `updated` (each pick adds 1)
+    weight: f64,
+    updated: DateTime<Utc>,
+}
+
+impl Selection {
+    fn weight_at(&self, now: DateTime<Utc>) -> f64 {
+        let days = (now - self.updated).num_seconds().max(0) as f64 / 86_400.0;
+        self.weight * 0.5f64.powf(days / HALF_LIFE_DAYS)
+    }
+}
+
+#[derive(Deserialize)]
+struct StoredHistory {
+    version: u32,
+    queries: HashMap<String, HashMap<String, Selection>>,
+}
+
+#[derive(Serialize)]
+struct StoredHistoryRef<'a> {
+    version: u32,
+    queries: &'a HashMap<String, HashMap<String, Selection>>,
+}
+
+/// Prompts picked per query
+#[derive(Default)]
+pub struct SelectionHistory {
+    /// Folded query -> prompt id -> selection
+    queries: HashMap<String, HashMap<String, Selection>>,
+}
+
+/// The form queries are recorded and looked up in: folded, with runs of
+/// whitespace collapsed. None for queries not worth recording.
+pub fn history_key(query: &str) -> Option<String> {
+    let key = fold(query).split_whitespace().collect::<Vec<_>>().join(" ");
+    (!key.is_empty() && key.chars().count() <= MAX_QUERY_CHARS).then_some(key)
+}
+
+impl SelectionHistory {
+    /// Load the history, starting empty if it's missing, unreadable or from
+    /// another format version
+    pub fn load(path: &Path) -> Self {
+        let stored = fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<StoredHistory>(&json).ok())
+            .filter(|stored| stored.version == FORMAT_VERSION);
+        Self { queries: stored.map(|stored| stored.queries).unwrap_or_default() }
+    }
+
+    pub fn save(&self, path: &Path) -> AppResult<()> {
+        let stored = StoredHistoryRef { version: FORMAT_VERSION, queries: &self.queries };
+        let json = serde_json::to_string(&stored)
+            .map_err(|e| AppError::parse("Failed to serialize selection history", e))?;
+        write_atomic(path, json.as_bytes()).map_err(|e| {
+            AppError::io("Failed to write selection history", e).with_context("path", path.display())
+        })
+    }
+
+    /// Record that `prompt_id` was picked after typing `query`
+    pub fn record(&mut self, query: &str, prompt_id: &str, now: DateTime<Utc>) {
+        let Some(key) = history_key(query) else {
+            return;
+        };
+        let selections = self.queries.entry(key).or_default();
+        let weight = selections.get(prompt_id).map_or(0.0, |s| s.weight_at(now)) + 1.0;
+        selections.insert(prompt_id.to_string(), Selection { weight, updated: now });
+        self.prune(now);
+    }
+
+    /// Decayed weight per prompt picked for `query` or for a longer query
+    /// starting with it
+    pub fn weights(&self, query: &str, now: DateTime<Utc>) -> HashMap<&str, f64> {
+        let mut weights: HashMap<&str, f64> = HashMap::new();
+        let Some(key) = history_key(query) else {
+            return weights;
+        };
+
+        for (recorded, selections) in &self.queries {
+            if !recorded.starts_with(key.as_str()) {
+                continue;
+            }
+            for (prompt_id, selection) in selections {
+                *weights.entry(prompt_id.as_str()).or_default() += selection.weight_at(now);
+            }
+        }
+        weights
+    }
+
+    /// Forget a deleted prompt
+    pub fn remove_prompt(&mut self, prompt_id: &str) {
+        for selections in self.queries.values_mut() {
+            selections.remove(prompt_id);
+        }
+        self.queries.retain(|_, selections| !selections.is_empty());
+    }
+
+    pub fn clear(&mut self) {
+        self.queries.clear();
+    }
+
+    /// Drop faded selections, then the least recently used queries over the limit
+    fn prune(&mut self, now: DateTime<Utc>) {
+        for selections in self.queries.values_mut() {
+            selections.retain(|_, selection| selection.weight_at(now) >= MIN_WEIGHT);
+        }
+        self.queries.retain(|_, selections| !selections.is_empty());
+
+        if self.queries.len() > MAX_QUERIES {
+            let mut by_recency: Vec<(String, DateTime<Utc>)> = self
+                .queries
+                .iter()
+                .map(|(query, selections)| {
+                    let latest = selections.values().map(|s| s.updated).max().unwrap_or(now);
+                    (query.clone(), latest)
+                })
+                .collect();
+            by_recency.sort_by_key(|(_, latest)| std::cmp::Reverse(*latest));
+            for (query, _) in by_recency.into_iter().skip(MAX_QUERIES) {
+                self.queries.remove(&query);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_prefixes_of_recorded_queries_match() {
+        let now = Utc::now();
+        let mut history = SelectionHistory::default();
+        history.record("Gi", "git", now);
+        history.record("gi", "git", now);
+        history.record("gist", "gist", now);
+
+        let weights = history.weights("g", now);
+        assert_eq!(weights["git"], 2.0);
+        assert_eq!(weights["gist"], 1.0);
+        assert_eq!(history.weights("gi ", now).len(), 2);
+        assert!(history.weights("git", now).is_empty());
+        assert!(history.weights("  ", now).is_empty());
+    }
+
+    #[test]
+    fn test_weights_decay_and_fade_out() {
+        let then = Utc::now() - Duration::days(14);
+        let mut history = SelectionHistory::default();
+        history.record("rev", "review", then);
+
+        let now = then + Duration::days(14);
+        assert!((history.weights("rev", now)["review"] - 0.5).abs() < 1e-9);
+
+        // Much later the old pick is pruned when something new is recorded
+        history.record("email", "reply", now + Duration::days(100));
+        assert!(history.weights("rev", now).is_empty());
+    }
+
+    #[test]
+    fn test_persists_and_clears() {
+        let dir = std::env::temp_dir().join(format!("promptlight-adaptive-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("selection_history.json");
+        let now = Utc::now();
+
+        let mut history = SelectionHistory::default();
+        history.record("rev", "review", now);
+        history.record("rev", "other", now);
+        history.remove_prompt("other");
+        history.save(&path).unwrap();
+
+        let mut loaded = SelectionHistory::load(&path);
+        assert_eq!(loaded.weights("r", now).keys().collect::<Vec<_>>(), vec![&"review"]);
+        loaded.clear();
+        assert!(loaded.weights("r", now).is_empty());
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,248 @@
+//! BM25F relevance scoring across prompt fields.
+//!
+//! For each query term, its frequency in every field is normalized by that
+//! field's length, weighted per field, and summed into one pseudo-frequency.
+//! That is saturated (extra occurrences add less and less) and scaled by the
+//! term's rarity across the library (IDF). A rare term in a prompt's content
+//! thus outranks a word that appears in every description, and a match in a
+//! short name counts for more than one buried in a long description.
+//!
+//! Terms match whole tokens, or the start of a token with `PREFIX_QUALITY`
+//! weight, the same way content is matched through the content index.
+
+use super::content_index::{tokenize, PREFIX_QUALITY};
+
+/// Number of scored fields
+pub const FIELD_COUNT: usize = 5;
+
+/// Fields scored by BM25F
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Name,
+    Folder,
+    Description,
+    Tags,
+    Content,
+}
+
+/// Weight of a field and how strongly its length dampens matches (0 = not at all)
+struct FieldParams {
+    weight: f64,
+    b: f64,
+}
+
+/// Parameters per field, in `Field` order
+const FIELDS: [FieldParams; FIELD_COUNT] = [
+    FieldParams { weight: 3.0, b: 0.5 },  // name
+    FieldParams { weight: 1.5, b: 0.0 },  // folder
+    FieldParams { weight: 1.5, b: 0.75 }, // description
+    FieldParams { weight: 2.0, b: 0.3 },  // tags
+    FieldParams { weight: 1.0, b: 0.75 }, // content
+];
+
+/// Term frequency saturation
+const K1: f64 = 1.2;
+
+/// One prompt's length and query term frequencies in each field
+#[derive(Debug, Clone)]
+pub struct DocumentTerms {
+    lengths: [f64; FIELD_COUNT],
+    /// Per query term, its (weighted) frequency in each field
+    frequencies: Vec<[f64; FIELD_COUNT]>,
+}
+
+impl DocumentTerms {
+    pub fn new(term_count: usize) -> Self {
+        Self {
+            lengths: [0.0; FIELD_COUNT],
+            frequencies: vec![[0.0; FIELD_COUNT]; term_count],
+        }
+    }
+
+    /// Tokenize a field's text and count the query terms in it
+    pub fn add_text(&mut self, field: Field, text: &str, terms: &[String]) {
+        let f = field as usize;
+        for token in tokenize(text) {
+            self.lengths[f] += 1.0;
+            for (term, frequencies) in terms.iter().zip(&mut self.frequencies) {
+                if token == *term {
+                    frequencies[f] += 1.0;
+                } else if token.starts_with(term.as_str()) {
+                    frequencies[f] += PREFIX_QUALITY;
+                }
+            }
+        }
+    }
+
+    /// Set a field's length and term frequencies counted elsewhere (content
+    /// comes from the content index)
+    pub fn set_counts(&mut self, field: Field, length: f64, term_frequencies: &[f64]) {
+        let f = field as usize;
+        self.lengths[f] = length;
+        for (frequencies, &frequency) in self.frequencies.iter_mut().zip(term_frequencies) {
+            frequencies[f] = frequency;
+        }
+    }
+
+    /// Whether any query term occurs in any field
+    pub fn has_match(&self) -> bool {
+        self.frequencies.iter().flatten().any(|&frequency| frequency > 0.0)
+    }
+}
+
+/// Library-wide statistics the scores are relative to
+#[derive(Debug, Clone)]
+pub struct Corpus {
+    documents: f64,
+    total_lengths: [f64; FIELD_COUNT],
+    /// Averages known up front, overriding `total_lengths`
+    known_averages: [Option<f64>; FIELD_COUNT],
+    /// Per query term, the number of documents containing it in any field
+    document_frequencies: Vec<f64>,
+}
+
+impl Corpus {
+    pub fn new(term_count: usize) -> Self {
+        Self {
+            documents: 0.0,
+            total_lengths: [0.0; FIELD_COUNT],
+            known_averages: [None; FIELD_COUNT],
+            document_frequencies: vec![0.0; term_count],
+        }
+    }
+
+    /// Count a document towards the statistics
+    pub fn add(&mut self, document: &DocumentTerms) {
+        self.documents += 1.0;
+        for (total, length) in self.total_lengths.iter_mut().zip(document.lengths) {
+            *total += length;
+        }
+        for (count, frequencies) in self.document_frequencies.iter_mut().zip(&document.frequencies) {
+            if frequencies.iter().any(|&frequency| frequency > 0.0) {
+                *count += 1.0;
+            }
+        }
+    }
+
+    /// Use a field's average length from elsewhere, for fields whose length
+    /// isn't known for every document
+    pub fn set_average_length(&mut self, field: Field, average: f64) {
+        self.known_averages[field as usize] = Some(average);
+    }
+
+    fn average_length(&self, f: usize) -> f64 {
+        self.known_averages[f].unwrap_or_else(|| {
+            if self.documents > 0.0 {
+                self.total_lengths[f] / self.documents
+            } else {
+                0.0
+            }
+        })
+    }
+
+    /// Inverse document frequency of a term (always positive)
+    fn idf(&self, document_frequency: f64) -> f64 {
+        ((self.documents - document_frequency + 0.5) / (document_frequency + 0.5) + 1.0).ln()
+    }
+
+    /// BM25F score of a document
+    pub fn score(&self, document: &DocumentTerms) -> f64 {
+        let averages: [f64; FIELD_COUNT] = std::array::from_fn(|f| self.average_length(f));
+
+        document
+            .frequencies
+            .iter()
+            .zip(&self.document_frequencies)
+            .map(|(frequencies, &document_frequency)| {
+                let pseudo_frequency: f64 = (0..FIELD_COUNT)
+                    .filter(|&f| frequencies[f] > 0.0)
+                    .map(|f| {
+                        let params = &FIELDS[f];
+                        let normalization = if averages[f] > 0.0 {
+                            1.0 - params.b + params.b * document.lengths[f] / averages[f]
+                        } else {
+                            1.0
+                        };
+                        params.weight * frequencies[f] / normalization
+                    })
+                    .sum();
+
+                if pseudo_frequency > 0.0 {
+                    self.idf(document_frequency) * pseudo_frequency / (K1 + pseudo_frequency)
+                } else {
+                    0.0
+                }
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(query: &str) -> Vec<String> {
+        tokenize(query).collect()
+    }
+
+    fn document(fields: &[(Field, &str)], terms: &[String]) -> DocumentTerms {
+        let mut document = DocumentTerms::new(terms.len());
+        for &(field, text) in fields {
+            document.add_text(field, text, terms);
+        }
+        document
+    }
+
+    fn scores(documents: &[DocumentTerms], terms: &[String]) -> Vec<f64> {
+        let mut corpus = Corpus::new(terms.len());
+        for document in documents {
+            corpus.add(document);
+        }
+        documents.iter().map(|document| corpus.score(document)).collect()
+    }
+
+    #[test]
+    fn test_rare_content_term_beats_common_description_word() {
+        let q = terms("helpful idempotent");
+        let mut documents: Vec<DocumentTerms> = (0..8)
+            .map(|_| document(&[(Field::Description, "A helpful writing assistant")], &q))
+            .collect();
+        documents.push(document(
+            &[
+                (Field::Description, "Design an API"),
+                (Field::Content, "Make every endpoint idempotent and retry safe"),
+            ],
+            &q,
+        ));
+
+        let scores = scores(&documents, &q);
+        assert!(scores[8] > scores[0], "{:?}", scores);
+    }
+
+    #[test]
+    fn test_short_fields_and_names_weigh_more() {
+        let q = terms("email");
+        let documents = vec![
+            document(&[(Field::Name, "Email")], &q),
+            document(&[(Field::Name, "Email follow up after the quarterly planning meeting")], &q),
+            document(&[(Field::Description, "Email")], &q),
+            document(&[(Field::Name, "Unrelated")], &q),
+        ];
+
+        let scores = scores(&documents, &q);
+        assert!(scores[0] > scores[1]);
+        assert!(scores[0] > scores[2]);
+        assert_eq!(scores[3], 0.0);
+        assert!(!documents[3].has_match());
+    }
+
+    #[test]
+    fn test_prefix_matches_count_less_than_whole_tokens() {
+        let q = terms("summ");
+        let exact = terms("summary");
+        let as_prefix = document(&[(Field::Name, "Summary")], &q);
+        let as_token = document(&[(Field::Name, "Summary")], &exact);
+        assert_eq!(as_prefix.frequencies[0][Field::Name as usize], PREFIX_QUALITY);
+        assert_eq!(as_token.frequencies[0][Field::Name as usize], 1.0);
+    }
+}
@@ -0,0 +1,183 @@
+//! Built-in template variables, resolved when a prompt is rendered.
+//!
+//! Reserved names such as `{{clipboard}}` or `{{date:%d %B}}` aren't filled
+//! in by the user: their values come from the environment the prompt is
+//! pasted in. Some take an argument after a colon (a chrono format string for
+//! `date` and `time`). Each has a fallback for when its source has nothing,
+//! which a `{{name|default}}` in the template overrides.
+//!
+//! To add one, add an entry to `BUILTINS` (and an `Environment` method if it
+//! needs something from outside the data layer).
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+
+/// Where built-in variables get their values (shared across awaits, so `Sync`)
+pub trait Environment: Sync {
+    /// Text on the clipboard
+    fn clipboard(&self) -> Option<String>;
+    /// Name of the app the launcher was opened over
+    fn app(&self) -> Option<String>;
+    /// Name of the active library
+    fn library(&self) -> Option<String>;
+    fn now(&self) -> DateTime<Local>;
+}
+
+/// A reserved template variable
+pub struct Builtin {
+    pub name: &'static str,
+    /// Whether it takes an argument (`{{name:arg}}`)
+    pub takes_arg: bool,
+    resolve: fn(&dyn Environment, Option<&str>) -> Option<String>,
+    /// Value when `resolve` has none and the placeholder has no default
+    pub fallback: &'static str,
+}
+
+impl Builtin {
+    /// Value for `{{name}}` or `{{name:arg}}`, if its source has one
+    pub fn resolve(&self, env: &dyn Environment, arg: Option<&str>) -> Option<String> {
+        (self.resolve)(env, arg)
+    }
+}
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+const DEFAULT_TIME_FORMAT: &str = "%H:%M";
+
+/// The built-in variables
+pub static BUILTINS: &[Builtin] = &[
+    // Text on the clipboard
+    Builtin {
+        name: "clipboard",
+        takes_arg: false,
+        resolve: |env, _| env.clipboard().filter(|text| !text.is_empty()),
+        fallback: "",
+    },
+    // Today's date; an optional chrono format, e.g. {{date:%d %B %Y}}
+    Builtin {
+        name: "date",
+        takes_arg: true,
+        resolve: |env, format| Some(format_now(env, format, DEFAULT_DATE_FORMAT)),
+        fallback: "",
+    },
+    // The current time; an optional chrono format, e.g. {{time:%H:%M:%S}}
+    Builtin {
+        name: "time",
+        takes_arg: true,
+        resolve: |env, format| Some(format_now(env, format, DEFAULT_TIME_FORMAT)),
+        fallback: "",
+    },
+    // The app the launcher was opened over
+    Builtin {
+        name: "app",
+        takes_arg: false,
+        resolve: |env, _| env.app(),
+        fallback: "",
+    },
+    // The active library: the account's email, or Local
+    Builtin {
+        name: "library",
+        takes_arg: false,
+        resolve: |env, _| env.library(),
+        fallback: "Local",
+    },
+    // A new random UUID (the same one for each use in a prompt)
+    Builtin {
+        name: "uuid",
+        takes_arg: false,
+        resolve: |_, _| Some(uuid::Uuid::new_v4().to_string()),
+        fallback: "",
+    },
+];
+
+/// The built-in variable a placeholder name refers to, and its argument.
+/// A name with an argument only matches a builtin that takes one.
+pub fn find(name: &str) -> Option<(&'static Builtin, Option<&str>)> {
+    let (base, arg) = match name.split_once(':') {
+        Some((base, arg)) => (base.trim(), Some(arg)),
+        None => (name, None),
+    };
+    BUILTINS
+        .iter()
+        .find(|b| b.name == base && (arg.is_none() || b.takes_arg))
+        .map(|b| (b, arg))
+}
+
+/// The current time in `format`, or in `default` if it's missing or invalid
+fn format_now(env: &dyn Environment, format: Option<&str>, default: &str) -> String {
+    let format = format
+        .filter(|f| !f.is_empty() && !StrftimeItems::new(f).any(|item| matches!(item, Item::Error)))
+        .unwrap_or(default);
+    env.now().format(format).to_string()
+}
+
+/// A fixed environment for tests
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct TestEnvironment {
+    pub clipboard: Option<String>,
+    pub app: Option<String>,
+    pub library: Option<String>,
+}
+
+#[cfg(test)]
+impl Environment for TestEnvironment {
+    fn clipboard(&self) -> Option<String> {
+        self.clipboard.clone()
+    }
+
+    fn app(&self) -> Option<String> {
+        self.app.clone()
+    }
+
+    fn library(&self) -> Option<String> {
+        self.library.clone()
+    }
+
+    fn now(&self) -> DateTime<Local> {
+        use chrono::TimeZone;
+        Local.with_ymd_and_hms(2024, 3, 9, 14, 5, 0).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(env: &TestEnvironment, name: &str) -> Option<String> {
+        let (builtin, arg) = find(name)?;
+        Some(builtin.resolve(env, arg).unwrap_or_else(|| builtin.fallback.to_string()))
+    }
+
+    #[test]
+    fn test_resolve_with_formats_and_fallbacks() {
+        let env = TestEnvironment::default();
+        assert_eq!(resolve(&env, "date").as_deref(), Some("2024-03-09"));
+        assert_eq!(resolve(&env, "date:%d %B").as_deref(), Some("09 March"));
+        assert_eq!(resolve(&env, "time").as_deref(), Some("14:05"));
+        // An invalid format falls back to the default one
+        assert_eq!(resolve(&env, "time:%Q").as_deref(), Some("14:05"));
+
+        assert_eq!(resolve(&env, "clipboard").as_deref(), Some(""));
+        assert_eq!(resolve(&env, "app").as_deref(), Some(""));
+        assert_eq!(resolve(&env, "library").as_deref(), Some("Local"));
+        assert_eq!(resolve(&env, "uuid").map(|u| u.len()), Some(36));
+
+        let env = TestEnvironment {
+            clipboard: Some("copied".to_string()),
+            app: Some("com.apple.Safari".to_string()),
+            library: Some("me@example.com".to_string()),
+        };
+        assert_eq!(resolve(&env, "clipboard").as_deref(), Some("copied"));
+        assert_eq!(resolve(&env, "app").as_deref(), Some("com.apple.Safari"));
+        assert_eq!(resolve(&env, "library").as_deref(), Some("me@example.com"));
+    }
+
+    #[test]
+    fn test_only_reserved_names_are_builtins() {
+        assert!(find("topic").is_none());
+        assert!(find("Date").is_none());
+        // Arguments are only taken by builtins that expect one
+        assert!(find("clipboard:x").is_none());
+        assert!(find("date:%Y").is_some());
+    }
+}
@@ -0,0 +1,186 @@
+//! In-memory cache for the parsed index and prompt content.
+//!
+//! `LocalDataStore` consults this cache instead of re-reading `index.json` and
+//! every prompt file on each command. Entries are validated against the file's
+//! modification time and length, so edits made outside the store (another
+//! process, a text editor) are picked up on the next read. Writes made through
+//! the store refresh the cache directly.
+//!
+//! The usage log is cached the same way as the index, keyed by its stamp.
+//!
+//! Content entries are re-stat'ed at most once per `CONTENT_REVALIDATE_AFTER`,
+//! so a burst of search keystrokes doesn't stat every prompt file each time.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::normalize::fold;
+use super::usage::UsageEvent;
+use super::PromptIndex;
+
+/// How long a content entry is trusted before its file is checked again
+pub const CONTENT_REVALIDATE_AFTER: Duration = Duration::from_secs(1);
+
+/// File identity used to detect external modification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    /// Read the current stamp of a file, or None if it doesn't exist
+    pub fn of(path: &Path) -> Option<Self> {
+        let meta = fs::metadata(path).ok()?;
+        Some(Self {
+            modified: meta.modified().ok(),
+            len: meta.len(),
+        })
+    }
+}
+
+/// Cached prompt content with a folded copy for search (see `normalize`)
+pub struct CachedContent {
+    pub content: Arc<str>,
+    pub folded: Arc<str>,
+}
+
+struct ContentEntry {
+    stamp: FileStamp,
+    checked: Instant,
+    content: Arc<str>,
+    folded: Arc<str>,
+}
+
+impl ContentEntry {
+    fn to_cached(&self) -> CachedContent {
+        CachedContent {
+            content: Arc::clone(&self.content),
+            folded: Arc::clone(&self.folded),
+        }
+    }
+}
+
+/// Cache of the parsed index and prompt file contents for one data directory
+#[derive(Default)]
+pub struct StoreCache {
+    index: Mutex<Option<(FileStamp, Arc<PromptIndex>)>>,
+    usage: Mutex<Option<(FileStamp, Arc<Vec<UsageEvent>>)>>,
+    content: Mutex<HashMap<PathBuf, ContentEntry>>,
+}
+
+impl StoreCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the cached index if it still matches the file on disk
+    pub fn get_index(&self, stamp: FileStamp) -> Option<Arc<PromptIndex>> {
+        let guard = self.index.lock().ok()?;
+        match guard.as_ref() {
+            Some((cached_stamp, index)) if *cached_stamp == stamp => Some(Arc::clone(index)),
+            _ => None,
+        }
+    }
+
+    /// Get the last known index without checking the file (used when the
+    /// library is locked by another instance)
+    pub fn last_index(&self) -> Option<Arc<PromptIndex>> {
+        let guard = self.index.lock().ok()?;
+        guard.as_ref().map(|(_, index)| Arc::clone(index))
+    }
+
+    /// Store the index as it was just read from or written to disk
+    pub fn put_index(&self, stamp: FileStamp, index: Arc<PromptIndex>) {
+        if let Ok(mut guard) = self.index.lock() {
+            *guard = Some((stamp, index));
+        }
+    }
+
+    /// Get the cached usage log if it still matches the file on disk
+    pub fn get_usage(&self, stamp: FileStamp) -> Option<Arc<Vec<UsageEvent>>> {
+        let guard = self.usage.lock().ok()?;
+        match guard.as_ref() {
+            Some((cached_stamp, events)) if *cached_stamp == stamp => Some(Arc::clone(events)),
+            _ => None,
+        }
+    }
+
+    /// Store the usage log as it was just read from disk
+    pub fn put_usage(&self, stamp: FileStamp, events: Arc<Vec<UsageEvent>>) {
+        if let Ok(mut guard) = self.usage.lock() {
+            *guard = Some((stamp, events));
+        }
+    }
+
+    /// Get cached content that was checked against the file recently enough
+    /// to be trusted without touching the disk
+    pub fn get_fresh_content(&self, path: &Path) -> Option<CachedContent> {
+        let guard = self.content.lock().ok()?;
+        guard
+            .get(path)
+            .filter(|entry| entry.checked.elapsed() < CONTENT_REVALIDATE_AFTER)
+            .map(ContentEntry::to_cached)
+    }
+
+    /// Get cached content if it still matches the file on disk,
+    /// marking it fresh again
+    pub fn revalidate_content(&self, path: &Path, stamp: FileStamp) -> Option<CachedContent> {
+        let mut guard = self.content.lock().ok()?;
+        let entry = guard.get_mut(path).filter(|entry| entry.stamp == stamp)?;
+        entry.checked = Instant::now();
+        Some(entry.to_cached())
+    }
+
+    /// Store content as it was just read from or written to disk
+    pub fn put_content(&self, path: &Path, stamp: FileStamp, content: &str) -> CachedContent {
+        let cached = CachedContent {
+            content: Arc::from(content),
+            folded: Arc::from(fold(content)),
+        };
+        if let Ok(mut guard) = self.content.lock() {
+            guard.insert(
+                path.to_path_buf(),
+                ContentEntry {
+                    stamp,
+                    checked: Instant::now(),
+                    content: Arc::clone(&cached.content),
+                    folded: Arc::clone(&cached.folded),
+                },
+            );
+        }
+        cached
+    }
+
+    /// Drop a content entry (file deleted or moved)
+    pub fn remove_content(&self, path: &Path) {
+        if let Ok(mut guard) = self.content.lock() {
+            guard.remove(path);
+        }
+    }
+
+    /// Drop all content entries under a directory (folder renamed or deleted)
+    pub fn remove_content_under(&self, dir: &Path) {
+        if let Ok(mut guard) = self.content.lock() {
+            guard.retain(|path, _| !path.starts_with(dir));
+        }
+    }
+
+    /// Drop everything
+    #[cfg(test)]
+    pub fn clear(&self) {
+        if let Ok(mut guard) = self.index.lock() {
+            *guard = None;
+        }
+        if let Ok(mut guard) = self.usage.lock() {
+            *guard = None;
+        }
+        if let Ok(mut guard) = self.content.lock() {
+            guard.clear();
+        }
+    }
+}
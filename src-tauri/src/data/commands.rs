@@ -1,21 +1,62 @@
-use tauri::State;
+use chrono::{Duration, Local, Utc};
+use std::collections::HashMap;
+use tauri::{AppHandle, State};
 
+use crate::error::AppResult;
+use crate::os::environment::AppEnvironment;
+use crate::os::previous_app;
+
+use super::include::{self, Includer};
+use super::page::{IndexPage, Page, PromptSort, SearchPage, SortDirection};
+use super::palette::{self, LauncherPage};
+use super::query_history::RecentQuery;
+use super::settings::{AppSettings, SearchSettings};
+use super::smart_folder::{self, FolderList, SmartFolder};
 use super::store::DataStore;
 use super::sync::SyncServiceState;
-use super::{Prompt, PromptIndex, PromptMetadata, SearchResult};
+use super::template::{self, Variable};
+use super::usage::{self, DailyUsage, PromptUsage, UsageAction};
+use super::{Prompt, PromptIndex, PromptMetadata, SearchContext};
 
 // ==================== Index Commands ====================
 
-/// Get the full index (all prompts metadata and folders)
+/// Prompts per page of `get_index_page` when no limit is given
+const DEFAULT_INDEX_PAGE_SIZE: usize = 100;
+
+/// Past queries `get_query_suggestions` returns when no limit is given
+const DEFAULT_QUERY_SUGGESTIONS: usize = 5;
+
+/// Get the full index (all prompts metadata and folders), with each smart
+/// folder's prompts found by running its query
+#[tauri::command]
+pub async fn get_index(store: State<'_, SyncServiceState>) -> AppResult<PromptIndex> {
+    let mut index = store.get_index().await?;
+    smart_folder::evaluate(store.inner().as_ref(), &mut index.smart_folders).await?;
+    Ok(index)
+}
+
+/// Get one page of prompt metadata (defaults: sorted by name, ascending, first 100)
 #[tauri::command]
-pub async fn get_index(store: State<'_, SyncServiceState>) -> Result<PromptIndex, String> {
-    store.get_index().await
+pub async fn get_index_page(
+    store: State<'_, SyncServiceState>,
+    sort: Option<PromptSort>,
+    direction: Option<SortDirection>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> AppResult<IndexPage> {
+    let page = Page::new(offset.unwrap_or(0), limit.unwrap_or(DEFAULT_INDEX_PAGE_SIZE));
+    store
+        .get_index_page(sort.unwrap_or_default(), direction.unwrap_or_default(), page)
+        .await
 }
 
-/// Get all folders
+/// Get all folders, and the smart folders with their current prompts
 #[tauri::command]
-pub async fn get_folders(store: State<'_, SyncServiceState>) -> Result<Vec<String>, String> {
-    store.get_folders().await
+pub async fn get_folders(store: State<'_, SyncServiceState>) -> AppResult<FolderList> {
+    let index = store.get_index().await?;
+    let mut smart_folders = index.smart_folders;
+    smart_folder::evaluate(store.inner().as_ref(), &mut smart_folders).await?;
+    Ok(FolderList { folders: index.folders, smart_folders })
 }
 
 // ==================== Prompt Commands ====================
@@ -25,7 +66,7 @@ pub async fn get_folders(store: State<'_, SyncServiceState>) -> Result<Vec<Strin
 pub async fn get_prompt(
     store: State<'_, SyncServiceState>,
     id: String,
-) -> Result<Prompt, String> {
+) -> AppResult<Prompt> {
     store.get_prompt(&id).await
 }
 
@@ -34,7 +75,7 @@ pub async fn get_prompt(
 pub async fn save_prompt(
     store: State<'_, SyncServiceState>,
     prompt: Prompt,
-) -> Result<PromptMetadata, String> {
+) -> AppResult<PromptMetadata> {
     store.save_prompt(&prompt).await
 }
 
@@ -43,7 +84,7 @@ pub async fn save_prompt(
 pub async fn delete_prompt(
     store: State<'_, SyncServiceState>,
     id: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     store.delete_prompt(&id).await
 }
 
@@ -54,7 +95,7 @@ pub async fn delete_prompt(
 pub async fn add_folder(
     store: State<'_, SyncServiceState>,
     name: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     store.add_folder(&name).await
 }
 
@@ -64,7 +105,7 @@ pub async fn rename_folder(
     store: State<'_, SyncServiceState>,
     old_name: String,
     new_name: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     store.rename_folder(&old_name, &new_name).await
 }
 
@@ -73,28 +114,242 @@ pub async fn rename_folder(
 pub async fn delete_folder(
     store: State<'_, SyncServiceState>,
     name: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     store.delete_folder(&name).await
 }
 
+/// Save a query as a smart folder, or update the one named `previous_name`
+#[tauri::command]
+pub async fn save_smart_folder(
+    store: State<'_, SyncServiceState>,
+    folder: SmartFolder,
+    previous_name: Option<String>,
+) -> AppResult<()> {
+    store.save_smart_folder(&folder, previous_name.as_deref()).await
+}
+
+/// Delete a smart folder (its prompts are untouched)
+#[tauri::command]
+pub async fn delete_smart_folder(
+    store: State<'_, SyncServiceState>,
+    name: String,
+) -> AppResult<()> {
+    store.delete_smart_folder(&name).await
+}
+
+// ==================== Template Commands ====================
+
+/// The `{{variables}}` of a prompt (its includes' too), with defaults,
+/// positions and the values recently entered for them
+#[tauri::command]
+pub async fn extract_variables(
+    store: State<'_, SyncServiceState>,
+    prompt_id: String,
+) -> AppResult<Vec<Variable>> {
+    let sync = store.inner().as_ref();
+    let mut variables = template::extract_prompt(sync, &prompt_id).await?;
+    // Suggestions are a convenience, so they don't fail the form
+    let settings = AppSettings::load().variables;
+    if let Err(e) = template::add_recent_values(sync, &prompt_id, &mut variables, &settings).await {
+        eprintln!("[template] Failed to load recent values: {}", e);
+    }
+    Ok(variables)
+}
+
+/// Forget all values entered for template variables
+#[tauri::command]
+pub async fn clear_variable_history(store: State<'_, SyncServiceState>) -> AppResult<()> {
+    store.clear_variable_history().await
+}
+
+/// The prompts that include a prompt, directly or through other fragments
+#[tauri::command]
+pub async fn get_included_by(
+    store: State<'_, SyncServiceState>,
+    prompt_id: String,
+) -> AppResult<Vec<Includer>> {
+    include::included_by(store.inner().as_ref(), &prompt_id).await
+}
+
+/// A prompt's content with its includes expanded and its variables filled in
+/// from `values`, the environment (built-ins) or their defaults. Fails if a
+/// variable has none, or an include is missing or cyclic.
+#[tauri::command]
+pub async fn render_prompt(
+    app: AppHandle,
+    store: State<'_, SyncServiceState>,
+    prompt_id: String,
+    values: Option<HashMap<String, String>>,
+) -> AppResult<String> {
+    let sync = store.inner().as_ref();
+    let env = AppEnvironment::new(&app, sync);
+    template::render_prompt(sync, &prompt_id, &values.unwrap_or_default(), &env).await
+}
+
 // ==================== Search & Stats Commands ====================
 
-/// Search prompts by query
+/// Search prompts by query, ranked for the app the launcher was opened over.
+/// Returns `limit` results (default from settings) starting at `offset`.
+/// With `explain`, each result carries its score by ranking signal.
 #[tauri::command]
 pub async fn search_prompts(
     store: State<'_, SyncServiceState>,
     query: String,
-) -> Result<Vec<SearchResult>, String> {
-    store.search_prompts(&query).await
+    offset: Option<usize>,
+    limit: Option<usize>,
+    explain: Option<bool>,
+) -> AppResult<SearchPage> {
+    let settings = AppSettings::load().search;
+    let context = search_context(&settings, explain);
+    let page = Page::new(offset.unwrap_or(0), limit.unwrap_or(settings.result_limit));
+    store.search_prompts(&query, &context, page).await
+}
+
+/// Launcher search: prompts, plus folders and actions matching the query.
+/// A query starting with `>` searches actions only.
+#[tauri::command]
+pub async fn search_launcher(
+    store: State<'_, SyncServiceState>,
+    query: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    explain: Option<bool>,
+) -> AppResult<LauncherPage> {
+    let settings = AppSettings::load().search;
+    let page = Page::new(offset.unwrap_or(0), limit.unwrap_or(settings.result_limit));
+    let signed_in = store.is_authenticated();
+
+    if let Some(action_query) = palette::action_query(&query) {
+        return Ok(palette::paginate(palette::matching_actions(action_query, signed_in), page));
+    }
+
+    // Enough prompts to fill the page whatever else ranks above them
+    let context = search_context(&settings, explain);
+    let prompts = store
+        .search_prompts(&query, &context, Page::new(0, page.offset + page.limit))
+        .await?;
+
+    let mut others = palette::matching_folders(&store.get_index().await?, &query);
+    if !query.trim().is_empty() {
+        others.extend(palette::matching_actions(&query, signed_in));
+    }
+    Ok(palette::merge(prompts, others, page))
+}
+
+/// Ranking context for a launcher search
+fn search_context(settings: &SearchSettings, explain: Option<bool>) -> SearchContext {
+    SearchContext {
+        app: previous_app::get_previous_app_key(),
+        ranking: settings.ranking,
+        explain: explain.unwrap_or(false),
+    }
 }
 
-/// Record usage of a prompt
+/// Record usage of a prompt (action defaults to a launcher paste). `query`
+/// is the launcher query it was picked from, used to rank it for that query.
 #[tauri::command]
 pub async fn record_usage(
     store: State<'_, SyncServiceState>,
     id: String,
-) -> Result<(), String> {
-    store.record_usage(&id).await
+    action: Option<UsageAction>,
+    query: Option<String>,
+) -> AppResult<()> {
+    let app = previous_app::get_previous_app_key();
+    store
+        .record_usage(&id, action.unwrap_or_default(), app.as_deref(), query.as_deref())
+        .await?;
+
+    // The query is remembered only once something was picked for it, so the
+    // paste isn't failed over history
+    if let Some(query) = query.filter(|_| AppSettings::load().search.history_enabled) {
+        if let Err(e) = store.record_query(&query).await {
+            eprintln!("[search] Failed to record query: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Forget which prompts were picked for which launcher queries
+#[tauri::command]
+pub async fn clear_selection_history(store: State<'_, SyncServiceState>) -> AppResult<()> {
+    store.clear_selection_history().await
+}
+
+/// Past launcher queries to offer for what's typed so far (the most recent
+/// ones for an empty input). Empty while search history is turned off.
+#[tauri::command]
+pub async fn get_query_suggestions(
+    store: State<'_, SyncServiceState>,
+    input: String,
+    limit: Option<usize>,
+) -> AppResult<Vec<RecentQuery>> {
+    if !AppSettings::load().search.history_enabled {
+        return Ok(Vec::new());
+    }
+    store
+        .query_suggestions(&input, limit.unwrap_or(DEFAULT_QUERY_SUGGESTIONS))
+        .await
+}
+
+/// Forget all recent launcher queries
+#[tauri::command]
+pub async fn clear_query_history(store: State<'_, SyncServiceState>) -> AppResult<()> {
+    store.clear_query_history().await
+}
+
+// ==================== Usage Analytics Commands ====================
+
+/// Most-used prompts over the last `days` days
+#[tauri::command]
+pub async fn get_top_prompts(
+    store: State<'_, SyncServiceState>,
+    days: u32,
+    limit: Option<usize>,
+) -> AppResult<Vec<PromptUsage>> {
+    let index = store.get_index().await?;
+    let events = store.get_usage_history().await?;
+    let since = Utc::now() - Duration::days(i64::from(days));
+    Ok(usage::top_prompts(&index, &events, since, limit.unwrap_or(10)))
+}
+
+/// Uses per day over the last `days` days, oldest first
+#[tauri::command]
+pub async fn get_daily_usage(
+    store: State<'_, SyncServiceState>,
+    days: u32,
+) -> AppResult<Vec<DailyUsage>> {
+    let events = store.get_usage_history().await?;
+    Ok(usage::daily_counts(&events, days, Local::now().date_naive()))
+}
+
+/// Prompts that have never been used
+#[tauri::command]
+pub async fn get_never_used_prompts(
+    store: State<'_, SyncServiceState>,
+) -> AppResult<Vec<PromptMetadata>> {
+    let index = store.get_index().await?;
+    let events = store.get_usage_history().await?;
+    Ok(usage::never_used(&index, &events))
+}
+
+/// Prompts not used in the last `days` days
+#[tauri::command]
+pub async fn get_unused_prompts(
+    store: State<'_, SyncServiceState>,
+    days: u32,
+) -> AppResult<Vec<PromptMetadata>> {
+    let index = store.get_index().await?;
+    let events = store.get_usage_history().await?;
+    let cutoff = Utc::now() - Duration::days(i64::from(days));
+    Ok(usage::unused_since(&index, &events, cutoff))
+}
+
+/// Export the usage log as CSV text
+#[tauri::command]
+pub async fn export_usage_csv(store: State<'_, SyncServiceState>) -> AppResult<String> {
+    let index = store.get_index().await?;
+    let events = store.get_usage_history().await?;
+    Ok(usage::export_csv(&index, &events))
 }
 
 // ==================== Sync Commands ====================
@@ -106,7 +361,7 @@ pub async fn set_sync_auth(
     sync: State<'_, SyncServiceState>,
     user_id: String,
     id_token: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     sync.set_auth(&user_id, &id_token);
 
     // Auto-sync from cloud after sign-in (cloud is source of truth)
@@ -129,13 +384,13 @@ pub fn update_sync_token(sync: State<'_, SyncServiceState>, id_token: String) {
 
 /// Sync local data to Firestore (upload all)
 #[tauri::command]
-pub async fn sync_to_cloud(sync: State<'_, SyncServiceState>) -> Result<(), String> {
+pub async fn sync_to_cloud(sync: State<'_, SyncServiceState>) -> AppResult<()> {
     sync.sync_to_firestore().await
 }
 
 /// Sync from Firestore to local (download all)
 #[tauri::command]
-pub async fn sync_from_cloud(sync: State<'_, SyncServiceState>) -> Result<(), String> {
+pub async fn sync_from_cloud(sync: State<'_, SyncServiceState>) -> AppResult<()> {
     sync.sync_from_firestore().await
 }
 
@@ -0,0 +1,389 @@
+//! Persistent inverted index over prompt content.
+//!
+//! Maps each content token to the prompt files containing it, so matching a
+//! query against content is a lookup instead of reading every file. The index
+//! is saved to `content_index.json` next to `index.json`. Each document keeps
+//! the stamp of the file it was built from, so files edited outside the store
+//! are re-indexed the next time the store reconciles against the disk.
+//!
+//! Documents are keyed by their path under `prompts/` (`folder/file.md`).
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::ops::{Bound, Range};
+use std::path::Path;
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::cache::FileStamp;
+use super::lock::write_atomic;
+use super::normalize::{self, Folded};
+use crate::error::{AppError, AppResult};
+
+/// Bump when the tokenizer or file format changes to force a rebuild
+const FORMAT_VERSION: u32 = 3;
+
+/// Longer tokens (base64 blobs, long URLs) are not indexed
+const MAX_TOKEN_LEN: usize = 64;
+
+/// Most index tokens a single query term may expand to as a prefix
+const MAX_PREFIX_EXPANSIONS: usize = 64;
+
+/// Quality of a query term that only matches the start of a token
+pub const PREFIX_QUALITY: f64 = 0.7;
+
+/// One indexed prompt file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Document {
+    stamp: FileStamp,
+    /// Number of tokens in the content
+    length: u32,
+    /// Token frequencies
+    terms: HashMap<String, u32>,
+}
+
+#[derive(Deserialize)]
+struct StoredIndex {
+    version: u32,
+    documents: HashMap<String, Document>,
+}
+
+#[derive(Serialize)]
+struct StoredIndexRef<'a> {
+    version: u32,
+    documents: &'a HashMap<String, Document>,
+}
+
+/// How well a document's content matched the query terms
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContentMatch {
+    /// Number of query terms found
+    pub matched: usize,
+    /// Sum of per-term quality (1.0 for a whole token, `PREFIX_QUALITY` for a prefix)
+    pub quality: f64,
+    /// Occurrences of each query term, prefix matches weighted by `PREFIX_QUALITY`
+    pub frequencies: Vec<f64>,
+    /// Number of tokens in the content
+    pub length: u32,
+}
+
+/// Inverted index from content tokens to the documents containing them
+#[derive(Default)]
+pub struct ContentIndex {
+    documents: HashMap<String, Document>,
+    /// Token -> document key -> term frequency, rebuilt from the documents on load
+    postings: BTreeMap<String, HashMap<Arc<str>, u32>>,
+    /// Sum of all document lengths
+    total_length: u64,
+    dirty: bool,
+}
+
+/// Key of a prompt file in the index
+pub fn document_key(folder: &str, filename: &str) -> String {
+    format!("{}/{}", folder, filename)
+}
+
+/// Split text into folded alphanumeric tokens (see `token_spans`)
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> {
+    token_spans(text).into_iter().map(|(_, token)| token)
+}
+
+/// Tokens of `text` with the byte range each was taken from.
+///
+/// Text is folded (see `normalize`) and split into Unicode words, which are
+/// split further at punctuation. Runs of Han and kana characters have no
+/// word breaks, so they are indexed as overlapping bigrams ("日本語" gives
+/// "日本" and "本語"); a lone character is kept as it is.
+pub fn token_spans(text: &str) -> Vec<(Range<usize>, String)> {
+    let folded = Folded::new(text);
+    let mut tokens = Vec::new();
+    // Consecutive CJK characters: (byte range in the folded text, char)
+    let mut run: Vec<(Range<usize>, char)> = Vec::new();
+
+    let flush_run = |run: &mut Vec<(Range<usize>, char)>, tokens: &mut Vec<(Range<usize>, String)>| {
+        if run.len() == 1 {
+            tokens.push((folded.original_range(run[0].0.clone()), run[0].1.to_string()));
+        }
+        for pair in run.windows(2) {
+            let range = pair[0].0.start..pair[1].0.end;
+            tokens.push((folded.original_range(range), pair.iter().map(|(_, c)| *c).collect()));
+        }
+        run.clear();
+    };
+
+    for (word_start, word) in folded.text.unicode_word_indices() {
+        let mut piece_start = None;
+        for (offset, c) in word.char_indices().chain(std::iter::once((word.len(), ' '))) {
+            let at = word_start + offset;
+            let is_cjk = normalize::is_cjk(c);
+            if let Some(start) = piece_start.filter(|_| is_cjk || !c.is_alphanumeric()) {
+                let piece = &folded.text[start..at];
+                if piece.len() <= MAX_TOKEN_LEN {
+                    flush_run(&mut run, &mut tokens);
+                    tokens.push((folded.original_range(start..at), piece.to_lowercase()));
+                }
+                piece_start = None;
+            }
+
+            if is_cjk {
+                if run.last().is_some_and(|(last, _)| last.end != at) {
+                    flush_run(&mut run, &mut tokens);
+                }
+                run.push((at..at + c.len_utf8(), c));
+            } else if c.is_alphanumeric() && piece_start.is_none() {
+                piece_start = Some(at);
+            }
+        }
+    }
+    flush_run(&mut run, &mut tokens);
+
+    tokens
+}
+
+impl ContentIndex {
+    /// Load the index from disk, starting empty if it's missing, unreadable or
+    /// from another format version
+    pub fn load(path: &Path) -> Self {
+        let stored = fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<StoredIndex>(&json).ok());
+
+        let mut index = Self::default();
+        match stored {
+            Some(stored) if stored.version == FORMAT_VERSION => {
+                for (key, document) in stored.documents {
+                    index.insert(key, document);
+                }
+            }
+            // Nothing usable on disk: write a fresh index on the next save
+            _ => index.dirty = true,
+        }
+        index
+    }
+
+    /// Write the index to disk if it changed since it was loaded or last saved
+    pub fn save(&mut self, path: &Path) -> AppResult<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let stored = StoredIndexRef {
+            version: FORMAT_VERSION,
+            documents: &self.documents,
+        };
+        let json = serde_json::to_string(&stored)
+            .map_err(|e| AppError::parse("Failed to serialize content index", e))?;
+        write_atomic(path, json.as_bytes()).map_err(|e| {
+            AppError::io("Failed to write content index", e).with_context("path", path.display())
+        })?;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Stamp of the file a document was indexed from
+    pub fn stamp(&self, key: &str) -> Option<FileStamp> {
+        self.documents.get(key).map(|document| document.stamp)
+    }
+
+    /// Index a document's content, replacing any previous version
+    pub fn update(&mut self, key: &str, stamp: FileStamp, content: &str) {
+        let mut terms: HashMap<String, u32> = HashMap::new();
+        let mut length = 0;
+        for token in tokenize(content) {
+            *terms.entry(token).or_default() += 1;
+            length += 1;
+        }
+
+        self.remove(key);
+        self.insert(key.to_string(), Document { stamp, length, terms });
+        self.dirty = true;
+    }
+
+    /// Drop a document (its file was deleted)
+    pub fn remove(&mut self, key: &str) {
+        self.take(key);
+    }
+
+    /// Move a document to a new key (its file was moved unchanged)
+    pub fn rename(&mut self, old_key: &str, new_key: &str) {
+        if let Some(document) = self.take(old_key) {
+            self.remove(new_key);
+            self.insert(new_key.to_string(), document);
+        }
+    }
+
+    /// Drop every document whose key isn't kept
+    pub fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        let gone: Vec<String> = self.documents.keys().filter(|key| !keep(key)).cloned().collect();
+        for key in gone {
+            self.remove(&key);
+        }
+    }
+
+    /// Average document length in tokens
+    pub fn average_length(&self) -> f64 {
+        if self.documents.is_empty() {
+            0.0
+        } else {
+            self.total_length as f64 / self.documents.len() as f64
+        }
+    }
+
+    /// Match query terms against the indexed content. A term matches a whole
+    /// token, or failing that the start of one, so results keep up while a
+    /// word is still being typed.
+    pub fn lookup(&self, terms: &[String]) -> HashMap<&str, ContentMatch> {
+        let mut results: HashMap<&str, ContentMatch> = HashMap::new();
+
+        for (i, term) in terms.iter().enumerate() {
+            // Per document: (best quality, weighted frequency) of this term
+            let mut found: HashMap<&str, (f64, f64)> = HashMap::new();
+            if let Some(documents) = self.postings.get(term.as_str()) {
+                found.extend(documents.iter().map(|(key, &count)| (&**key, (1.0, f64::from(count)))));
+            }
+
+            let longer = self
+                .postings
+                .range::<str, _>((Bound::Excluded(term.as_str()), Bound::Unbounded))
+                .take_while(|(token, _)| token.starts_with(term.as_str()))
+                .take(MAX_PREFIX_EXPANSIONS);
+            for (_, documents) in longer {
+                for (key, &count) in documents {
+                    let entry = found.entry(&**key).or_insert((PREFIX_QUALITY, 0.0));
+                    entry.1 += PREFIX_QUALITY * f64::from(count);
+                }
+            }
+
+            for (key, (quality, frequency)) in found {
+                let hit = results.entry(key).or_insert_with(|| ContentMatch {
+                    frequencies: vec![0.0; terms.len()],
+                    length: self.documents.get(key).map_or(0, |document| document.length),
+                    ..ContentMatch::default()
+                });
+                hit.matched += 1;
+                hit.quality += quality;
+                hit.frequencies[i] = frequency;
+            }
+        }
+
+        results
+    }
+
+    fn insert(&mut self, key: String, document: Document) {
+        let shared: Arc<str> = Arc::from(key.as_str());
+        for (term, &count) in &document.terms {
+            self.postings
+                .entry(term.clone())
+                .or_default()
+                .insert(Arc::clone(&shared), count);
+        }
+        self.total_length += u64::from(document.length);
+        self.documents.insert(key, document);
+    }
+
+    fn take(&mut self, key: &str) -> Option<Document> {
+        let document = self.documents.remove(key)?;
+        self.total_length -= u64::from(document.length);
+        for term in document.terms.keys() {
+            if let Some(documents) = self.postings.get_mut(term) {
+                documents.remove(key);
+                if documents.is_empty() {
+                    self.postings.remove(term);
+                }
+            }
+        }
+        self.dirty = true;
+        Some(document)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stamp_of(path: &Path) -> FileStamp {
+        FileStamp::of(path).unwrap()
+    }
+
+    fn temp_file(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("promptlight-content-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    #[test]
+    fn test_tokenize_splits_on_punctuation_and_lowercases() {
+        let tokens: Vec<String> = tokenize("Don't PANIC: re-read the v2 README.").collect();
+        assert_eq!(tokens, vec!["don", "t", "panic", "re", "read", "the", "v2", "readme"]);
+    }
+
+    #[test]
+    fn test_tokenize_folds_and_splits_cjk_into_bigrams() {
+        let tokens: Vec<String> = tokenize("Ｒésumé 日本語の翻訳 ok").collect();
+        assert_eq!(tokens, vec!["resume", "日本", "本語", "語の", "の翻", "翻訳", "ok"]);
+
+        let text = "見て café";
+        let spans: Vec<&str> = token_spans(text).into_iter().map(|(range, _)| &text[range]).collect();
+        assert_eq!(spans, vec!["見て", "café"]);
+        assert_eq!(tokenize("字").collect::<Vec<_>>(), vec!["字"]);
+    }
+
+    #[test]
+    fn test_lookup_matches_tokens_and_prefixes() {
+        let file = temp_file("a.md");
+        fs::write(&file, "x").unwrap();
+        let stamp = stamp_of(&file);
+
+        let mut index = ContentIndex::default();
+        index.update("coding/a.md", stamp, "Refactor the parser module");
+        index.update("writing/b.md", stamp, "Tighten the prose");
+
+        let terms = |q: &str| tokenize(q).collect::<Vec<_>>();
+        let hits = index.lookup(&terms("the parser"));
+        assert_eq!(
+            hits["coding/a.md"],
+            ContentMatch { matched: 2, quality: 2.0, frequencies: vec![1.0, 1.0], length: 4 }
+        );
+        assert_eq!(hits["writing/b.md"].matched, 1);
+        assert_eq!(index.average_length(), 3.5);
+
+        // A partially typed word matches as a prefix
+        let hits = index.lookup(&terms("pars"));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits["coding/a.md"].quality, PREFIX_QUALITY);
+
+        // Substrings inside a token don't match
+        assert!(index.lookup(&terms("factor")).is_empty());
+
+        fs::remove_dir_all(file.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_updates_renames_and_persistence() {
+        let file = temp_file("content_index.json");
+        fs::write(&file, "x").unwrap();
+        let stamp = stamp_of(&file);
+
+        let mut index = ContentIndex::default();
+        index.update("coding/a.md", stamp, "walrus walrus");
+        index.update("coding/a.md", stamp, "narwhal");
+        index.update("coding/b.md", stamp, "walrus");
+        assert!(index.lookup(&["walrus".to_string()]).contains_key("coding/b.md"));
+        assert_eq!(index.lookup(&["walrus".to_string()]).len(), 1);
+
+        index.rename("coding/a.md", "ops/a.md");
+        index.remove("coding/b.md");
+        index.save(&file).unwrap();
+
+        let reloaded = ContentIndex::load(&file);
+        assert_eq!(reloaded.stamp("ops/a.md"), Some(stamp));
+        assert_eq!(reloaded.stamp("coding/a.md"), None);
+        let hits = reloaded.lookup(&["narwhal".to_string()]);
+        assert_eq!(hits.keys().collect::<Vec<_>>(), vec![&"ops/a.md"]);
+        assert!(reloaded.lookup(&["walrus".to_string()]).is_empty());
+
+        fs::remove_dir_all(file.parent().unwrap()).ok();
+    }
+}
@@ -10,6 +10,7 @@
 //!   meta (document)
 //!     - folders: string[]
 //!     - folderMeta: map
+//!     - smartFolders: map[] (name, query, icon, color)
 //!   prompts/{prompt_id} (document)
 //!     - all prompt fields including content
 //! ```
@@ -18,7 +19,9 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::smart_folder::SmartFolder;
 use super::{FolderMetadata, Prompt, PromptIndex, PromptMetadata};
+use crate::error::{AppError, AppResult, ErrorCode};
 
 /// Firestore REST API base URL
 const FIRESTORE_BASE_URL: &str = "https://firestore.googleapis.com/v1";
@@ -57,7 +60,7 @@ impl FirestoreClient {
         &self,
         user_id: &str,
         id_token: &str,
-    ) -> Result<Vec<Prompt>, String> {
+    ) -> AppResult<Vec<Prompt>> {
         let url = format!("{}/prompts", self.user_docs_url(user_id));
 
         let response = self
@@ -66,7 +69,7 @@ impl FirestoreClient {
             .bearer_auth(id_token)
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch prompts: {}", e))?;
+            .map_err(|e| AppError::network("Failed to fetch prompts", e))?;
 
         if response.status() == 404 {
             // No prompts collection yet
@@ -74,14 +77,13 @@ impl FirestoreClient {
         }
 
         if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(format!("Firestore error: {}", error));
+            return Err(error_from_response(response).await);
         }
 
         let list_response: FirestoreListResponse = response
             .json()
             .await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
+            .map_err(|e| AppError::parse("Failed to parse response", e))?;
 
         let prompts = list_response
             .documents
@@ -99,7 +101,7 @@ impl FirestoreClient {
         &self,
         user_id: &str,
         id_token: &str,
-    ) -> Result<UserMeta, String> {
+    ) -> AppResult<UserMeta> {
         // Store meta on the user document directly (not a subdocument)
         let url = self.user_docs_url(user_id);
 
@@ -109,7 +111,7 @@ impl FirestoreClient {
             .bearer_auth(id_token)
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch meta: {}", e))?;
+            .map_err(|e| AppError::network("Failed to fetch meta", e))?;
 
         if response.status() == 404 {
             // No meta document yet, return defaults
@@ -117,14 +119,13 @@ impl FirestoreClient {
         }
 
         if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(format!("Firestore error: {}", error));
+            return Err(error_from_response(response).await);
         }
 
         let doc: FirestoreDocument = response
             .json()
             .await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
+            .map_err(|e| AppError::parse("Failed to parse response", e))?;
 
         doc.to_user_meta()
     }
@@ -135,7 +136,7 @@ impl FirestoreClient {
         user_id: &str,
         id_token: &str,
         prompt: &Prompt,
-    ) -> Result<(), String> {
+    ) -> AppResult<()> {
         let url = format!("{}/prompts/{}", self.user_docs_url(user_id), prompt.metadata.id);
 
         let doc = FirestoreDocument::from_prompt(prompt);
@@ -147,11 +148,10 @@ impl FirestoreClient {
             .json(&doc)
             .send()
             .await
-            .map_err(|e| format!("Failed to save prompt: {}", e))?;
+            .map_err(|e| AppError::network("Failed to save prompt", e))?;
 
         if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(format!("Firestore error: {}", error));
+            return Err(error_from_response(response).await);
         }
 
         Ok(())
@@ -163,7 +163,7 @@ impl FirestoreClient {
         user_id: &str,
         id_token: &str,
         prompt_id: &str,
-    ) -> Result<(), String> {
+    ) -> AppResult<()> {
         let url = format!("{}/prompts/{}", self.user_docs_url(user_id), prompt_id);
 
         let response = self
@@ -172,12 +172,11 @@ impl FirestoreClient {
             .bearer_auth(id_token)
             .send()
             .await
-            .map_err(|e| format!("Failed to delete prompt: {}", e))?;
+            .map_err(|e| AppError::network("Failed to delete prompt", e))?;
 
         // 404 is OK - prompt might not exist in Firestore
         if !response.status().is_success() && response.status() != 404 {
-            let error = response.text().await.unwrap_or_default();
-            return Err(format!("Firestore error: {}", error));
+            return Err(error_from_response(response).await);
         }
 
         Ok(())
@@ -190,7 +189,7 @@ impl FirestoreClient {
         user_id: &str,
         id_token: &str,
         meta: &UserMeta,
-    ) -> Result<(), String> {
+    ) -> AppResult<()> {
         // Store meta on the user document directly (not a subdocument)
         let url = self.user_docs_url(user_id);
 
@@ -203,11 +202,10 @@ impl FirestoreClient {
             .json(&doc)
             .send()
             .await
-            .map_err(|e| format!("Failed to save meta: {}", e))?;
+            .map_err(|e| AppError::network("Failed to save meta", e))?;
 
         if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(format!("Firestore error: {}", error));
+            return Err(error_from_response(response).await);
         }
 
         Ok(())
@@ -220,11 +218,12 @@ impl FirestoreClient {
         id_token: &str,
         index: &PromptIndex,
         prompts: &[Prompt],
-    ) -> Result<(), String> {
+    ) -> AppResult<()> {
         // Upload meta (folders)
         let meta = UserMeta {
             folders: index.folders.clone(),
             folder_meta: index.folder_meta.clone(),
+            smart_folders: index.smart_folders.clone(),
         };
         self.save_meta(user_id, id_token, &meta).await?;
 
@@ -241,7 +240,7 @@ impl FirestoreClient {
         &self,
         user_id: &str,
         id_token: &str,
-    ) -> Result<(PromptIndex, Vec<Prompt>), String> {
+    ) -> AppResult<(PromptIndex, Vec<Prompt>)> {
         let meta = self.fetch_meta(user_id, id_token).await?;
         let prompts = self.fetch_all_prompts(user_id, id_token).await?;
 
@@ -254,6 +253,7 @@ impl FirestoreClient {
             prompts: prompt_metadata,
             folders: meta.folders,
             folder_meta: meta.folder_meta,
+            smart_folders: meta.smart_folders,
             seeded: true, // Cloud users have already been seeded
         };
 
@@ -261,11 +261,25 @@ impl FirestoreClient {
     }
 }
 
+/// Convert a non-success Firestore response into a structured error.
+/// 401 means the ID token expired or was revoked, so the user must re-authenticate.
+async fn error_from_response(response: reqwest::Response) -> AppError {
+    let status = response.status();
+    let error = response.text().await.unwrap_or_default();
+    let code = if status == reqwest::StatusCode::UNAUTHORIZED {
+        ErrorCode::AuthExpired
+    } else {
+        ErrorCode::Remote
+    };
+    AppError::new(code, format!("Firestore error: {}", error)).with_context("status", status.as_u16())
+}
+
 /// User metadata stored in Firestore
 #[derive(Debug, Clone, Default)]
 pub struct UserMeta {
     pub folders: Vec<String>,
     pub folder_meta: Option<HashMap<String, FolderMetadata>>,
+    pub smart_folders: Vec<SmartFolder>,
 }
 
 // ==================== Firestore Document Types ====================
@@ -330,17 +344,39 @@ impl FirestoreDocument {
         if let Some(ref color) = prompt.metadata.color {
             fields.insert("color".to_string(), FirestoreValue::StringValue(color.clone()));
         }
+        if !prompt.metadata.tags.is_empty() {
+            let tags = prompt.metadata.tags.iter().cloned().map(FirestoreValue::StringValue).collect();
+            fields.insert(
+                "tags".to_string(),
+                FirestoreValue::ArrayValue(FirestoreArrayValue { values: Some(tags) }),
+            );
+        }
+        if prompt.metadata.pinned {
+            fields.insert("pinned".to_string(), FirestoreValue::BooleanValue(true));
+        }
+        if let Some(ref keyword) = prompt.metadata.keyword {
+            fields.insert("keyword".to_string(), FirestoreValue::StringValue(keyword.clone()));
+        }
+        if !prompt.metadata.aliases.is_empty() {
+            let aliases = prompt.metadata.aliases.iter().cloned().map(FirestoreValue::StringValue).collect();
+            fields.insert(
+                "aliases".to_string(),
+                FirestoreValue::ArrayValue(FirestoreArrayValue { values: Some(aliases) }),
+            );
+        }
 
         Self { name: None, fields }
     }
 
     /// Convert a Firestore document to a Prompt
-    fn to_prompt(&self) -> Result<Prompt, String> {
-        let get_string = |key: &str| -> Result<String, String> {
+    fn to_prompt(&self) -> AppResult<Prompt> {
+        let get_string = |key: &str| -> AppResult<String> {
             match self.fields.get(key) {
                 Some(FirestoreValue::StringValue(s)) => Ok(s.clone()),
-                Some(_) => Err(format!("Field {} is not a string", key)),
-                None => Err(format!("Missing field: {}", key)),
+                Some(_) => Err(AppError::new(ErrorCode::Parse, format!("Field {} is not a string", key))
+                    .with_context("field", key)),
+                None => Err(AppError::new(ErrorCode::Parse, format!("Missing field: {}", key))
+                    .with_context("field", key)),
             }
         };
 
@@ -351,6 +387,25 @@ impl FirestoreDocument {
             }
         };
 
+        let get_string_array = |key: &str| -> Vec<String> {
+            match self.fields.get(key) {
+                Some(FirestoreValue::ArrayValue(arr)) => arr
+                    .values
+                    .iter()
+                    .flatten()
+                    .filter_map(|v| match v {
+                        FirestoreValue::StringValue(s) => Some(s.clone()),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            }
+        };
+
+        let get_bool = |key: &str| -> bool {
+            matches!(self.fields.get(key), Some(FirestoreValue::BooleanValue(true)))
+        };
+
         let get_u32 = |key: &str| -> u32 {
             match self.fields.get(key) {
                 Some(FirestoreValue::IntegerValue(s)) => s.parse().unwrap_or(0),
@@ -370,6 +425,10 @@ impl FirestoreDocument {
             updated: get_string("updated")?,
             icon: get_optional_string("icon"),
             color: get_optional_string("color"),
+            tags: get_string_array("tags"),
+            pinned: get_bool("pinned"),
+            keyword: get_optional_string("keyword"),
+            aliases: get_string_array("aliases"),
         };
 
         let content = get_string("content").unwrap_or_default();
@@ -418,11 +477,35 @@ impl FirestoreDocument {
             );
         }
 
+        // Smart folders as an array of maps, keeping their order
+        if !meta.smart_folders.is_empty() {
+            let smart_values = meta
+                .smart_folders
+                .iter()
+                .map(|sf| {
+                    let mut sf_fields = HashMap::new();
+                    sf_fields.insert("name".to_string(), FirestoreValue::StringValue(sf.name.clone()));
+                    sf_fields.insert("query".to_string(), FirestoreValue::StringValue(sf.query.clone()));
+                    if let Some(ref icon) = sf.icon {
+                        sf_fields.insert("icon".to_string(), FirestoreValue::StringValue(icon.clone()));
+                    }
+                    if let Some(ref color) = sf.color {
+                        sf_fields.insert("color".to_string(), FirestoreValue::StringValue(color.clone()));
+                    }
+                    FirestoreValue::MapValue(FirestoreMapValue { fields: sf_fields })
+                })
+                .collect();
+            fields.insert(
+                "smartFolders".to_string(),
+                FirestoreValue::ArrayValue(FirestoreArrayValue { values: Some(smart_values) }),
+            );
+        }
+
         Self { name: None, fields }
     }
 
     /// Convert a Firestore document to UserMeta
-    fn to_user_meta(&self) -> Result<UserMeta, String> {
+    fn to_user_meta(&self) -> AppResult<UserMeta> {
         let mut folders = Vec::new();
 
         if let Some(FirestoreValue::ArrayValue(arr)) = self.fields.get("folders") {
@@ -456,12 +539,35 @@ impl FirestoreDocument {
             None
         };
 
+        let mut smart_folders = Vec::new();
+        if let Some(FirestoreValue::ArrayValue(arr)) = self.fields.get("smartFolders") {
+            for value in arr.values.iter().flatten() {
+                let FirestoreValue::MapValue(sf_map) = value else {
+                    continue;
+                };
+                let string = |key: &str| match sf_map.fields.get(key) {
+                    Some(FirestoreValue::StringValue(s)) => Some(s.clone()),
+                    _ => None,
+                };
+                // Skip entries missing a name or query rather than failing the sync
+                if let (Some(name), Some(query)) = (string("name"), string("query")) {
+                    smart_folders.push(SmartFolder {
+                        name,
+                        query,
+                        icon: string("icon"),
+                        color: string("color"),
+                        prompt_ids: None,
+                    });
+                }
+            }
+        }
+
         // Ensure default folders exist
         if folders.is_empty() {
             folders.push("uncategorized".to_string());
         }
 
-        Ok(UserMeta { folders, folder_meta })
+        Ok(UserMeta { folders, folder_meta, smart_folders })
     }
 }
 
@@ -484,6 +590,10 @@ mod tests {
                 updated: "2024-01-02T00:00:00Z".to_string(),
                 icon: Some("code".to_string()),
                 color: None,
+                tags: vec!["review".to_string()],
+                pinned: true,
+                keyword: Some(";test".to_string()),
+                aliases: vec!["Sample".to_string(), "Demo".to_string()],
             },
             content: "This is the prompt content.".to_string(),
         };
@@ -495,6 +605,10 @@ mod tests {
         assert_eq!(roundtrip.metadata.name, prompt.metadata.name);
         assert_eq!(roundtrip.metadata.folder, prompt.metadata.folder);
         assert_eq!(roundtrip.metadata.use_count, prompt.metadata.use_count);
+        assert_eq!(roundtrip.metadata.tags, prompt.metadata.tags);
+        assert!(roundtrip.metadata.pinned);
+        assert_eq!(roundtrip.metadata.keyword, prompt.metadata.keyword);
+        assert_eq!(roundtrip.metadata.aliases, prompt.metadata.aliases);
         assert_eq!(roundtrip.content, prompt.content);
     }
 
@@ -510,9 +624,18 @@ mod tests {
             },
         );
 
+        let smart_folders = vec![SmartFolder {
+            name: "Stale".to_string(),
+            query: "unused:60d".to_string(),
+            icon: Some("clock".to_string()),
+            color: None,
+            prompt_ids: None,
+        }];
+
         let meta = UserMeta {
             folders: vec!["development".to_string(), "writing".to_string()],
             folder_meta: Some(folder_meta),
+            smart_folders,
         };
 
         let doc = FirestoreDocument::from_user_meta(&meta);
@@ -520,5 +643,6 @@ mod tests {
 
         assert_eq!(roundtrip.folders, meta.folders);
         assert!(roundtrip.folder_meta.is_some());
+        assert_eq!(roundtrip.smart_folders, meta.smart_folders);
     }
 }
@@ -0,0 +1,209 @@
+//! Frecency: ranking prompts by how often and how recently they're used.
+//!
+//! Modeled on Firefox's visit-bucket frecency. The most recent uses of a prompt
+//! are sampled and each is weighted by its age bucket; the average sample weight
+//! is multiplied by the total use count. A prompt used 200 times last month thus
+//! outranks one used once an hour ago, while a burst of recent use still climbs
+//! quickly.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+use super::usage::UsageEvent;
+use super::PromptMetadata;
+
+/// Number of most recent uses sampled per prompt
+pub const SAMPLE_SIZE: usize = 10;
+
+/// (max age in days, weight) for each bucket, newest first
+const BUCKETS: [(i64, f64); 4] = [(4, 100.0), (14, 70.0), (31, 50.0), (90, 30.0)];
+
+/// Weight of a use older than the last bucket
+const OLD_WEIGHT: f64 = 10.0;
+
+/// Raw frecency at which `normalize` returns 0.5 (about five uses this week)
+const NORMALIZE_MIDPOINT: f64 = 500.0;
+
+/// Weight of a single use of the given age
+pub fn bucket_weight(age: Duration) -> f64 {
+    let days = age.num_days().max(0);
+    BUCKETS
+        .iter()
+        .find(|(max_days, _)| days <= *max_days)
+        .map_or(OLD_WEIGHT, |(_, weight)| *weight)
+}
+
+/// Raw frecency from the total use count and a sample of recent use times
+pub fn frecency(use_count: u32, recent_uses: &[DateTime<Utc>], now: DateTime<Utc>) -> f64 {
+    let sample = &recent_uses[..recent_uses.len().min(SAMPLE_SIZE)];
+    if sample.is_empty() {
+        return 0.0;
+    }
+
+    let total: f64 = sample.iter().map(|used| bucket_weight(now - *used)).sum();
+    let use_count = use_count.max(sample.len() as u32);
+    f64::from(use_count) * total / sample.len() as f64
+}
+
+/// Map raw frecency onto 0..1 so it can be mixed with other score components
+pub fn normalize(frecency: f64) -> f64 {
+    frecency / (frecency + NORMALIZE_MIDPOINT)
+}
+
+/// The most recent use times of each prompt, newest first, at most `SAMPLE_SIZE` each
+pub fn recent_uses(events: &[UsageEvent]) -> HashMap<&str, Vec<DateTime<Utc>>> {
+    let mut recent: HashMap<&str, Vec<DateTime<Utc>>> = HashMap::new();
+    // The log is append-only, so walking it backwards visits newest uses first
+    for event in events.iter().rev() {
+        let uses = recent.entry(&event.prompt_id).or_default();
+        if uses.len() < SAMPLE_SIZE {
+            if let Ok(time) = DateTime::parse_from_rfc3339(&event.timestamp) {
+                uses.push(time.with_timezone(&Utc));
+            }
+        }
+    }
+    recent
+}
+
+/// Newest use of a prompt. Prompts used before the usage log existed fall
+/// back to `last_used`, which saving also touches, so only with a use count.
+pub fn newest_use(prompt: &PromptMetadata, recent: &HashMap<&str, Vec<DateTime<Utc>>>) -> Option<DateTime<Utc>> {
+    match recent.get(prompt.id.as_str()).and_then(|uses| uses.first()) {
+        Some(newest) => Some(*newest),
+        None if prompt.use_count > 0 => prompt
+            .last_used
+            .as_deref()
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+            .map(|last| last.with_timezone(&Utc)),
+        None => None,
+    }
+}
+
+/// Frecency of a prompt. Prompts used before the usage log existed fall back
+/// to `last_used` as their only sample.
+pub fn prompt_frecency(
+    prompt: &PromptMetadata,
+    recent: &HashMap<&str, Vec<DateTime<Utc>>>,
+    now: DateTime<Utc>,
+) -> f64 {
+    match recent.get(prompt.id.as_str()) {
+        Some(uses) if !uses.is_empty() => frecency(prompt.use_count, uses, now),
+        _ => newest_use(prompt, recent).map_or(0.0, |last| frecency(prompt.use_count, &[last], now)),
+    }
+}
+
+/// Recency of a prompt in 0..1: the age bucket weight of its newest use,
+/// relative to the newest bucket
+pub fn recency(prompt: &PromptMetadata, recent: &HashMap<&str, Vec<DateTime<Utc>>>, now: DateTime<Utc>) -> f64 {
+    newest_use(prompt, recent).map_or(0.0, |last| bucket_weight(now - last) / BUCKETS[0].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::usage::UsageAction;
+
+    fn days_ago(now: DateTime<Utc>, days: i64) -> DateTime<Utc> {
+        now - Duration::days(days)
+    }
+
+    fn legacy_prompt(now: DateTime<Utc>) -> PromptMetadata {
+        PromptMetadata {
+            id: "legacy".to_string(),
+            name: "Legacy".to_string(),
+            folder: "uncategorized".to_string(),
+            description: String::new(),
+            filename: "legacy.md".to_string(),
+            use_count: 0,
+            last_used: Some(days_ago(now, 2).to_rfc3339()),
+            created: now.to_rfc3339(),
+            updated: now.to_rfc3339(),
+            icon: None,
+            color: None,
+            tags: Vec::new(),
+            pinned: false,
+            keyword: None,
+            aliases: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_bucket_boundaries() {
+        assert_eq!(bucket_weight(Duration::hours(1)), 100.0);
+        assert_eq!(bucket_weight(Duration::days(4)), 100.0);
+        assert_eq!(bucket_weight(Duration::days(5)), 70.0);
+        assert_eq!(bucket_weight(Duration::days(31)), 50.0);
+        assert_eq!(bucket_weight(Duration::days(90)), 30.0);
+        assert_eq!(bucket_weight(Duration::days(400)), 10.0);
+    }
+
+    #[test]
+    fn test_heavy_use_last_month_beats_single_recent_use() {
+        let now = Utc::now();
+        let last_month: Vec<_> = (0..SAMPLE_SIZE as i64).map(|i| days_ago(now, 20 + i)).collect();
+
+        let heavy = frecency(200, &last_month, now);
+        let once = frecency(1, &[now - Duration::hours(1)], now);
+        assert!(heavy > once, "{} should beat {}", heavy, once);
+
+        // Recency still matters at equal counts
+        assert!(frecency(5, &[days_ago(now, 1)], now) > frecency(5, &[days_ago(now, 60)], now));
+    }
+
+    #[test]
+    fn test_recent_uses_samples_newest_first() {
+        let now = Utc::now();
+        let events: Vec<UsageEvent> = (0..15)
+            .map(|i| UsageEvent {
+                prompt_id: "a".to_string(),
+                timestamp: days_ago(now, 15 - i).to_rfc3339(),
+                action: UsageAction::Paste,
+                app: None,
+            })
+            .collect();
+
+        let recent = recent_uses(&events);
+        let uses = &recent["a"];
+        assert_eq!(uses.len(), SAMPLE_SIZE);
+        assert!(uses[0] > uses[1]);
+        assert_eq!((now - uses[0]).num_days(), 1);
+    }
+
+    #[test]
+    fn test_recency_follows_newest_logged_use() {
+        let now = Utc::now();
+        let events: Vec<UsageEvent> = [60, 20]
+            .iter()
+            .map(|&days| UsageEvent {
+                prompt_id: "a".to_string(),
+                timestamp: days_ago(now, days).to_rfc3339(),
+                action: UsageAction::Paste,
+                app: None,
+            })
+            .collect();
+        let recent = recent_uses(&events);
+
+        let mut prompt = legacy_prompt(now);
+        prompt.id = "a".to_string();
+        // An edit just now doesn't make the prompt recent
+        prompt.last_used = Some(now.to_rfc3339());
+        assert_eq!(recency(&prompt, &recent, now), 0.5);
+    }
+
+    #[test]
+    fn test_prompt_without_log_falls_back_to_last_used() {
+        let now = Utc::now();
+        let mut prompt = legacy_prompt(now);
+        let recent = HashMap::new();
+
+        // Saving a prompt touches last_used, so it only counts with a use count
+        assert_eq!(prompt_frecency(&prompt, &recent, now), 0.0);
+
+        assert_eq!(recency(&prompt, &recent, now), 0.0);
+
+        prompt.use_count = 3;
+        assert_eq!(prompt_frecency(&prompt, &recent, now), 300.0);
+        assert_eq!(recency(&prompt, &recent, now), 1.0);
+        assert!(normalize(300.0) > 0.0 && normalize(300.0) < 1.0);
+    }
+}
@@ -0,0 +1,488 @@
+//! Typo-tolerant fuzzy matching for search.
+//!
+//! A field is first checked against the whole query (exact, prefix, substring).
+//! Failing that, each query term is matched on its own, preferring in order:
+//! - a substring, best at the start of a word
+//! - an fzf-style subsequence, scored for word boundaries and consecutive
+//!   characters and penalized for gaps ("sumarize", "imp wrt")
+//! - a word within a small edit distance ("summarzie")
+//!
+//! Query and fields are folded first (see `normalize`), so "resume" matches
+//! "Résumé" and full-width text matches its ASCII spelling. Matched ranges
+//! are reported in UTF-16 code units of the original text, so the launcher
+//! can slice JavaScript strings with them directly.
+
+use serde::{Deserialize, Serialize};
+
+use super::normalize::{fold_cased, fold_graphemes};
+
+// Subsequence scoring (after fzf)
+const SCORE_MATCH: i32 = 16;
+const BONUS_BOUNDARY: i32 = 8;
+const BONUS_CONSECUTIVE: i32 = 4;
+const BONUS_FIRST_CHAR_MULT: i32 = 2;
+const PENALTY_GAP_START: i32 = 3;
+const PENALTY_GAP_EXTENSION: i32 = 1;
+
+// Term match quality (0..1) by match type
+const QUALITY_WORD_SUBSTRING: f64 = 1.0;
+const QUALITY_SUBSTRING: f64 = 0.8;
+const QUALITY_SUBSEQUENCE_MAX: f64 = 0.7;
+const QUALITY_TYPO: [f64; 2] = [0.5, 0.35];
+
+/// A matched span of a field, in UTF-16 code units (`start..end`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// How a field matched the query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    /// The field equals the query
+    Exact,
+    /// The field starts with the query
+    Prefix,
+    /// The field contains the query
+    Substring,
+    /// Terms matched individually (possibly fuzzily); `matched` of `total`
+    Terms { matched: usize, total: usize },
+}
+
+/// Result of matching a query against one field
+#[derive(Debug, Clone)]
+pub struct TextMatch {
+    pub kind: MatchKind,
+    /// Mean quality of the matched terms, 0..1
+    pub quality: f64,
+    pub ranges: Vec<MatchRange>,
+}
+
+/// A search query prepared for fuzzy matching
+pub struct FuzzyQuery {
+    whole: Vec<char>,
+    terms: Vec<Vec<char>>,
+}
+
+impl FuzzyQuery {
+    pub fn new(query: &str) -> Self {
+        let folded = fold_cased(query);
+        let whole = lower_chars(folded.trim());
+        let terms = folded.split_whitespace().map(lower_chars).collect();
+        Self { whole, terms }
+    }
+
+    /// Match the query against a field's text
+    pub fn match_text(&self, text: &str) -> Option<TextMatch> {
+        if self.whole.is_empty() {
+            return None;
+        }
+        let text = Text::new(text);
+        let n = text.lower.len();
+        let whole_len = self.whole.len();
+
+        if text.lower == self.whole {
+            return Some(TextMatch::whole(MatchKind::Exact, vec![text.range(0, n)]));
+        }
+        if text.lower.starts_with(&self.whole) {
+            return Some(TextMatch::whole(MatchKind::Prefix, vec![text.range(0, whole_len)]));
+        }
+        if let Some((start, _)) = find_substring(&text, &self.whole) {
+            let ranges = vec![text.range(start, start + whole_len)];
+            return Some(TextMatch::whole(MatchKind::Substring, ranges));
+        }
+
+        let mut spans = Vec::new();
+        let mut quality = 0.0;
+        let mut matched = 0;
+        for term in &self.terms {
+            if let Some((term_quality, term_spans)) = match_term(&text, term) {
+                matched += 1;
+                quality += term_quality;
+                spans.extend(term_spans);
+            }
+        }
+        if matched == 0 {
+            return None;
+        }
+
+        // Spans in one grapheme (a ligature) map to the same original range
+        let mut ranges: Vec<MatchRange> = Vec::new();
+        for (start, end) in merge_spans(spans) {
+            let range = text.range(start, end);
+            match ranges.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => ranges.push(range),
+            }
+        }
+
+        Some(TextMatch {
+            kind: MatchKind::Terms { matched, total: self.terms.len() },
+            quality: quality / matched as f64,
+            ranges,
+        })
+    }
+}
+
+impl TextMatch {
+    fn whole(kind: MatchKind, ranges: Vec<MatchRange>) -> Self {
+        Self { kind, quality: 1.0, ranges }
+    }
+}
+
+/// A field prepared for matching, indexed by char position in the folded text
+struct Text {
+    chars: Vec<char>,
+    lower: Vec<char>,
+    /// UTF-16 range in the original text each char was folded from
+    sources: Vec<(usize, usize)>,
+}
+
+impl Text {
+    fn new(text: &str) -> Self {
+        let mut chars = Vec::with_capacity(text.len());
+        let mut sources = Vec::with_capacity(text.len());
+        for (range, folded) in fold_graphemes(text) {
+            for c in folded.chars() {
+                chars.push(c);
+                sources.push((range.start, range.end));
+            }
+        }
+        let lower = chars.iter().map(|c| lower_char(*c)).collect();
+        Self { chars, lower, sources }
+    }
+
+    /// Whether a word starts at char `i` (after a separator, or a camelCase hump)
+    fn is_boundary(&self, i: usize) -> bool {
+        i == 0
+            || !self.chars[i - 1].is_alphanumeric()
+            || (self.chars[i - 1].is_lowercase() && self.chars[i].is_uppercase())
+    }
+
+    /// Convert a non-empty char span to UTF-16 offsets in the original text
+    fn range(&self, start: usize, end: usize) -> MatchRange {
+        MatchRange { start: self.sources[start].0, end: self.sources[end - 1].1 }
+    }
+}
+
+/// Lowercase char by char, so positions line up with the original text
+fn lower_char(c: char) -> char {
+    if c.is_ascii() {
+        c.to_ascii_lowercase()
+    } else {
+        c.to_lowercase().next().unwrap_or(c)
+    }
+}
+
+fn lower_chars(s: &str) -> Vec<char> {
+    s.chars().map(lower_char).collect()
+}
+
+/// Match one term; returns its quality and matched char spans
+fn match_term(text: &Text, term: &[char]) -> Option<(f64, Vec<(usize, usize)>)> {
+    if let Some((start, at_word)) = find_substring(text, term) {
+        let quality = if at_word { QUALITY_WORD_SUBSTRING } else { QUALITY_SUBSTRING };
+        return Some((quality, vec![(start, start + term.len())]));
+    }
+
+    if term.len() >= 2 {
+        if let Some((score, positions)) = subsequence(text, term) {
+            let max_score = term.len() as i32 * SCORE_MATCH
+                + BONUS_BOUNDARY * BONUS_FIRST_CHAR_MULT
+                + (term.len() as i32 - 1) * BONUS_CONSECUTIVE;
+            // Gap penalties outweighing a quarter of the base score: too scattered
+            if score * 4 >= term.len() as i32 * SCORE_MATCH * 3 {
+                let quality = QUALITY_SUBSEQUENCE_MAX * (score as f64 / max_score as f64).min(1.0);
+                return Some((quality, positions_to_spans(&positions)));
+            }
+        }
+    }
+
+    typo_match(text, term).map(|(distance, span)| (QUALITY_TYPO[distance - 1], vec![span]))
+}
+
+/// First occurrence of `needle`, preferring one at a word start.
+/// Returns the char position and whether it is at a word start.
+fn find_substring(text: &Text, needle: &[char]) -> Option<(usize, bool)> {
+    let (n, m) = (text.lower.len(), needle.len());
+    if m == 0 || m > n {
+        return None;
+    }
+
+    let mut first = None;
+    for start in 0..=n - m {
+        if &text.lower[start..start + m] == needle {
+            if text.is_boundary(start) {
+                return Some((start, true));
+            }
+            first.get_or_insert((start, false));
+        }
+    }
+    first
+}
+
+/// Best-scoring alignment of `term` as a subsequence of the text.
+/// Returns the score and the matched char positions.
+fn subsequence(text: &Text, term: &[char]) -> Option<(i32, Vec<usize>)> {
+    // Cheap rejection before the DP
+    let mut rest = text.lower.iter();
+    if !term.iter().all(|c| rest.any(|t| t == c)) {
+        return None;
+    }
+
+    const NONE: i32 = i32::MIN / 2;
+    let (n, m) = (text.lower.len(), term.len());
+    // score[i * n + j]: best score with term[i] matched at text[j]
+    let mut score = vec![NONE; m * n];
+    let mut from = vec![usize::MAX; m * n];
+
+    for i in 0..m {
+        // Best predecessor at least one char back, with gap penalties applied
+        let mut gap_best = NONE;
+        let mut gap_from = usize::MAX;
+
+        for j in 0..n {
+            if i > 0 && j >= 2 {
+                gap_best -= PENALTY_GAP_EXTENSION;
+                let prev = score[(i - 1) * n + j - 2];
+                if prev != NONE && prev - PENALTY_GAP_START > gap_best {
+                    gap_best = prev - PENALTY_GAP_START;
+                    gap_from = j - 2;
+                }
+            }
+
+            if text.lower[j] != term[i] {
+                continue;
+            }
+
+            let bonus = if text.is_boundary(j) { BONUS_BOUNDARY } else { 0 };
+            if i == 0 {
+                score[j] = SCORE_MATCH + bonus * BONUS_FIRST_CHAR_MULT;
+                continue;
+            }
+
+            let mut best = NONE;
+            let mut best_from = usize::MAX;
+            if j >= 1 && score[(i - 1) * n + j - 1] != NONE {
+                best = score[(i - 1) * n + j - 1] + BONUS_CONSECUTIVE;
+                best_from = j - 1;
+            }
+            if gap_best > best && gap_best > NONE / 2 {
+                best = gap_best;
+                best_from = gap_from;
+            }
+            if best != NONE {
+                score[i * n + j] = SCORE_MATCH + bonus + best;
+                from[i * n + j] = best_from;
+            }
+        }
+    }
+
+    let last_row = &score[(m - 1) * n..];
+    let (mut j, best) = last_row
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, s)| *s != NONE)
+        .max_by_key(|(_, s)| *s)?;
+
+    let mut positions = vec![0; m];
+    for i in (0..m).rev() {
+        positions[i] = j;
+        if i > 0 {
+            j = from[i * n + j];
+        }
+    }
+    Some((best, positions))
+}
+
+/// Closest word within the allowed number of typos.
+/// Returns the edit distance and the word's char span.
+fn typo_match(text: &Text, term: &[char]) -> Option<(usize, (usize, usize))> {
+    let allowed = match term.len() {
+        0..=3 => return None,
+        4..=7 => 1,
+        _ => 2,
+    };
+
+    let mut best: Option<(usize, (usize, usize))> = None;
+    let mut start = 0;
+    while start < text.lower.len() {
+        if !text.lower[start].is_alphanumeric() {
+            start += 1;
+            continue;
+        }
+        let end = (start..text.lower.len())
+            .find(|&i| !text.lower[i].is_alphanumeric())
+            .unwrap_or(text.lower.len());
+        let word = &text.lower[start..end];
+
+        // Compare against the whole word, and its prefix for partially typed words
+        let whole = edit_distance(term, word, allowed);
+        let prefix = (word.len() > term.len())
+            .then(|| edit_distance(term, &word[..term.len()], allowed))
+            .flatten();
+        let distance = match (whole, prefix) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if let Some(distance) = distance {
+            if best.is_none_or(|(d, _)| distance < d) {
+                best = Some((distance, (start, end)));
+            }
+        }
+        start = end;
+    }
+    best.filter(|(d, _)| *d > 0)
+}
+
+/// Longest word considered for typo matching (keeps the DP rows on the stack)
+const MAX_TYPO_WORD: usize = 32;
+
+/// Optimal string alignment distance (Levenshtein plus adjacent transpositions),
+/// or None once it's certain to exceed `max`
+fn edit_distance(a: &[char], b: &[char], max: usize) -> Option<usize> {
+    if a.len() > MAX_TYPO_WORD || b.len() > MAX_TYPO_WORD || a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    // Three rolling rows: i-2, i-1 and i
+    let mut rows = [[0usize; MAX_TYPO_WORD + 1]; 3];
+    for (j, cell) in rows[0].iter_mut().enumerate().take(b.len() + 1) {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        let (cur, prev, prev2) = (i % 3, (i + 2) % 3, (i + 1) % 3);
+        rows[cur][0] = i;
+        let mut row_min = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut d = (rows[prev][j] + 1)
+                .min(rows[cur][j - 1] + 1)
+                .min(rows[prev][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d = d.min(rows[prev2][j - 2] + 1);
+            }
+            rows[cur][j] = d;
+            row_min = row_min.min(d);
+        }
+        if row_min > max {
+            return None;
+        }
+    }
+
+    Some(rows[a.len() % 3][b.len()]).filter(|d| *d <= max)
+}
+
+/// Group sorted char positions into contiguous spans
+fn positions_to_spans(positions: &[usize]) -> Vec<(usize, usize)> {
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    for &pos in positions {
+        match spans.last_mut() {
+            Some((_, end)) if *end == pos => *end = pos + 1,
+            _ => spans.push((pos, pos + 1)),
+        }
+    }
+    spans
+}
+
+/// Sort spans and merge any that overlap or touch
+fn merge_spans(mut spans: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    spans.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = (*last_end).max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(m: &TextMatch) -> Vec<(usize, usize)> {
+        m.ranges.iter().map(|r| (r.start, r.end)).collect()
+    }
+
+    #[test]
+    fn test_whole_query_kinds() {
+        let q = FuzzyQuery::new("Sum");
+        assert_eq!(q.match_text("sum").unwrap().kind, MatchKind::Exact);
+        assert_eq!(q.match_text("Summarize").unwrap().kind, MatchKind::Prefix);
+
+        let m = q.match_text("Quick summary").unwrap();
+        assert_eq!(m.kind, MatchKind::Substring);
+        assert_eq!(ranges(&m), vec![(6, 9)]);
+    }
+
+    #[test]
+    fn test_subsequence_with_missing_letter() {
+        let m = FuzzyQuery::new("sumarize").match_text("Summarize").unwrap();
+        assert_eq!(m.kind, MatchKind::Terms { matched: 1, total: 1 });
+        assert!(m.quality > 0.3 && m.quality <= QUALITY_SUBSEQUENCE_MAX);
+    }
+
+    #[test]
+    fn test_abbreviated_terms_match_each_word() {
+        let m = FuzzyQuery::new("imp wrt").match_text("Improve Writing").unwrap();
+        assert_eq!(m.kind, MatchKind::Terms { matched: 2, total: 2 });
+        // "Imp" + "Wr" + "t"
+        assert_eq!(ranges(&m), vec![(0, 3), (8, 10), (11, 12)]);
+    }
+
+    #[test]
+    fn test_word_boundaries_score_higher() {
+        let boundary = FuzzyQuery::new("gc").match_text("git commit").unwrap();
+        let buried = FuzzyQuery::new("gc").match_text("magic").unwrap();
+        assert!(boundary.quality > buried.quality);
+    }
+
+    #[test]
+    fn test_typo_within_edit_distance() {
+        let m = FuzzyQuery::new("summarzie").match_text("Summarize text").unwrap();
+        assert_eq!(m.quality, QUALITY_TYPO[0]);
+        assert_eq!(ranges(&m), vec![(0, 9)]);
+
+        assert!(FuzzyQuery::new("xyzzy").match_text("Summarize text").is_none());
+    }
+
+    #[test]
+    fn test_scattered_subsequence_is_rejected() {
+        let text = "the quick brown fox jumps over the lazy dog";
+        assert!(FuzzyQuery::new("tqz").match_text(text).is_none());
+    }
+
+    #[test]
+    fn test_ranges_are_utf16_offsets() {
+        // The emoji is two UTF-16 code units
+        let m = FuzzyQuery::new("review").match_text("🚀 Code review").unwrap();
+        assert_eq!(ranges(&m), vec![(8, 14)]);
+    }
+
+    #[test]
+    fn test_folded_matches_highlight_the_original() {
+        let m = FuzzyQuery::new("resume").match_text("Re\u{301}sume\u{301} tips").unwrap();
+        assert_eq!(m.kind, MatchKind::Prefix);
+        assert_eq!(ranges(&m), vec![(0, 8)]);
+
+        let m = FuzzyQuery::new("ＲＥＶＩＥＷ").match_text("Code review").unwrap();
+        assert_eq!(ranges(&m), vec![(5, 11)]);
+
+        let m = FuzzyQuery::new("翻訳").match_text("日本語の翻訳").unwrap();
+        assert_eq!(ranges(&m), vec![(4, 6)]);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let d = |a: &str, b: &str| edit_distance(&lower_chars(a), &lower_chars(b), 3);
+        assert_eq!(d("kitten", "sitting"), Some(3));
+        assert_eq!(d("form", "from"), Some(1));
+        assert_eq!(d("", "abc"), Some(3));
+        assert_eq!(edit_distance(&lower_chars("neutral"), &lower_chars("fixture"), 1), None);
+    }
+}
@@ -0,0 +1,260 @@
+//! Prompt composition: `{{> folder/prompt-name}}` and `{{> id}}` includes.
+//!
+//! Boilerplate shared by many prompts ("respond in markdown, cite sources")
+//! can live in one prompt and be included by the others. An include is
+//! replaced by the included prompt's content before variables are filled in,
+//! so a fragment's `{{variables}}` become variables of every prompt that
+//! includes it. Includes nest up to `MAX_DEPTH` levels; a prompt that ends up
+//! including itself is an error rather than an endless expansion.
+//!
+//! `included_by` answers the reverse question: which prompts would change if
+//! a fragment were edited.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use super::store::DataStore;
+use super::template::{self, Segment};
+use super::{Prompt, PromptIndex, PromptMetadata};
+use crate::error::{AppError, AppResult};
+
+/// Deepest nesting of includes (a prompt's own includes are level 1)
+pub const MAX_DEPTH: usize = 8;
+
+/// A prompt that includes another, as returned by `get_included_by`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Includer {
+    pub prompt: PromptMetadata,
+    /// Whether it includes the prompt itself, rather than through a fragment
+    /// that does
+    pub direct: bool,
+}
+
+/// The prompt an include refers to: by id, or by `folder/name` (ignoring case)
+pub fn resolve<'a>(index: &'a PromptIndex, target: &str) -> Option<&'a PromptMetadata> {
+    index.prompts.iter().find(|p| p.id == target).or_else(|| {
+        let (folder, name) = target.rsplit_once('/')?;
+        index.prompts.iter().find(|p| {
+            p.folder.eq_ignore_ascii_case(folder.trim()) && p.name.eq_ignore_ascii_case(name.trim())
+        })
+    })
+}
+
+/// A prompt's content with its includes expanded, recursively
+pub async fn expand<S: DataStore + ?Sized>(store: &S, prompt: &Prompt) -> AppResult<String> {
+    if template::includes(&prompt.content).is_empty() {
+        return Ok(prompt.content.clone());
+    }
+    let index = store.get_index().await?;
+
+    // Load every prompt reachable through includes, then expand without awaiting
+    let mut contents = HashMap::from([(prompt.metadata.id.clone(), prompt.content.clone())]);
+    let mut pending = vec![prompt.metadata.id.clone()];
+    while let Some(id) = pending.pop() {
+        let targets: Vec<String> = template::includes(&contents[&id])
+            .into_iter()
+            .filter_map(|target| resolve(&index, target))
+            .map(|p| p.id.clone())
+            .collect();
+        for target in targets {
+            if !contents.contains_key(&target) {
+                let included = store.get_prompt(&target).await?;
+                contents.insert(target.clone(), included.content);
+                pending.push(target);
+            }
+        }
+    }
+
+    let mut stack = vec![prompt.metadata.id.as_str()];
+    expand_content(&index, &contents, &prompt.content, &mut stack)
+}
+
+/// Expand `content`, the prompt on top of `stack`, whose entries are the
+/// prompts being expanded (outermost first)
+fn expand_content<'a>(
+    index: &'a PromptIndex,
+    contents: &HashMap<String, String>,
+    content: &str,
+    stack: &mut Vec<&'a str>,
+) -> AppResult<String> {
+    let mut expanded = String::with_capacity(content.len());
+    for segment in template::parse(content) {
+        match segment {
+            Segment::Text(text) => expanded.push_str(text),
+            Segment::Placeholder(placeholder) => expanded.push_str(&content[placeholder.span]),
+            Segment::Include { target, .. } => {
+                let includer = stack.last().copied().unwrap_or_default();
+                let included = resolve(index, target).ok_or_else(|| {
+                    AppError::not_found(format!("Included prompt not found: {}", target))
+                        .with_context("include", target)
+                        .with_context("promptId", includer)
+                })?;
+                if let Some(start) = stack.iter().position(|id| *id == included.id) {
+                    return Err(AppError::invalid_input("Prompt includes itself")
+                        .with_context("cycle", cycle_names(index, &stack[start..], &included.id)));
+                }
+                if stack.len() > MAX_DEPTH {
+                    return Err(AppError::invalid_input("Includes are nested too deeply")
+                        .with_context("maxDepth", MAX_DEPTH)
+                        .with_context("promptId", includer));
+                }
+
+                let included_content = contents.get(&included.id).map(String::as_str).unwrap_or_default();
+                stack.push(&included.id);
+                let result = expand_content(index, contents, included_content, stack);
+                stack.pop();
+                expanded.push_str(&result?);
+            }
+        }
+    }
+    Ok(expanded)
+}
+
+/// "A → B → A" for a cycle through the prompts in `path` back to `back_to`
+fn cycle_names(index: &PromptIndex, path: &[&str], back_to: &str) -> String {
+    let name = |id: &str| index.prompts.iter().find(|p| p.id == id).map_or(id.to_string(), |p| p.name.clone());
+    path.iter().copied().chain([back_to]).map(name).collect::<Vec<_>>().join(" → ")
+}
+
+/// Every prompt that includes `id`, directly or through other fragments:
+/// the prompts an edit to it would change. Direct includers come first,
+/// then by name.
+pub async fn included_by<S: DataStore + ?Sized>(store: &S, id: &str) -> AppResult<Vec<Includer>> {
+    let index = store.get_index().await?;
+
+    // What each prompt includes directly
+    let mut includes: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for meta in &index.prompts {
+        // A prompt whose file can't be read can't include anything
+        let Ok(prompt) = store.get_prompt(&meta.id).await else { continue };
+        let targets: HashSet<&str> = template::includes(&prompt.content)
+            .into_iter()
+            .filter_map(|target| resolve(&index, target))
+            .map(|p| p.id.as_str())
+            .collect();
+        if !targets.is_empty() {
+            includes.insert(&meta.id, targets);
+        }
+    }
+
+    Ok(includers(&index, &includes, id))
+}
+
+/// Walk up `includes` from `id`
+fn includers(index: &PromptIndex, includes: &HashMap<&str, HashSet<&str>>, id: &str) -> Vec<Includer> {
+    let mut found: Vec<Includer> = Vec::new();
+    let mut seen = HashSet::from([id]);
+    let mut pending = vec![id];
+    while let Some(current) = pending.pop() {
+        for meta in &index.prompts {
+            let includes_current = includes.get(meta.id.as_str()).is_some_and(|t| t.contains(current));
+            if includes_current && seen.insert(&meta.id) {
+                found.push(Includer { prompt: meta.clone(), direct: current == id });
+                pending.push(&meta.id);
+            }
+        }
+    }
+
+    found.sort_by(|a, b| {
+        b.direct.cmp(&a.direct).then_with(|| a.prompt.name.to_lowercase().cmp(&b.prompt.name.to_lowercase()))
+    });
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+
+    fn meta(id: &str, folder: &str, name: &str) -> PromptMetadata {
+        PromptMetadata {
+            id: id.to_string(),
+            name: name.to_string(),
+            folder: folder.to_string(),
+            description: String::new(),
+            filename: format!("{}.md", id),
+            use_count: 0,
+            last_used: None,
+            created: String::new(),
+            updated: String::new(),
+            icon: None,
+            color: None,
+            tags: Vec::new(),
+            pinned: false,
+            keyword: None,
+            aliases: Vec::new(),
+        }
+    }
+
+    /// An index and contents for prompts given as (id, folder, name, content)
+    fn library(prompts: &[(&str, &str, &str, &str)]) -> (PromptIndex, HashMap<String, String>) {
+        let index = PromptIndex {
+            prompts: prompts.iter().map(|(id, folder, name, _)| meta(id, folder, name)).collect(),
+            ..Default::default()
+        };
+        let contents = prompts.iter().map(|(id, _, _, content)| (id.to_string(), content.to_string())).collect();
+        (index, contents)
+    }
+
+    fn expand(index: &PromptIndex, contents: &HashMap<String, String>, id: &str) -> AppResult<String> {
+        expand_content(index, contents, &contents[id], &mut vec![id])
+    }
+
+    #[test]
+    fn test_expand_by_path_and_id_recursively() {
+        let (index, contents) = library(&[
+            ("main", "writing", "Review", "Review {{topic}}.\n{{> shared/Style}}"),
+            ("style", "shared", "Style", "Use markdown. {{> cite}}"),
+            ("cite", "shared", "Cite", "Cite sources in {{format|APA}}."),
+        ]);
+        assert_eq!(
+            expand(&index, &contents, "main").unwrap(),
+            "Review {{topic}}.\nUse markdown. Cite sources in {{format|APA}}."
+        );
+        // Paths ignore case
+        assert_eq!(resolve(&index, "SHARED/style").map(|p| p.id.as_str()), Some("style"));
+        assert!(resolve(&index, "style/shared").is_none());
+    }
+
+    #[test]
+    fn test_missing_cycles_and_depth_are_errors() {
+        let (index, contents) = library(&[
+            ("a", "f", "A", "{{> f/B}}"),
+            ("b", "f", "B", "{{> a}}"),
+            ("c", "f", "C", "{{> nowhere}}"),
+        ]);
+        let err = expand(&index, &contents, "a").unwrap_err();
+        assert_eq!(err.context.get("cycle").map(String::as_str), Some("A → B → A"));
+        assert_eq!(expand(&index, &contents, "c").unwrap_err().code, ErrorCode::NotFound);
+
+        let chain: Vec<(String, String)> = (0..=MAX_DEPTH + 1)
+            .map(|i| (i.to_string(), format!("{} {{{{> {}}}}}", i, i + 1)))
+            .collect();
+        let mut prompts: Vec<(&str, &str, &str, &str)> =
+            chain.iter().map(|(id, content)| (id.as_str(), "f", id.as_str(), content.as_str())).collect();
+        prompts.last_mut().unwrap().3 = "end";
+        let (index, contents) = library(&prompts);
+        assert!(expand(&index, &contents, "1").is_ok());
+        assert!(expand(&index, &contents, "0").is_err());
+    }
+
+    #[test]
+    fn test_includers_direct_first_then_transitive() {
+        let (index, _) = library(&[
+            ("cite", "shared", "Cite", ""),
+            ("style", "shared", "Style", ""),
+            ("review", "writing", "Review", ""),
+            ("blog", "writing", "Blog", ""),
+            ("other", "writing", "Other", ""),
+        ]);
+        let includes = HashMap::from([
+            ("style", HashSet::from(["cite"])),
+            ("review", HashSet::from(["style"])),
+            ("blog", HashSet::from(["cite", "style"])),
+        ]);
+        let found = includers(&index, &includes, "cite");
+        let found: Vec<(&str, bool)> = found.iter().map(|i| (i.prompt.name.as_str(), i.direct)).collect();
+        assert_eq!(found, [("Blog", true), ("Style", true), ("Review", false)]);
+    }
+}
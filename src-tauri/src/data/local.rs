@@ -1,25 +1,33 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
 use uuid::Uuid;
 
+use super::adaptive::SelectionHistory;
+use super::cache::{CachedContent, FileStamp, StoreCache, CONTENT_REVALIDATE_AFTER};
+use super::content_index::{self, ContentIndex, ContentMatch};
+use super::fuzzy::FuzzyQuery;
+use super::lock::{write_atomic, LibraryLock};
+use super::normalize::fold;
+use super::page::{self, IndexPage, Page, PromptSort, SearchPage, SortDirection};
+use super::query::{FilterContext, SearchQuery};
+use super::query_history::{QueryHistory, RecentQuery};
+use super::ranking::{self, Ranker, Score, TextRanking};
+use super::smart_folder::{self, SmartFolder};
+use super::snippet;
 use super::store::DataStore;
-use super::{create_sample_prompts, Prompt, PromptIndex, PromptMetadata, SearchResult};
-
-// Search scoring constants
-const SCORE_NAME_MATCH: f64 = 100.0;
-const SCORE_FOLDER_MATCH: f64 = 50.0;
-const SCORE_DESCRIPTION_MATCH: f64 = 30.0;
-const SCORE_CONTENT_MATCH: f64 = 15.0;
-const MULT_EXACT: f64 = 2.0;
-const MULT_PREFIX: f64 = 1.5;
-const MULT_WORD: f64 = 0.5;
-const RECENCY_MAX_SCORE: f64 = 100.0;
-const RECENCY_HALF_LIFE_HOURS: f64 = 720.0;
-const RECENCY_TIEBREAKER_MAX: f64 = 10.0;
-const NEVER_USED_PENALTY: f64 = -1000.0;
-const MAX_RESULTS: usize = 15;
+use super::template;
+use super::usage::{self, UsageAction, UsageEvent};
+use super::variable_history::VariableHistory;
+use super::{
+    create_sample_prompts, Prompt, PromptIndex, PromptMetadata, SearchContext, SearchMatches,
+    SearchResult,
+};
+use crate::error::{AppError, AppResult, ErrorCode};
 
 /// Local file-based data store implementation.
 ///
@@ -30,9 +38,61 @@ const MAX_RESULTS: usize = 15;
 /// Each directory contains:
 /// - index.json: metadata for all prompts and folder list
 /// - prompts/<folder>/<filename>.md: individual prompt content files
+/// - usage.jsonl: append-only log of every prompt use
+/// - content_index.json: inverted index over prompt content for search
+/// - selection_history.json: which prompts were picked for which queries
+/// - query_history.json: recent launcher queries
+/// - variable_history.json: values recently entered for template variables
+///
+/// The parsed index and prompt content are cached in memory and revalidated
+/// against file mtimes, so repeated searches don't touch the disk.
+///
+/// Mutations hold an exclusive lock on the data directory for the whole
+/// read-modify-write, so concurrent processes can't lose each other's writes.
 pub struct LocalDataStore {
     data_dir: PathBuf,
     user_id: Option<String>,
+    cache: StoreCache,
+    content_index: Mutex<ContentIndexState>,
+    /// Loaded on first use
+    selections: Mutex<Option<HistoryFile<SelectionHistory>>>,
+    /// Loaded on first use
+    queries: Mutex<Option<HistoryFile<QueryHistory>>>,
+    /// Loaded on first use
+    variable_values: Mutex<Option<HistoryFile<VariableHistory>>>,
+}
+
+/// A history kept in memory along with the stamp of the file it was read
+/// from, so changes saved by another instance are picked up
+struct HistoryFile<T> {
+    stamp: Option<FileStamp>,
+    history: T,
+}
+
+/// Run `f` on the history in `slot`, (re)loading it from `path` on first use
+/// or when the file changed since. Mutations hold the write lock, so what
+/// they save includes every other instance's changes.
+fn with_history_file<T, R>(
+    slot: &Mutex<Option<HistoryFile<T>>>,
+    path: &Path,
+    load: fn(&Path) -> T,
+    f: impl FnOnce(&mut T) -> R,
+) -> R {
+    let mut slot = slot.lock().unwrap_or_else(PoisonError::into_inner);
+    let stamp = FileStamp::of(path);
+    let file = match slot.take() {
+        Some(file) if file.stamp == stamp => slot.insert(file),
+        _ => slot.insert(HistoryFile { stamp, history: load(path) }),
+    };
+    f(&mut file.history)
+}
+
+/// The content index (loaded on first use) and when it was last reconciled
+/// against the prompt files on disk
+#[derive(Default)]
+struct ContentIndexState {
+    loaded: Option<ContentIndex>,
+    reconciled: Option<(Instant, Arc<PromptIndex>)>,
 }
 
 impl LocalDataStore {
@@ -42,7 +102,15 @@ impl LocalDataStore {
             .expect("Could not find home directory")
             .join(".prompt-launcher");
         let data_dir = base_dir.join("local");
-        Self { data_dir, user_id: None }
+        Self {
+            data_dir,
+            user_id: None,
+            cache: StoreCache::new(),
+            content_index: Mutex::default(),
+            selections: Mutex::default(),
+            queries: Mutex::default(),
+            variable_values: Mutex::default(),
+        }
     }
 
     /// Create a LocalDataStore for a specific authenticated user
@@ -51,7 +119,15 @@ impl LocalDataStore {
             .expect("Could not find home directory")
             .join(".prompt-launcher");
         let data_dir = base_dir.join("users").join(user_id);
-        Self { data_dir, user_id: Some(user_id.to_string()) }
+        Self {
+            data_dir,
+            user_id: Some(user_id.to_string()),
+            cache: StoreCache::new(),
+            content_index: Mutex::default(),
+            selections: Mutex::default(),
+            queries: Mutex::default(),
+            variable_values: Mutex::default(),
+        }
     }
 
     /// Get the current user ID (None for anonymous)
@@ -67,7 +143,15 @@ impl LocalDataStore {
     /// Create a LocalDataStore with a custom data directory (for testing)
     #[allow(dead_code)]
     pub fn with_data_dir(data_dir: PathBuf) -> Self {
-        Self { data_dir, user_id: None }
+        Self {
+            data_dir,
+            user_id: None,
+            cache: StoreCache::new(),
+            content_index: Mutex::default(),
+            selections: Mutex::default(),
+            queries: Mutex::default(),
+            variable_values: Mutex::default(),
+        }
     }
 
     /// Get the anonymous (pre-auth) data directory
@@ -81,7 +165,7 @@ impl LocalDataStore {
     /// Migrate data from anonymous storage to user's storage.
     /// Only migrates if the user's directory is empty (no index.json).
     /// This is called when a user first authenticates.
-    pub fn migrate_from_anonymous(&self) -> Result<bool, String> {
+    pub fn migrate_from_anonymous(&self) -> AppResult<bool> {
         // Only migrate if this is a user store and the user's directory doesn't exist
         if self.user_id.is_none() {
             return Ok(false);
@@ -101,13 +185,16 @@ impl LocalDataStore {
             return Ok(false);
         }
 
-        // Create user's data directory
-        fs::create_dir_all(&self.data_dir)
-            .map_err(|e| format!("Failed to create user directory: {}", e))?;
+        // Create user's data directory (locking creates it)
+        let _lock = LibraryLock::exclusive(&self.data_dir)?;
+        if user_index_path.exists() {
+            // Another instance migrated while we waited for the lock
+            return Ok(false);
+        }
 
         // Copy index.json
         fs::copy(&anon_index_path, &user_index_path)
-            .map_err(|e| format!("Failed to copy index: {}", e))?;
+            .map_err(|e| AppError::io("Failed to copy index", e).with_context("path", anon_index_path.display()))?;
 
         // Copy prompts directory if it exists
         let anon_prompts_dir = anon_dir.join("prompts");
@@ -115,6 +202,14 @@ impl LocalDataStore {
             copy_dir_recursive(&anon_prompts_dir, &self.prompts_dir())?;
         }
 
+        // Copy usage history so frecency carries over
+        let anon_usage_path = anon_dir.join("usage.jsonl");
+        if anon_usage_path.exists() {
+            fs::copy(&anon_usage_path, self.usage_path()).map_err(|e| {
+                AppError::io("Failed to copy usage log", e).with_context("path", anon_usage_path.display())
+            })?;
+        }
+
         Ok(true)
     }
 
@@ -128,18 +223,67 @@ impl LocalDataStore {
         self.data_dir.join("index.json")
     }
 
+    /// Get the path to the usage log
+    fn usage_path(&self) -> PathBuf {
+        self.data_dir.join("usage.jsonl")
+    }
+
+    /// Get the path to the content index
+    fn content_index_path(&self) -> PathBuf {
+        self.data_dir.join("content_index.json")
+    }
+
+    /// Get the path to the selection history
+    fn selection_history_path(&self) -> PathBuf {
+        self.data_dir.join("selection_history.json")
+    }
+
+    /// Get the path to the recent query history
+    fn query_history_path(&self) -> PathBuf {
+        self.data_dir.join("query_history.json")
+    }
+
+    /// Get the path to the recent variable values
+    fn variable_history_path(&self) -> PathBuf {
+        self.data_dir.join("variable_history.json")
+    }
+
     /// Get the path to the prompts directory
     fn prompts_dir(&self) -> PathBuf {
         self.data_dir.join("prompts")
     }
 
     /// Read prompt content from file
-    fn read_prompt_content(&self, folder: &str, filename: &str) -> Result<String, String> {
+    fn read_prompt_content(&self, folder: &str, filename: &str) -> AppResult<String> {
+        Ok(self
+            .load_prompt_content(folder, filename)?
+            .map(|cached| cached.content.to_string())
+            .unwrap_or_default())
+    }
+
+    /// Read prompt content through the cache (None if the file doesn't exist)
+    fn load_prompt_content(&self, folder: &str, filename: &str) -> AppResult<Option<CachedContent>> {
         let file_path = self.prompts_dir().join(folder).join(filename);
-        if !file_path.exists() {
-            return Ok(String::new());
+        if let Some(cached) = self.cache.get_fresh_content(&file_path) {
+            return Ok(Some(cached));
+        }
+
+        let stamp = match FileStamp::of(&file_path) {
+            Some(stamp) => stamp,
+            None => {
+                self.cache.remove_content(&file_path);
+                return Ok(None);
+            }
+        };
+
+        if let Some(cached) = self.cache.revalidate_content(&file_path, stamp) {
+            return Ok(Some(cached));
         }
-        fs::read_to_string(&file_path).map_err(|e| format!("Failed to read prompt file: {}", e))
+
+        let content = fs::read_to_string(&file_path).map_err(|e| {
+            AppError::io("Failed to read prompt file", e).with_context("path", file_path.display())
+        })?;
+        Ok(Some(self.cache.put_content(&file_path, stamp, &content)))
     }
 
     /// Write prompt content to file
@@ -148,26 +292,143 @@ impl LocalDataStore {
         folder: &str,
         filename: &str,
         content: &str,
-    ) -> Result<(), String> {
+    ) -> AppResult<()> {
         let folder_path = self.prompts_dir().join(folder);
-        fs::create_dir_all(&folder_path)
-            .map_err(|e| format!("Failed to create folder: {}", e))?;
+        fs::create_dir_all(&folder_path).map_err(|e| {
+            AppError::io("Failed to create folder", e).with_context("path", folder_path.display())
+        })?;
         let file_path = folder_path.join(filename);
-        fs::write(&file_path, content).map_err(|e| format!("Failed to write prompt file: {}", e))
+        write_atomic(&file_path, content.as_bytes()).map_err(|e| {
+            AppError::io("Failed to write prompt file", e).with_context("path", file_path.display())
+        })?;
+
+        let key = content_index::document_key(folder, filename);
+        match FileStamp::of(&file_path) {
+            Some(stamp) => {
+                self.cache.put_content(&file_path, stamp, content);
+                self.with_content_index(|index| index.update(&key, stamp, content));
+            }
+            None => {
+                self.cache.remove_content(&file_path);
+                self.with_content_index(|index| index.remove(&key));
+            }
+        }
+        Ok(())
     }
 
     /// Delete prompt content file
-    fn delete_prompt_content(&self, folder: &str, filename: &str) -> Result<(), String> {
+    fn delete_prompt_content(&self, folder: &str, filename: &str) -> AppResult<()> {
         let file_path = self.prompts_dir().join(folder).join(filename);
         if file_path.exists() {
-            fs::remove_file(&file_path)
-                .map_err(|e| format!("Failed to delete prompt file: {}", e))?;
+            fs::remove_file(&file_path).map_err(|e| {
+                AppError::io("Failed to delete prompt file", e)
+                    .with_context("path", file_path.display())
+            })?;
         }
+        self.cache.remove_content(&file_path);
+        let key = content_index::document_key(folder, filename);
+        self.with_content_index(|index| index.remove(&key));
         Ok(())
     }
 
+    /// Run `f` on the content index, loading it from disk on first use
+    fn with_content_index<R>(&self, f: impl FnOnce(&mut ContentIndex) -> R) -> R {
+        let mut state = self.content_index.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = state
+            .loaded
+            .get_or_insert_with(|| ContentIndex::load(&self.content_index_path()));
+        f(index)
+    }
+
+    /// Save the content index if it changed. It can always be rebuilt from the
+    /// prompt files, so failures are logged rather than failing the operation.
+    fn save_content_index(&self, index: &mut ContentIndex) {
+        if let Err(e) = index.save(&self.content_index_path()) {
+            eprintln!("[search] Failed to save content index: {}", e);
+        }
+    }
+
+    /// Run `f` on the selection history, loading it from disk on first use
+    /// and again whenever another instance saved it
+    fn with_selections<R>(&self, f: impl FnOnce(&mut SelectionHistory) -> R) -> R {
+        with_history_file(&self.selections, &self.selection_history_path(), SelectionHistory::load, f)
+    }
+
+    /// Save the selection history. It only refines ranking, so failures are
+    /// logged rather than failing the operation.
+    fn save_selections(&self, history: &SelectionHistory) {
+        if let Err(e) = history.save(&self.selection_history_path()) {
+            eprintln!("[search] Failed to save selection history: {}", e);
+        }
+    }
+
+    /// Run `f` on the recent query history, loading it from disk on first use
+    /// and again whenever another instance saved it
+    fn with_queries<R>(&self, f: impl FnOnce(&mut QueryHistory) -> R) -> R {
+        with_history_file(&self.queries, &self.query_history_path(), QueryHistory::load, f)
+    }
+
+    /// Run `f` on the recent variable values, loading them from disk on first
+    /// use and again whenever another instance saved them
+    fn with_variable_values<R>(&self, f: impl FnOnce(&mut VariableHistory) -> R) -> R {
+        with_history_file(&self.variable_values, &self.variable_history_path(), VariableHistory::load, f)
+    }
+
+    /// Look up query terms in the content index, first bringing it in line with
+    /// the prompt files on disk. Files are re-checked at most once per
+    /// revalidation window unless the prompt index changed. Also returns the
+    /// average content length.
+    fn content_matches(
+        &self,
+        index: &Arc<PromptIndex>,
+        terms: &[String],
+    ) -> (HashMap<String, ContentMatch>, f64) {
+        let mut guard = self.content_index.lock().unwrap_or_else(PoisonError::into_inner);
+        let state = &mut *guard;
+        let content_index = state
+            .loaded
+            .get_or_insert_with(|| ContentIndex::load(&self.content_index_path()));
+
+        let fresh = matches!(
+            &state.reconciled,
+            Some((at, seen)) if Arc::ptr_eq(seen, index) && at.elapsed() < CONTENT_REVALIDATE_AFTER
+        );
+        if !fresh {
+            let mut keys = HashSet::with_capacity(index.prompts.len());
+            for prompt in &index.prompts {
+                let path = self.prompts_dir().join(&prompt.folder).join(&prompt.filename);
+                let Some(stamp) = FileStamp::of(&path) else {
+                    continue;
+                };
+                let key = content_index::document_key(&prompt.folder, &prompt.filename);
+                // Read the file itself: the content cache may still trust an older version
+                if content_index.stamp(&key) != Some(stamp) {
+                    match fs::read_to_string(&path) {
+                        Ok(content) => content_index.update(&key, stamp, &content),
+                        Err(e) => eprintln!("[search] Failed to index {}: {}", path.display(), e),
+                    }
+                }
+                keys.insert(key);
+            }
+            content_index.retain(|key| keys.contains(key));
+            state.reconciled = Some((Instant::now(), Arc::clone(index)));
+            // Searches must not wait on a writer; a skipped save stays dirty
+            // and is written by the next reconcile or index save
+            if let Ok(_lock) = LibraryLock::try_exclusive(&self.data_dir) {
+                self.save_content_index(content_index);
+            }
+        }
+
+        let hits = content_index
+            .lookup(terms)
+            .into_iter()
+            .map(|(key, hit)| (key.to_string(), hit))
+            .collect();
+        (hits, content_index.average_length())
+    }
+
     /// Seed sample prompts for new users
-    fn seed_sample_prompts(&self) -> Result<PromptIndex, String> {
+    fn seed_sample_prompts(&self) -> AppResult<PromptIndex> {
         let (index, files) = create_sample_prompts();
 
         // Write prompt files to correct folder paths
@@ -177,67 +438,181 @@ impl LocalDataStore {
         }
 
         // Save the index
-        self.save_index_sync(&index)?;
+        self.write_index(&index)?;
 
         Ok(index)
     }
 
-    /// Synchronous index save (public for SyncService)
-    pub fn save_index_sync(&self, index: &PromptIndex) -> Result<(), String> {
-        fs::create_dir_all(&self.data_dir)
-            .map_err(|e| format!("Failed to create data directory: {}", e))?;
+    /// Take the exclusive library lock for a read-modify-write
+    fn write_lock(&self) -> AppResult<LibraryLock> {
+        LibraryLock::exclusive(&self.data_dir)
+    }
+
+    /// Synchronous index save (public for SyncService), writing the content of
+    /// `prompts` under the same lock so other instances never see new content
+    /// with the old index
+    pub fn save_index_sync(&self, index: &PromptIndex, prompts: &[Prompt]) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+
+        // Write prompt content files first; saving the index then commits
+        // the content index updated by these writes
+        for prompt in prompts {
+            self.write_prompt_content(&prompt.metadata.folder, &prompt.metadata.filename, &prompt.content)?;
+        }
+        self.write_index(index)
+    }
+
+    /// Write the index to disk and the cache (caller holds the write lock)
+    fn write_index(&self, index: &PromptIndex) -> AppResult<()> {
+        fs::create_dir_all(&self.data_dir).map_err(|e| {
+            AppError::io("Failed to create data directory", e)
+                .with_context("path", self.data_dir.display())
+        })?;
 
         let content = serde_json::to_string_pretty(index)
-            .map_err(|e| format!("Failed to serialize index: {}", e))?;
+            .map_err(|e| AppError::parse("Failed to serialize index", e))?;
+
+        let index_path = self.index_path();
+        write_atomic(&index_path, content.as_bytes()).map_err(|e| {
+            AppError::io("Failed to write index", e).with_context("path", index_path.display())
+        })?;
 
-        fs::write(self.index_path(), content).map_err(|e| format!("Failed to write index: {}", e))
+        if let Some(stamp) = FileStamp::of(&index_path) {
+            self.cache.put_index(stamp, Arc::new(index.clone()));
+        }
+
+        // Content changes made in the same operation are committed with the index
+        let mut state = self.content_index.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(content_index) = state.loaded.as_mut() {
+            self.save_content_index(content_index);
+        }
+        Ok(())
     }
 
     /// Synchronous index load (public for SyncService)
-    pub fn load_index_sync(&self) -> Result<PromptIndex, String> {
+    pub fn load_index_sync(&self) -> AppResult<PromptIndex> {
+        self.read_index().map(|index| (*index).clone())
+    }
+
+    /// Load one page of the prompt list, sorted
+    pub fn index_page_sync(
+        &self,
+        sort: PromptSort,
+        direction: SortDirection,
+        page: Page,
+    ) -> AppResult<IndexPage> {
+        let index = self.read_index()?;
+        let mut prompts: Vec<&PromptMetadata> = index.prompts.iter().collect();
+        page::sort_prompts(&mut prompts, sort, direction);
+
+        Ok(IndexPage {
+            total: prompts.len(),
+            prompts: page.slice(prompts).into_iter().cloned().collect(),
+        })
+    }
+
+    /// Load the index for reading under a shared lock.
+    ///
+    /// If another instance holds the write lock for too long, falls back to the
+    /// last index this store saw instead of failing the read.
+    fn read_index(&self) -> AppResult<Arc<PromptIndex>> {
+        let loaded = LibraryLock::shared(&self.data_dir).and_then(|_lock| self.load_index_file());
+
+        match loaded {
+            Ok(Some(index)) => Ok(index),
+            // Fresh library: seeding writes, so it needs the write lock
+            Ok(None) => {
+                let _lock = self.write_lock()?;
+                self.index_for_update().map(Arc::new)
+            }
+            Err(e) if e.code == ErrorCode::LibraryLocked => self.cache.last_index().ok_or(e),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Load an owned copy of the index for mutation, seeding a fresh library
+    /// (caller holds the write lock)
+    fn index_for_update(&self) -> AppResult<PromptIndex> {
+        match self.load_index_file()? {
+            Some(index) => Ok((*index).clone()),
+            None => self.seed_sample_prompts(),
+        }
+    }
+
+    /// Load the index through the cache, re-reading only when index.json changed.
+    /// Returns None when the library still needs to be seeded.
+    fn load_index_file(&self) -> AppResult<Option<Arc<PromptIndex>>> {
         let index_path = self.index_path();
 
-        if !index_path.exists() {
-            return self.seed_sample_prompts();
+        let stamp = match FileStamp::of(&index_path) {
+            Some(stamp) => stamp,
+            None => return Ok(None),
+        };
+
+        if let Some(index) = self.cache.get_index(stamp) {
+            return Ok(Some(index));
         }
 
-        let content =
-            fs::read_to_string(&index_path).map_err(|e| format!("Failed to read index: {}", e))?;
+        let content = fs::read_to_string(&index_path).map_err(|e| {
+            AppError::io("Failed to read index", e).with_context("path", index_path.display())
+        })?;
 
-        let index: PromptIndex =
-            serde_json::from_str(&content).map_err(|e| format!("Failed to parse index: {}", e))?;
+        let index: PromptIndex = serde_json::from_str(&content).map_err(|e| {
+            AppError::parse("Failed to parse index", e).with_context("path", index_path.display())
+        })?;
 
         // Only seed if this is a fresh install (never seeded before)
         // Don't reseed if user intentionally deleted all prompts
         if index.prompts.is_empty() && !index.seeded {
-            return self.seed_sample_prompts();
+            return Ok(None);
         }
 
-        Ok(index)
-    }
-
-    /// Write prompt content synchronously (public for SyncService)
-    pub fn write_prompt_content_sync(
-        &self,
-        folder: &str,
-        filename: &str,
-        content: &str,
-    ) -> Result<(), String> {
-        let folder_path = self.prompts_dir().join(folder);
-        fs::create_dir_all(&folder_path)
-            .map_err(|e| format!("Failed to create folder: {}", e))?;
-        let file_path = folder_path.join(filename);
-        fs::write(&file_path, content)
-            .map_err(|e| format!("Failed to write prompt file: {}", e))
+        let index = Arc::new(index);
+        self.cache.put_index(stamp, Arc::clone(&index));
+        Ok(Some(index))
     }
 
     // ==================== Sync Methods for SyncService ====================
 
+    /// Get a prompt by ID synchronously
+    pub fn get_prompt_sync(&self, id: &str) -> AppResult<Prompt> {
+        let index = self.read_index()?;
+
+        let metadata = index
+            .prompts
+            .iter()
+            .find(|p| p.id == id)
+            .ok_or_else(|| AppError::prompt_not_found(id))?
+            .clone();
+
+        let content = self.read_prompt_content(&metadata.folder, &metadata.filename)?;
+
+        Ok(Prompt { metadata, content })
+    }
+
     /// Save a prompt synchronously
-    pub fn save_prompt_sync(&self, prompt: &Prompt) -> Result<PromptMetadata, String> {
-        let mut index = self.load_index_sync()?;
+    pub fn save_prompt_sync(&self, prompt: &Prompt) -> AppResult<PromptMetadata> {
+        let _lock = self.write_lock()?;
+        let mut index = self.index_for_update()?;
         let now = Utc::now().to_rfc3339();
 
+        let (keyword, aliases) = clean_shortcuts(&prompt.metadata)?;
+        template::check_defaults(&prompt.content)?;
+        if let Some(keyword) = &keyword {
+            let folded = fold(keyword);
+            let taken = index.prompts.iter().find(|p| {
+                p.id != prompt.metadata.id && p.keyword.as_deref().is_some_and(|k| fold(k) == folded)
+            });
+            if let Some(other) = taken {
+                return Err(AppError::already_exists(format!(
+                    "Keyword \"{}\" is already used by \"{}\"",
+                    keyword, other.name
+                ))
+                .with_context("keyword", keyword)
+                .with_context("promptId", &other.id));
+            }
+        }
+
         let existing_idx = index
             .prompts
             .iter()
@@ -245,6 +620,9 @@ impl LocalDataStore {
 
         let metadata = if let Some(idx) = existing_idx {
             let mut updated = prompt.metadata.clone();
+            updated.keyword = keyword;
+            updated.aliases = aliases;
+            updated.tags = clean_names(&prompt.metadata.tags);
             updated.updated = now.clone();
             updated.last_used = Some(now);
             index.prompts[idx] = updated.clone();
@@ -274,6 +652,10 @@ impl LocalDataStore {
                 updated: now,
                 icon: prompt.metadata.icon.clone(),
                 color: prompt.metadata.color.clone(),
+                tags: clean_names(&prompt.metadata.tags),
+                pinned: prompt.metadata.pinned,
+                keyword,
+                aliases,
             };
 
             if !index.folders.contains(&new_metadata.folder) {
@@ -285,80 +667,103 @@ impl LocalDataStore {
         };
 
         self.write_prompt_content(&metadata.folder, &metadata.filename, &prompt.content)?;
-        self.save_index_sync(&index)?;
+        self.write_index(&index)?;
 
         Ok(metadata)
     }
 
     /// Delete a prompt synchronously
-    pub fn delete_prompt_sync(&self, id: &str) -> Result<(), String> {
-        let mut index = self.load_index_sync()?;
+    pub fn delete_prompt_sync(&self, id: &str) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+        let mut index = self.index_for_update()?;
 
         let idx = index
             .prompts
             .iter()
             .position(|p| p.id == id)
-            .ok_or_else(|| format!("Prompt not found: {}", id))?;
+            .ok_or_else(|| AppError::prompt_not_found(id))?;
 
         let metadata = index.prompts.remove(idx);
         self.delete_prompt_content(&metadata.folder, &metadata.filename)?;
-        self.save_index_sync(&index)?;
+        self.write_index(&index)?;
+        self.with_selections(|history| {
+            history.remove_prompt(id);
+            self.save_selections(history);
+        });
+        self.with_variable_values(|history| {
+            history.remove_prompt(id);
+            if let Err(e) = history.save(&self.variable_history_path()) {
+                eprintln!("[template] Failed to save variable history: {}", e);
+            }
+        });
 
         Ok(())
     }
 
     /// Add a folder synchronously
-    pub fn add_folder_sync(&self, name: &str) -> Result<(), String> {
-        let mut index = self.load_index_sync()?;
+    pub fn add_folder_sync(&self, name: &str) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+        let mut index = self.index_for_update()?;
 
         let folder_name = name.trim().to_lowercase();
         if folder_name.is_empty() {
-            return Err("Folder name cannot be empty".to_string());
+            return Err(AppError::invalid_input("Folder name cannot be empty"));
         }
 
-        if index.folders.contains(&folder_name) {
-            return Err("Folder already exists".to_string());
+        if index.folders.contains(&folder_name) || smart_folder::exists(&index, &folder_name) {
+            return Err(AppError::already_exists("Folder already exists")
+                .with_context("folder", &folder_name));
         }
 
         let folder_path = self.prompts_dir().join(&folder_name);
-        fs::create_dir_all(&folder_path)
-            .map_err(|e| format!("Failed to create folder directory: {}", e))?;
+        fs::create_dir_all(&folder_path).map_err(|e| {
+            AppError::io("Failed to create folder directory", e)
+                .with_context("path", folder_path.display())
+        })?;
 
         index.folders.push(folder_name);
-        self.save_index_sync(&index)?;
+        self.write_index(&index)?;
 
         Ok(())
     }
 
     /// Rename a folder synchronously
-    pub fn rename_folder_sync(&self, old_name: &str, new_name: &str) -> Result<(), String> {
-        let mut index = self.load_index_sync()?;
+    pub fn rename_folder_sync(&self, old_name: &str, new_name: &str) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+        let mut index = self.index_for_update()?;
 
         let old_folder = old_name.trim().to_lowercase();
         let new_folder = new_name.trim().to_lowercase();
 
         if new_folder.is_empty() {
-            return Err("Folder name cannot be empty".to_string());
+            return Err(AppError::invalid_input("Folder name cannot be empty"));
         }
 
         if !index.folders.contains(&old_folder) {
-            return Err("Folder does not exist".to_string());
+            return Err(AppError::folder_not_found(&old_folder));
         }
 
-        if index.folders.contains(&new_folder) {
-            return Err("A folder with that name already exists".to_string());
+        if index.folders.contains(&new_folder) || smart_folder::exists(&index, &new_folder) {
+            return Err(AppError::already_exists("A folder with that name already exists")
+                .with_context("folder", &new_folder));
         }
 
         let old_path = self.prompts_dir().join(&old_folder);
         let new_path = self.prompts_dir().join(&new_folder);
 
         if old_path.exists() {
-            fs::rename(&old_path, &new_path)
-                .map_err(|e| format!("Failed to rename folder directory: {}", e))?;
+            fs::rename(&old_path, &new_path).map_err(|e| {
+                AppError::io("Failed to rename folder directory", e)
+                    .with_context("path", old_path.display())
+            })?;
         }
+        self.cache.remove_content_under(&old_path);
 
         for prompt in &mut index.prompts {
             if prompt.folder == old_folder {
+                let old_key = content_index::document_key(&old_folder, &prompt.filename);
+                let new_key = content_index::document_key(&new_folder, &prompt.filename);
+                self.with_content_index(|content_index| content_index.rename(&old_key, &new_key));
                 prompt.folder = new_folder.clone();
             }
         }
@@ -367,30 +772,34 @@ impl LocalDataStore {
             index.folders[pos] = new_folder;
         }
 
-        self.save_index_sync(&index)?;
+        self.write_index(&index)?;
 
         Ok(())
     }
 
     /// Delete a folder synchronously
-    pub fn delete_folder_sync(&self, name: &str) -> Result<(), String> {
-        let mut index = self.load_index_sync()?;
+    pub fn delete_folder_sync(&self, name: &str) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+        let mut index = self.index_for_update()?;
 
         let folder_name = name.trim().to_lowercase();
 
         if folder_name == "uncategorized" {
-            return Err("Cannot delete the uncategorized folder".to_string());
+            return Err(AppError::invalid_input("Cannot delete the uncategorized folder")
+                .with_context("folder", &folder_name));
         }
 
         if !index.folders.contains(&folder_name) {
-            return Err("Folder does not exist".to_string());
+            return Err(AppError::folder_not_found(&folder_name));
         }
 
         let folder_path = self.prompts_dir().join(&folder_name);
         let uncategorized_path = self.prompts_dir().join("uncategorized");
 
-        fs::create_dir_all(&uncategorized_path)
-            .map_err(|e| format!("Failed to create uncategorized folder: {}", e))?;
+        fs::create_dir_all(&uncategorized_path).map_err(|e| {
+            AppError::io("Failed to create uncategorized folder", e)
+                .with_context("path", uncategorized_path.display())
+        })?;
 
         for prompt in &mut index.prompts {
             if prompt.folder == folder_name {
@@ -398,56 +807,235 @@ impl LocalDataStore {
                 let new_file = uncategorized_path.join(&prompt.filename);
 
                 if old_file.exists() {
-                    fs::rename(&old_file, &new_file)
-                        .map_err(|e| format!("Failed to move prompt file: {}", e))?;
+                    fs::rename(&old_file, &new_file).map_err(|e| {
+                        AppError::io("Failed to move prompt file", e)
+                            .with_context("path", old_file.display())
+                    })?;
                 }
+                let old_key = content_index::document_key(&folder_name, &prompt.filename);
+                let new_key = content_index::document_key("uncategorized", &prompt.filename);
+                self.with_content_index(|content_index| content_index.rename(&old_key, &new_key));
 
                 prompt.folder = "uncategorized".to_string();
             }
         }
 
         if folder_path.exists() {
-            fs::remove_dir_all(&folder_path)
-                .map_err(|e| format!("Failed to remove folder directory: {}", e))?;
+            fs::remove_dir_all(&folder_path).map_err(|e| {
+                AppError::io("Failed to remove folder directory", e)
+                    .with_context("path", folder_path.display())
+            })?;
         }
+        self.cache.remove_content_under(&folder_path);
 
         index.folders.retain(|f| f != &folder_name);
-        self.save_index_sync(&index)?;
+        self.write_index(&index)?;
 
         Ok(())
     }
 
-    /// Record usage synchronously
-    pub fn record_usage_sync(&self, id: &str) -> Result<(), String> {
-        let mut index = self.load_index_sync()?;
+    /// Create a smart folder, or replace the one named `previous`, synchronously
+    pub fn save_smart_folder_sync(&self, folder: &SmartFolder, previous: Option<&str>) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+        let mut index = self.index_for_update()?;
+        smart_folder::upsert(&mut index, folder, previous)?;
+        self.write_index(&index)
+    }
+
+    /// Delete a smart folder synchronously
+    pub fn delete_smart_folder_sync(&self, name: &str) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+        let mut index = self.index_for_update()?;
+        smart_folder::remove(&mut index, name)?;
+        self.write_index(&index)
+    }
+
+    /// Record usage synchronously. `query` is what was typed in the launcher
+    /// when the prompt was picked there.
+    pub fn record_usage_sync(
+        &self,
+        id: &str,
+        action: UsageAction,
+        app: Option<&str>,
+        query: Option<&str>,
+    ) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+        let mut index = self.index_for_update()?;
 
         let prompt = index
             .prompts
             .iter_mut()
             .find(|p| p.id == id)
-            .ok_or_else(|| format!("Prompt not found: {}", id))?;
+            .ok_or_else(|| AppError::prompt_not_found(id))?;
 
+        let event = UsageEvent::now(id, action, app.map(str::to_string));
         prompt.use_count += 1;
-        prompt.last_used = Some(Utc::now().to_rfc3339());
+        prompt.last_used = Some(event.timestamp.clone());
 
-        self.save_index_sync(&index)?;
+        self.write_index(&index)?;
+        usage::append_event(&self.usage_path(), &event)?;
+
+        // Only launcher picks say anything about what a query was looking for
+        if let Some(query) = query.filter(|_| action != UsageAction::EditorPaste) {
+            self.with_selections(|history| {
+                history.record(query, id, Utc::now());
+                self.save_selections(history);
+            });
+        }
 
         Ok(())
     }
 
+    /// Forget which prompts were picked for which queries
+    pub fn clear_selection_history_sync(&self) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+        self.with_selections(|history| {
+            history.clear();
+            history.save(&self.selection_history_path())
+        })
+    }
+
+    /// Add a launcher query to the recent query history
+    pub fn record_query_sync(&self, query: &str) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+        self.with_queries(|history| {
+            history.record(query, Utc::now());
+            history.save(&self.query_history_path())
+        })
+    }
+
+    /// Past queries to offer for what's typed so far
+    pub fn query_suggestions_sync(&self, input: &str, limit: usize) -> Vec<RecentQuery> {
+        self.with_queries(|history| history.suggestions(input, limit))
+    }
+
+    /// Forget all recent queries
+    pub fn clear_query_history_sync(&self) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+        self.with_queries(|history| {
+            history.clear();
+            history.save(&self.query_history_path())
+        })
+    }
+
+    /// Remember the values a prompt was pasted with, the last `limit` per variable
+    pub fn record_variable_values_sync(
+        &self,
+        prompt_id: &str,
+        values: &HashMap<String, String>,
+        limit: usize,
+    ) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+        self.with_variable_values(|history| {
+            history.record(prompt_id, values, limit);
+            history.save(&self.variable_history_path())
+        })
+    }
+
+    /// Up to `limit` values recently entered for each of a prompt's variables
+    /// in `names`, shared across prompts by name if `shared`
+    pub fn recent_variable_values_sync(
+        &self,
+        prompt_id: &str,
+        names: &[&str],
+        shared: bool,
+        limit: usize,
+    ) -> HashMap<String, Vec<String>> {
+        self.with_variable_values(|history| {
+            names
+                .iter()
+                .map(|name| (name.to_string(), history.recent(prompt_id, name, shared, limit)))
+                .filter(|(_, values)| !values.is_empty())
+                .collect()
+        })
+    }
+
+    /// Forget all values entered for variables
+    pub fn clear_variable_history_sync(&self) -> AppResult<()> {
+        let _lock = self.write_lock()?;
+        self.with_variable_values(|history| {
+            history.clear();
+            history.save(&self.variable_history_path())
+        })
+    }
+
+    /// Read the full usage log, oldest first
+    pub fn usage_history_sync(&self) -> AppResult<Vec<UsageEvent>> {
+        Ok(self.read_usage()?.to_vec())
+    }
+
+    /// Read the usage log through the cache. Events are appended one line at
+    /// a time and torn lines are skipped, so this doesn't take the library lock.
+    fn read_usage(&self) -> AppResult<Arc<Vec<UsageEvent>>> {
+        let path = self.usage_path();
+        let Some(stamp) = FileStamp::of(&path) else {
+            return Ok(Arc::default());
+        };
+
+        if let Some(events) = self.cache.get_usage(stamp) {
+            return Ok(events);
+        }
+
+        let events = Arc::new(usage::read_events(&path)?);
+        self.cache.put_usage(stamp, Arc::clone(&events));
+        Ok(events)
+    }
+
     /// Search prompts synchronously
-    pub fn search_prompts_sync(&self, query: &str) -> Result<Vec<SearchResult>, String> {
-        let index = self.load_index_sync()?;
-        let query_lower = query.to_lowercase();
+    pub fn search_prompts_sync(
+        &self,
+        query: &str,
+        context: &SearchContext,
+        page: Page,
+    ) -> AppResult<SearchPage> {
+        let index = self.read_index()?;
+        let parsed = SearchQuery::parse(query);
+        let query_lower = parsed.text.as_str();
+
+        let events = self.read_usage().unwrap_or_else(|e| {
+            eprintln!("[search] Failed to read usage log: {}", e);
+            Arc::default()
+        });
+
+        // Structured clauses (folder:, tag:, -term, ...) narrow the candidates
+        let filter = FilterContext { last_uses: usage::last_use_by_prompt(&events) };
+        let candidates: Vec<&PromptMetadata> = index
+            .prompts
+            .iter()
+            .filter(|prompt| {
+                parsed.accepts(prompt, &filter, || {
+                    self.load_prompt_content(&prompt.folder, &prompt.filename).ok().flatten()
+                })
+            })
+            .collect();
 
-        if query_lower.is_empty() {
-            let mut results: Vec<SearchResult> = index
-                .prompts
+        // Prompts picked before for this query (or one starting with it)
+        let selections: HashMap<String, f64> = self.with_selections(|history| {
+            history
+                .weights(query_lower, Utc::now())
                 .into_iter()
-                .map(|prompt| {
-                    let score = calculate_recency_score(&prompt);
-                    SearchResult { prompt, score }
-                })
+                .map(|(id, weight)| (id.to_string(), weight))
+                .collect()
+        });
+        let ranker = Ranker::new(
+            context.ranking,
+            query_lower.is_empty(),
+            &events,
+            context.app.as_deref(),
+            selections,
+        );
+        let result = |prompt: &PromptMetadata, score: Score, matches: SearchMatches| SearchResult {
+            prompt: prompt.clone(),
+            score: score.total(),
+            matches,
+            snippet: None,
+            explanation: context.explain.then(|| score.explain()),
+        };
+
+        if query_lower.is_empty() {
+            let mut results: Vec<SearchResult> = candidates
+                .iter()
+                .map(|prompt| result(prompt, ranker.score(prompt, 0.0), SearchMatches::default()))
                 .collect();
 
             results.sort_by(|a, b| {
@@ -462,96 +1050,99 @@ impl LocalDataStore {
                     (None, None) => std::cmp::Ordering::Equal,
                 }
             });
-            results.truncate(MAX_RESULTS);
-            return Ok(results);
+            return Ok(SearchPage {
+                total: results.len(),
+                results: page.slice(results),
+            });
         }
 
-        let mut results: Vec<SearchResult> = index
-            .prompts
-            .into_iter()
-            .filter_map(|prompt| {
-                let score = self.calculate_score(&prompt, &query_lower);
-                if score > 0.0 {
-                    Some(SearchResult { prompt, score })
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let fuzzy = FuzzyQuery::new(query_lower);
+        let terms: Vec<String> = content_index::tokenize(query_lower).collect();
+        let (content_hits, average_content_length) = self.content_matches(&index, &terms);
 
-        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
-        results.truncate(MAX_RESULTS);
+        let mut scored: Vec<(&PromptMetadata, f64, SearchMatches)> = match context.ranking.text {
+            TextRanking::Classic => candidates
+                .iter()
+                .map(|prompt| {
+                    let key = content_index::document_key(&prompt.folder, &prompt.filename);
+                    let content = content_hits.get(&key).map(|hit| (hit, terms.len()));
+                    let (score, matches) = ranking::classic_text_score(prompt, &fuzzy, content);
+                    (*prompt, score, matches)
+                })
+                .collect(),
+            TextRanking::Bm25f => {
+                ranking::bm25f_text_scores(&candidates, &terms, &content_hits, average_content_length)
+                    .into_iter()
+                    .map(|(prompt, score)| (prompt, score, SearchMatches::default()))
+                    .collect()
+            }
+        };
 
-        Ok(results)
-    }
+        // A prompt picked for this query before is listed even if its text
+        // no longer matches (e.g. after a rename)
+        for prompt in &candidates {
+            if ranker.was_selected(prompt) && !scored.iter().any(|(p, _, _)| p.id == prompt.id) {
+                scored.push((*prompt, 0.0, SearchMatches::default()));
+            }
+        }
 
-    /// Calculate match score for a prompt during search
-    fn calculate_score(&self, prompt: &PromptMetadata, query: &str) -> f64 {
-        let mut score = 0.0;
+        let mut results: Vec<SearchResult> = scored
+            .into_iter()
+            .filter(|(prompt, text, _)| *text > 0.0 || ranker.was_selected(prompt))
+            .map(|(prompt, text, matches)| result(prompt, ranker.score(prompt, text), matches))
+            .collect();
 
-        let name_lower = prompt.name.to_lowercase();
-        let folder_lower = prompt.folder.to_lowercase();
-        let desc_lower = prompt.description.to_lowercase();
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
 
-        // Exact match in name (highest priority)
-        if name_lower == query {
-            score += SCORE_NAME_MATCH * MULT_EXACT;
-        } else if name_lower.starts_with(query) {
-            score += SCORE_NAME_MATCH * MULT_PREFIX;
-        } else if name_lower.contains(query) {
-            score += SCORE_NAME_MATCH;
+        // A prompt's keyword typed exactly selects it, whatever else matched
+        let keyword = fold(query.trim());
+        let keyword_hit = index
+            .prompts
+            .iter()
+            .find(|prompt| prompt.keyword.as_deref().is_some_and(|k| fold(k) == keyword));
+        if let Some(prompt) = keyword_hit {
+            results.retain(|result| result.prompt.id != prompt.id);
+            results.insert(
+                0,
+                SearchResult {
+                    prompt: prompt.clone(),
+                    score: ranking::KEYWORD_SCORE,
+                    matches: SearchMatches::default(),
+                    snippet: None,
+                    explanation: context.explain.then(ranking::keyword_explanation),
+                },
+            );
         }
 
-        // Folder match
-        if folder_lower.contains(query) {
-            score += SCORE_FOLDER_MATCH;
-        }
+        let total = results.len();
+        let mut results = page.slice(results);
 
-        // Description match
-        if desc_lower.contains(query) {
-            score += SCORE_DESCRIPTION_MATCH;
-        }
-
-        // Fuzzy matching for partial word matches in metadata
-        if score == 0.0 {
-            let query_words: Vec<&str> = query.split_whitespace().collect();
-            for word in &query_words {
-                if name_lower.contains(word) {
-                    score += SCORE_NAME_MATCH * MULT_WORD;
-                }
-                if folder_lower.contains(word) {
-                    score += SCORE_FOLDER_MATCH * MULT_WORD;
-                }
-                if desc_lower.contains(word) {
-                    score += SCORE_DESCRIPTION_MATCH * MULT_WORD;
-                }
+        for result in &mut results {
+            // BM25F matches tokens, so the shown results are highlighted separately
+            if context.ranking.text == TextRanking::Bm25f {
+                result.matches = Self::highlight(&result.prompt, &fuzzy);
             }
-        }
 
-        // Content search as fallback (only if no metadata match)
-        if score == 0.0 {
-            if let Ok(content) = self.read_prompt_content(&prompt.folder, &prompt.filename) {
-                let content_lower = content.to_lowercase();
-                if content_lower.contains(query) {
-                    score += SCORE_CONTENT_MATCH;
-                } else {
-                    // Try word matching in content
-                    let query_words: Vec<&str> = query.split_whitespace().collect();
-                    for word in &query_words {
-                        if content_lower.contains(word) {
-                            score += SCORE_CONTENT_MATCH * MULT_WORD;
-                        }
-                    }
+            // Show where content matched, from the (usually cached) content it was scored on
+            let key = content_index::document_key(&result.prompt.folder, &result.prompt.filename);
+            if content_hits.contains_key(&key) {
+                if let Ok(Some(cached)) = self.load_prompt_content(&result.prompt.folder, &result.prompt.filename) {
+                    result.snippet = snippet::content_snippet(&cached.content, &terms);
                 }
             }
         }
 
-        // Recency tiebreaker (only if we have a match)
-        if score > 0.0 {
-            score += calculate_recency_tiebreaker(prompt);
-        }
+        Ok(SearchPage { results, total })
+    }
 
-        score
+    /// Matched ranges of each metadata field
+    fn highlight(prompt: &PromptMetadata, fuzzy: &FuzzyQuery) -> SearchMatches {
+        let ranges = |text: &str| fuzzy.match_text(text).map(|m| m.ranges).unwrap_or_default();
+        SearchMatches {
+            name: ranges(&prompt.name),
+            folder: ranges(&prompt.folder),
+            description: ranges(&prompt.description),
+        }
     }
 }
 
@@ -563,364 +1154,903 @@ impl Default for LocalDataStore {
 
 #[async_trait]
 impl DataStore for LocalDataStore {
-    async fn get_index(&self) -> Result<PromptIndex, String> {
+    async fn get_index(&self) -> AppResult<PromptIndex> {
         self.load_index_sync()
     }
 
-    async fn save_index(&self, index: &PromptIndex) -> Result<(), String> {
-        self.save_index_sync(index)
+    async fn get_index_page(
+        &self,
+        sort: PromptSort,
+        direction: SortDirection,
+        page: Page,
+    ) -> AppResult<IndexPage> {
+        self.index_page_sync(sort, direction, page)
     }
 
-    async fn get_prompt(&self, id: &str) -> Result<Prompt, String> {
-        let index = self.load_index_sync()?;
+    async fn get_prompt(&self, id: &str) -> AppResult<Prompt> {
+        self.get_prompt_sync(id)
+    }
 
-        let metadata = index
-            .prompts
-            .iter()
-            .find(|p| p.id == id)
-            .ok_or_else(|| format!("Prompt not found: {}", id))?
-            .clone();
+    async fn save_prompt(&self, prompt: &Prompt) -> AppResult<PromptMetadata> {
+        self.save_prompt_sync(prompt)
+    }
 
-        let content = self.read_prompt_content(&metadata.folder, &metadata.filename)?;
+    async fn delete_prompt(&self, id: &str) -> AppResult<()> {
+        self.delete_prompt_sync(id)
+    }
 
-        Ok(Prompt { metadata, content })
+    async fn add_folder(&self, name: &str) -> AppResult<()> {
+        self.add_folder_sync(name)
     }
 
-    async fn save_prompt(&self, prompt: &Prompt) -> Result<PromptMetadata, String> {
-        let mut index = self.load_index_sync()?;
-        let now = Utc::now().to_rfc3339();
+    async fn rename_folder(&self, old_name: &str, new_name: &str) -> AppResult<()> {
+        self.rename_folder_sync(old_name, new_name)
+    }
 
-        // Check if this is an update or create
-        let existing_idx = index
-            .prompts
-            .iter()
-            .position(|p| p.id == prompt.metadata.id);
+    async fn delete_folder(&self, name: &str) -> AppResult<()> {
+        self.delete_folder_sync(name)
+    }
 
-        let metadata = if let Some(idx) = existing_idx {
-            // Update existing
-            let mut updated = prompt.metadata.clone();
-            updated.updated = now.clone();
-            // Set last_used on edit so edited prompts appear at top of recency list
-            updated.last_used = Some(now);
-            index.prompts[idx] = updated.clone();
-            updated
+    async fn save_smart_folder(&self, folder: &SmartFolder, previous: Option<&str>) -> AppResult<()> {
+        self.save_smart_folder_sync(folder, previous)
+    }
+
+    async fn delete_smart_folder(&self, name: &str) -> AppResult<()> {
+        self.delete_smart_folder_sync(name)
+    }
+
+    async fn record_usage(
+        &self,
+        id: &str,
+        action: UsageAction,
+        app: Option<&str>,
+        query: Option<&str>,
+    ) -> AppResult<()> {
+        self.record_usage_sync(id, action, app, query)
+    }
+
+    async fn clear_selection_history(&self) -> AppResult<()> {
+        self.clear_selection_history_sync()
+    }
+
+    async fn get_usage_history(&self) -> AppResult<Vec<UsageEvent>> {
+        self.usage_history_sync()
+    }
+
+    async fn search_prompts(
+        &self,
+        query: &str,
+        context: &SearchContext,
+        page: Page,
+    ) -> AppResult<SearchPage> {
+        self.search_prompts_sync(query, context, page)
+    }
+
+    async fn record_query(&self, query: &str) -> AppResult<()> {
+        self.record_query_sync(query)
+    }
+
+    async fn query_suggestions(&self, input: &str, limit: usize) -> AppResult<Vec<RecentQuery>> {
+        Ok(self.query_suggestions_sync(input, limit))
+    }
+
+    async fn clear_query_history(&self) -> AppResult<()> {
+        self.clear_query_history_sync()
+    }
+
+    async fn record_variable_values(
+        &self,
+        prompt_id: &str,
+        values: &HashMap<String, String>,
+        limit: usize,
+    ) -> AppResult<()> {
+        self.record_variable_values_sync(prompt_id, values, limit)
+    }
+
+    async fn recent_variable_values(
+        &self,
+        prompt_id: &str,
+        names: &[&str],
+        shared: bool,
+        limit: usize,
+    ) -> AppResult<HashMap<String, Vec<String>>> {
+        Ok(self.recent_variable_values_sync(prompt_id, names, shared, limit))
+    }
+
+    async fn clear_variable_history(&self) -> AppResult<()> {
+        self.clear_variable_history_sync()
+    }
+}
+
+/// Trimmed keyword (None if blank) and aliases (see `clean_names`)
+fn clean_shortcuts(metadata: &PromptMetadata) -> AppResult<(Option<String>, Vec<String>)> {
+    let keyword = metadata
+        .keyword
+        .as_deref()
+        .map(str::trim)
+        .filter(|keyword| !keyword.is_empty())
+        .map(str::to_string);
+    if keyword.as_deref().is_some_and(|keyword| keyword.contains(char::is_whitespace)) {
+        return Err(AppError::invalid_input("A keyword can't contain spaces")
+            .with_context("promptId", &metadata.id));
+    }
+
+    Ok((keyword, clean_names(&metadata.aliases)))
+}
+
+/// Trimmed names (aliases or tags), blanks and duplicates (ignoring case) dropped
+fn clean_names(names: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    names
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty() && seen.insert(fold(name)))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Convert a name to a filename-safe slug
+fn slugify(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Recursively copy a directory and its contents
+fn copy_dir_recursive(src: &PathBuf, dst: &PathBuf) -> AppResult<()> {
+    fs::create_dir_all(dst).map_err(|e| {
+        AppError::io("Failed to create directory", e).with_context("path", dst.display())
+    })?;
+
+    for entry in fs::read_dir(src).map_err(|e| {
+        AppError::io("Failed to read directory", e).with_context("path", src.display())
+    })? {
+        let entry = entry.map_err(|e| AppError::io("Failed to read entry", e))?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+
+        if src_path.is_dir() {
+            copy_dir_recursive(&src_path, &dst_path)?;
         } else {
-            // Create new
-            let id = if prompt.metadata.id.is_empty() {
-                Uuid::new_v4().to_string()
-            } else {
-                prompt.metadata.id.clone()
-            };
+            fs::copy(&src_path, &dst_path).map_err(|e| {
+                AppError::io("Failed to copy file", e)
+                    .with_context("path", src_path.display())
+                    .with_context("destination", dst_path.display())
+            })?;
+        }
+    }
 
-            let filename = if prompt.metadata.filename.is_empty() {
-                format!("{}.md", slugify(&prompt.metadata.name))
-            } else {
-                prompt.metadata.filename.clone()
-            };
+    Ok(())
+}
 
-            let new_metadata = PromptMetadata {
-                id,
-                name: prompt.metadata.name.clone(),
-                folder: prompt.metadata.folder.clone(),
-                description: prompt.metadata.description.clone(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::fuzzy::MatchRange;
+    use crate::data::ranking::{RankingProfile, Signal};
+    use crate::data::settings::DEFAULT_RESULT_LIMIT;
+    use std::time::{Duration, Instant};
+
+    /// Create a store in a fresh temp directory
+    fn temp_store() -> LocalDataStore {
+        let dir = std::env::temp_dir().join(format!("promptlight-test-{}", Uuid::new_v4()));
+        LocalDataStore::with_data_dir(dir)
+    }
+
+    /// Write a library of `count` prompts directly to disk (bypassing the store)
+    fn write_fixture(store: &LocalDataStore, count: usize) {
+        let folders = ["coding", "writing", "research", "ops", "personal"];
+        let now = Utc::now();
+        let mut index = PromptIndex {
+            folders: folders.iter().map(|f| f.to_string()).collect(),
+            seeded: true,
+            ..PromptIndex::default()
+        };
+
+        for i in 0..count {
+            let folder = folders[i % folders.len()];
+            let filename = format!("prompt-{}.md", i);
+            let folder_path = store.prompts_dir().join(folder);
+            fs::create_dir_all(&folder_path).unwrap();
+            fs::write(
+                folder_path.join(&filename),
+                format!(
+                    "You are assistant number {}. Summarize the following text, \
+                     keeping the tone neutral and the length under {} words.",
+                    i,
+                    50 + i % 200
+                ),
+            )
+            .unwrap();
+
+            let ts = (now - chrono::Duration::hours(i as i64)).to_rfc3339();
+            index.prompts.push(PromptMetadata {
+                id: format!("id-{}", i),
+                name: format!("Prompt {}", i),
+                folder: folder.to_string(),
+                description: format!("Fixture prompt {}", i),
                 filename,
-                use_count: 0,
-                // Set last_used on create so new prompts appear at top of recency list
-                last_used: Some(now.clone()),
-                created: now.clone(),
-                updated: now,
-                icon: prompt.metadata.icon.clone(),
-                color: prompt.metadata.color.clone(),
-            };
+                use_count: (i % 7) as u32,
+                last_used: Some(ts.clone()),
+                created: ts.clone(),
+                updated: ts,
+                icon: None,
+                color: None,
+                tags: Vec::new(),
+                pinned: false,
+                keyword: None,
+                aliases: Vec::new(),
+            });
+        }
 
-            // Ensure folder exists in index
-            if !index.folders.contains(&new_metadata.folder) {
-                index.folders.push(new_metadata.folder.clone());
-            }
+        fs::create_dir_all(store.data_dir()).unwrap();
+        fs::write(store.index_path(), serde_json::to_string(&index).unwrap()).unwrap();
+    }
 
-            index.prompts.push(new_metadata.clone());
-            new_metadata
-        };
+    fn search(store: &LocalDataStore, query: &str) -> Vec<SearchResult> {
+        search_with(store, query, &SearchContext::default())
+    }
 
-        // Write content to file
-        self.write_prompt_content(&metadata.folder, &metadata.filename, &prompt.content)?;
+    fn ranked_by(text: TextRanking) -> SearchContext {
+        SearchContext { ranking: RankingProfile { text, ..RankingProfile::default() }, ..SearchContext::default() }
+    }
+
+    fn search_with(store: &LocalDataStore, query: &str, context: &SearchContext) -> Vec<SearchResult> {
+        let page = Page::new(0, DEFAULT_RESULT_LIMIT);
+        store.search_prompts_sync(query, context, page).unwrap().results
+    }
 
-        // Save index
-        self.save_index_sync(&index)?;
+    #[test]
+    fn test_search_reflects_store_writes() {
+        let store = temp_store();
+        write_fixture(&store, 3);
 
-        Ok(metadata)
+        // Warm the cache
+        assert!(search(&store, "walrus").is_empty());
+
+        let mut prompt = store.get_prompt_sync("id-1").unwrap();
+        prompt.content = "Talk like a walrus".to_string();
+        store.save_prompt_sync(&prompt).unwrap();
+
+        let results = search(&store, "walrus");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].prompt.id, "id-1");
+
+        fs::remove_dir_all(store.data_dir()).ok();
     }
 
-    async fn delete_prompt(&self, id: &str) -> Result<(), String> {
-        let mut index = self.load_index_sync()?;
+    #[test]
+    fn test_search_skips_content_index_save_while_locked() {
+        let store = temp_store();
+        write_fixture(&store, 3);
+        store.load_index_sync().unwrap();
+        fs::remove_file(store.content_index_path()).ok();
 
-        let idx = index
-            .prompts
-            .iter()
-            .position(|p| p.id == id)
-            .ok_or_else(|| format!("Prompt not found: {}", id))?;
+        // Another instance is mid-write: search still answers, but leaves the file alone
+        let held = LibraryLock::exclusive(store.data_dir()).unwrap();
+        assert_eq!(search(&store, "assistant").len(), 3);
+        assert!(!store.content_index_path().exists());
+        drop(held);
 
-        let metadata = index.prompts.remove(idx);
+        fs::remove_dir_all(store.data_dir()).ok();
+    }
 
-        // Delete the file
-        self.delete_prompt_content(&metadata.folder, &metadata.filename)?;
+    #[test]
+    fn test_content_index_follows_moves_and_persists() {
+        let store = temp_store();
+        write_fixture(&store, 3);
+
+        let mut prompt = store.get_prompt_sync("id-0").unwrap();
+        prompt.content = "Talk like a walrus".to_string();
+        store.save_prompt_sync(&prompt).unwrap();
+        store.rename_folder_sync("coding", "code").unwrap();
+
+        let results = search(&store, "walrus");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].prompt.folder, "code");
+        // Content-only matches explain themselves with a snippet
+        let snippet = results[0].snippet.as_ref().unwrap();
+        assert_eq!(snippet.text, "Talk like a walrus");
+        assert_eq!(snippet.highlights, [MatchRange { start: 12, end: 18 }]);
+
+        // A new store (next launch) starts from the saved index
+        assert!(store.content_index_path().exists());
+        let reopened = LocalDataStore::with_data_dir(store.data_dir().clone());
+        assert_eq!(search(&reopened, "walr").len(), 1);
+
+        reopened.delete_prompt_sync("id-0").unwrap();
+        assert!(search(&reopened, "walrus").is_empty());
+        let saved = fs::read_to_string(store.content_index_path()).unwrap();
+        assert!(!saved.contains("walrus"));
+
+        fs::remove_dir_all(store.data_dir()).ok();
+    }
 
-        self.save_index_sync(&index)?;
+    #[test]
+    fn test_cache_picks_up_external_changes() {
+        let store = temp_store();
+        write_fixture(&store, 3);
 
-        Ok(())
+        assert_eq!(store.load_index_sync().unwrap().prompts.len(), 3);
+        assert!(search(&store, "walrus").is_empty());
+
+        // Another process edits a prompt file and rewrites the index
+        let file = store.prompts_dir().join("coding").join("prompt-0.md");
+        fs::write(&file, "An external walrus edit").unwrap();
+        // Content is trusted for a short window before being re-checked
+        std::thread::sleep(Duration::from_millis(1100));
+
+        let mut index: PromptIndex =
+            serde_json::from_str(&fs::read_to_string(store.index_path()).unwrap()).unwrap();
+        index.prompts.pop();
+        fs::write(store.index_path(), serde_json::to_string_pretty(&index).unwrap()).unwrap();
+
+        assert_eq!(store.load_index_sync().unwrap().prompts.len(), 2);
+        assert_eq!(store.get_prompt_sync("id-0").unwrap().content, "An external walrus edit");
+        assert_eq!(search(&store, "walrus").len(), 1);
+
+        fs::remove_dir_all(store.data_dir()).ok();
     }
 
-    async fn add_folder(&self, name: &str) -> Result<(), String> {
-        let mut index = self.load_index_sync()?;
+    #[test]
+    fn test_delete_folder_drops_cached_content() {
+        let store = temp_store();
+        write_fixture(&store, 5);
 
-        let folder_name = name.trim().to_lowercase();
-        if folder_name.is_empty() {
-            return Err("Folder name cannot be empty".to_string());
-        }
+        let before = store.get_prompt_sync("id-0").unwrap().content;
+        store.delete_folder_sync("coding").unwrap();
+
+        let after = store.get_prompt_sync("id-0").unwrap();
+        assert_eq!(after.metadata.folder, "uncategorized");
+        assert_eq!(after.content, before);
+
+        fs::remove_dir_all(store.data_dir()).ok();
+    }
 
-        if index.folders.contains(&folder_name) {
-            return Err("Folder already exists".to_string());
+    #[test]
+    fn test_concurrent_stores_do_not_lose_writes() {
+        let dir = temp_store().data_dir().clone();
+        write_fixture(&LocalDataStore::with_data_dir(dir.clone()), 1);
+
+        // Each thread models a separate process with its own store and cache
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let dir = dir.clone();
+                std::thread::spawn(move || {
+                    let store = LocalDataStore::with_data_dir(dir);
+                    for _ in 0..10 {
+                        store.record_usage_sync("id-0", UsageAction::Paste, None, None).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
         }
 
-        // Create the folder directory
-        let folder_path = self.prompts_dir().join(&folder_name);
-        fs::create_dir_all(&folder_path)
-            .map_err(|e| format!("Failed to create folder directory: {}", e))?;
+        let store = LocalDataStore::with_data_dir(dir.clone());
+        let index = store.load_index_sync().unwrap();
+        assert_eq!(index.prompts[0].use_count, 40);
 
-        // Add to index and save
-        index.folders.push(folder_name);
-        self.save_index_sync(&index)?;
+        fs::remove_dir_all(&dir).ok();
+    }
 
-        Ok(())
+    #[test]
+    fn test_stores_share_selection_history() {
+        let dir = temp_store().data_dir().clone();
+        write_fixture(&LocalDataStore::with_data_dir(dir.clone()), 3);
+        let first = LocalDataStore::with_data_dir(dir.clone());
+        let second = LocalDataStore::with_data_dir(dir.clone());
+        let picked = |store: &LocalDataStore, query: &str, id: &str| {
+            store.with_selections(|history| history.weights(query, Utc::now()).contains_key(id))
+        };
+
+        first.record_usage_sync("id-0", UsageAction::Paste, None, Some("alpha")).unwrap();
+        second.record_usage_sync("id-1", UsageAction::Paste, None, Some("beta")).unwrap();
+        // Each store sees the other's picks, and saving keeps them
+        assert!(picked(&first, "beta", "id-1"));
+        first.record_usage_sync("id-2", UsageAction::Paste, None, Some("gamma")).unwrap();
+
+        let fresh = LocalDataStore::with_data_dir(dir.clone());
+        assert!(picked(&fresh, "alpha", "id-0"));
+        assert!(picked(&fresh, "beta", "id-1"));
+        assert!(picked(&fresh, "gamma", "id-2"));
+
+        fs::remove_dir_all(&dir).ok();
     }
 
-    async fn rename_folder(&self, old_name: &str, new_name: &str) -> Result<(), String> {
-        let mut index = self.load_index_sync()?;
+    #[test]
+    fn test_stores_share_query_history() {
+        let dir = temp_store().data_dir().clone();
+        let first = LocalDataStore::with_data_dir(dir.clone());
+        let second = LocalDataStore::with_data_dir(dir.clone());
+        let suggested = |store: &LocalDataStore| -> Vec<String> {
+            let mut queries: Vec<String> = store.query_suggestions_sync("", 10).into_iter().map(|q| q.query).collect();
+            queries.sort();
+            queries
+        };
 
-        let old_folder = old_name.trim().to_lowercase();
-        let new_folder = new_name.trim().to_lowercase();
+        first.record_query_sync("alpha").unwrap();
+        second.record_query_sync("beta").unwrap();
+        assert_eq!(suggested(&first), ["alpha", "beta"]);
+        first.record_query_sync("gamma").unwrap();
 
-        if new_folder.is_empty() {
-            return Err("Folder name cannot be empty".to_string());
-        }
+        assert_eq!(suggested(&LocalDataStore::with_data_dir(dir.clone())), ["alpha", "beta", "gamma"]);
 
-        if !index.folders.contains(&old_folder) {
-            return Err("Folder does not exist".to_string());
-        }
+        fs::remove_dir_all(&dir).ok();
+    }
 
-        if index.folders.contains(&new_folder) {
-            return Err("A folder with that name already exists".to_string());
-        }
+    #[test]
+    fn test_stores_share_variable_history() {
+        let dir = temp_store().data_dir().clone();
+        let first = LocalDataStore::with_data_dir(dir.clone());
+        let second = LocalDataStore::with_data_dir(dir.clone());
+        let values = |value: &str| HashMap::from([("topic".to_string(), value.to_string())]);
+        let recent = |store: &LocalDataStore| store.recent_variable_values_sync("a", &["topic"], false, 5);
 
-        // Rename the folder directory
-        let old_path = self.prompts_dir().join(&old_folder);
-        let new_path = self.prompts_dir().join(&new_folder);
+        first.record_variable_values_sync("a", &values("Rust"), 5).unwrap();
+        second.record_variable_values_sync("a", &values("Go"), 5).unwrap();
+        assert_eq!(recent(&first)["topic"], ["Go", "Rust"]);
+        first.record_variable_values_sync("a", &values("Zig"), 5).unwrap();
 
-        if old_path.exists() {
-            fs::rename(&old_path, &new_path)
-                .map_err(|e| format!("Failed to rename folder directory: {}", e))?;
-        }
+        assert_eq!(recent(&LocalDataStore::with_data_dir(dir.clone()))["topic"], ["Zig", "Go", "Rust"]);
 
-        // Update prompts that were in this folder
-        for prompt in &mut index.prompts {
-            if prompt.folder == old_folder {
-                prompt.folder = new_folder.clone();
-            }
-        }
+        fs::remove_dir_all(&dir).ok();
+    }
 
-        // Update folders list
-        if let Some(pos) = index.folders.iter().position(|f| f == &old_folder) {
-            index.folders[pos] = new_folder;
-        }
+    #[test]
+    fn test_locked_library_rejects_writes_but_serves_reads() {
+        let store = temp_store();
+        write_fixture(&store, 2);
+        assert_eq!(search(&store, "").len(), 2);
 
-        self.save_index_sync(&index)?;
+        // Another instance holds the write lock
+        let held = LibraryLock::exclusive(store.data_dir()).unwrap();
 
-        Ok(())
+        let err = store.record_usage_sync("id-0", UsageAction::Paste, None, None).unwrap_err();
+        assert_eq!(err.code, ErrorCode::LibraryLocked);
+        assert_eq!(search(&store, "").len(), 2);
+
+        drop(held);
+        store.record_usage_sync("id-0", UsageAction::Paste, None, None).unwrap();
+
+        fs::remove_dir_all(store.data_dir()).ok();
     }
 
-    async fn delete_folder(&self, name: &str) -> Result<(), String> {
-        let mut index = self.load_index_sync()?;
+    #[test]
+    fn test_downloaded_content_and_index_are_saved_together() {
+        let store = temp_store();
+        write_fixture(&store, 2);
+
+        let mut downloaded = store.load_index_sync().unwrap();
+        downloaded.prompts.truncate(1);
+        let mut prompt = store.get_prompt_sync("id-0").unwrap();
+        prompt.content = "Downloaded from the cloud".to_string();
+
+        // Nothing is written while another instance holds the lock
+        let held = LibraryLock::exclusive(store.data_dir()).unwrap();
+        let err = store.save_index_sync(&downloaded, std::slice::from_ref(&prompt)).unwrap_err();
+        assert_eq!(err.code, ErrorCode::LibraryLocked);
+        drop(held);
+        assert_eq!(store.load_index_sync().unwrap().prompts.len(), 2);
+        assert!(search(&store, "cloud").is_empty());
+
+        store.save_index_sync(&downloaded, &[prompt]).unwrap();
+        assert_eq!(store.load_index_sync().unwrap().prompts.len(), 1);
+        assert_eq!(store.get_prompt_sync("id-0").unwrap().content, "Downloaded from the cloud");
+        assert_eq!(search(&store, "cloud").len(), 1);
+
+        fs::remove_dir_all(store.data_dir()).ok();
+    }
 
-        let folder_name = name.trim().to_lowercase();
+    #[test]
+    fn test_search_and_index_pages() {
+        let store = temp_store();
+        write_fixture(&store, 40);
+        let context = SearchContext::default();
+
+        let first = store.search_prompts_sync("", &context, Page::new(0, 15)).unwrap();
+        assert_eq!((first.results.len(), first.total), (15, 40));
+        let last = store.search_prompts_sync("", &context, Page::new(30, 15)).unwrap();
+        assert_eq!(last.results.len(), 10);
+        assert!(last.results.iter().all(|r| first.results.iter().all(|f| f.prompt.id != r.prompt.id)));
+
+        let matched = store.search_prompts_sync("fixture", &context, Page::new(0, 5)).unwrap();
+        assert_eq!((matched.results.len(), matched.total), (5, 40));
+
+        let page = store
+            .index_page_sync(PromptSort::UseCount, SortDirection::Desc, Page::new(0, 6))
+            .unwrap();
+        assert_eq!(page.total, 40);
+        let counts: Vec<u32> = page.prompts.iter().map(|p| p.use_count).collect();
+        assert_eq!(counts, [6, 6, 6, 6, 6, 5]);
+
+        fs::remove_dir_all(store.data_dir()).ok();
+    }
 
-        if folder_name == "uncategorized" {
-            return Err("Cannot delete the uncategorized folder".to_string());
-        }
+    #[test]
+    fn test_fuzzy_search_returns_highlight_ranges() {
+        let store = temp_store();
+        write_fixture(&store, 3);
 
-        if !index.folders.contains(&folder_name) {
-            return Err("Folder does not exist".to_string());
-        }
+        let results = search(&store, "prmpt 2");
+        assert_eq!(results[0].prompt.id, "id-2");
+        let name: Vec<_> = results[0].matches.name.iter().map(|r| (r.start, r.end)).collect();
+        assert_eq!(name, vec![(0, 2), (3, 6), (7, 8)]);
 
-        let folder_path = self.prompts_dir().join(&folder_name);
-        let uncategorized_path = self.prompts_dir().join("uncategorized");
+        fs::remove_dir_all(store.data_dir()).ok();
+    }
 
-        // Ensure uncategorized folder exists
-        fs::create_dir_all(&uncategorized_path)
-            .map_err(|e| format!("Failed to create uncategorized folder: {}", e))?;
+    #[test]
+    fn test_search_filters_and_exclusions() {
+        let store = temp_store();
+        write_fixture(&store, 6);
+        let folders = |results: &[SearchResult]| -> Vec<String> {
+            results.iter().map(|r| r.prompt.folder.clone()).collect()
+        };
 
-        // Move prompts to uncategorized
-        for prompt in &mut index.prompts {
-            if prompt.folder == folder_name {
-                let old_file = folder_path.join(&prompt.filename);
-                let new_file = uncategorized_path.join(&prompt.filename);
+        let in_folder = search(&store, "folder:coding prompt");
+        assert!(!in_folder.is_empty());
+        assert!(folders(&in_folder).iter().all(|f| f == "coding"));
 
-                if old_file.exists() {
-                    fs::rename(&old_file, &new_file)
-                        .map_err(|e| format!("Failed to move prompt file: {}", e))?;
-                }
+        let excluded = search(&store, "-folder:coding");
+        assert!(!excluded.is_empty());
+        assert!(folders(&excluded).iter().all(|f| f != "coding"));
 
-                prompt.folder = "uncategorized".to_string();
-            }
-        }
+        // Phrases and exclusions reach into content
+        let phrase = search(&store, "\"assistant number 3.\"");
+        assert_eq!(phrase.len(), 1);
+        assert_eq!(phrase[0].prompt.id, "id-3");
+        assert_eq!(search(&store, "-summarize").len(), 0);
 
-        // Remove the folder directory
-        if folder_path.exists() {
-            fs::remove_dir_all(&folder_path)
-                .map_err(|e| format!("Failed to remove folder directory: {}", e))?;
-        }
+        fs::remove_dir_all(store.data_dir()).ok();
+    }
 
-        // Remove from folders list
-        index.folders.retain(|f| f != &folder_name);
+    #[test]
+    fn test_keywords_and_aliases() {
+        let store = temp_store();
+        write_fixture(&store, 4);
+
+        let mut review = store.get_prompt_sync("id-3").unwrap();
+        review.metadata.keyword = Some(" ;rev ".to_string());
+        review.metadata.aliases = vec!["PR feedback".to_string(), " ".to_string()];
+        let saved = store.save_prompt_sync(&review).unwrap();
+        assert_eq!(saved.keyword.as_deref(), Some(";rev"));
+        assert_eq!(saved.aliases, ["PR feedback"]);
+
+        // An exact keyword ranks first, even over prompts matching the text better
+        let mut other = store.get_prompt_sync("id-0").unwrap();
+        other.metadata.name = ";rev".to_string();
+        store.save_prompt_sync(&other).unwrap();
+        assert_eq!(search(&store, ";REV")[0].prompt.id, "id-3");
+
+        // Aliases are scored like the name
+        assert_eq!(search(&store, "feedback")[0].prompt.id, "id-3");
+
+        // Keywords are unique
+        let mut clash = store.get_prompt_sync("id-1").unwrap();
+        clash.metadata.keyword = Some(";Rev".to_string());
+        let err = store.save_prompt_sync(&clash).unwrap_err();
+        assert_eq!(err.code, ErrorCode::AlreadyExists);
+        clash.metadata.keyword = Some("two words".to_string());
+        assert_eq!(store.save_prompt_sync(&clash).unwrap_err().code, ErrorCode::InvalidInput);
+        // Saving the owner again keeps its keyword
+        store.save_prompt_sync(&review).unwrap();
+
+        fs::remove_dir_all(store.data_dir()).ok();
+    }
 
-        self.save_index_sync(&index)?;
+    #[test]
+    fn test_folder_names_cannot_clash_with_smart_folders() {
+        let store = temp_store();
+        write_fixture(&store, 2);
+        let stale = SmartFolder {
+            name: "Stale".to_string(),
+            query: "unused:60d".to_string(),
+            icon: None,
+            color: None,
+            prompt_ids: None,
+        };
+        store.save_smart_folder_sync(&stale, None).unwrap();
 
-        Ok(())
+        assert_eq!(store.add_folder_sync("stale").unwrap_err().code, ErrorCode::AlreadyExists);
+        let err = store.rename_folder_sync("coding", "STALE").unwrap_err();
+        assert_eq!(err.code, ErrorCode::AlreadyExists);
+        assert!(store.load_index_sync().unwrap().folders.contains(&"coding".to_string()));
+
+        fs::remove_dir_all(store.data_dir()).ok();
     }
 
-    async fn record_usage(&self, id: &str) -> Result<(), String> {
-        let mut index = self.load_index_sync()?;
+    #[test]
+    fn test_saved_tags_and_pin_filter_search() {
+        let store = temp_store();
+        write_fixture(&store, 3);
+
+        let mut prompt = store.get_prompt_sync("id-1").unwrap();
+        prompt.metadata.tags = vec![" review ".to_string(), "Review".to_string(), String::new()];
+        prompt.metadata.pinned = true;
+        let saved = store.save_prompt_sync(&prompt).unwrap();
+        assert_eq!(saved.tags, ["review"]);
+        assert!(saved.pinned);
+
+        let ids = |query: &str| -> Vec<String> { search(&store, query).into_iter().map(|r| r.prompt.id).collect() };
+        assert_eq!(ids("tag:review"), ["id-1"]);
+        assert_eq!(ids("is:pinned"), ["id-1"]);
+
+        prompt.metadata.tags.clear();
+        prompt.metadata.pinned = false;
+        store.save_prompt_sync(&prompt).unwrap();
+        assert!(ids("tag:review").is_empty());
+        assert!(ids("is:pinned").is_empty());
+
+        fs::remove_dir_all(store.data_dir()).ok();
+    }
 
-        let prompt = index
-            .prompts
-            .iter_mut()
-            .find(|p| p.id == id)
-            .ok_or_else(|| format!("Prompt not found: {}", id))?;
+    #[test]
+    fn test_save_rejects_invalid_variable_defaults() {
+        let store = temp_store();
+        write_fixture(&store, 1);
 
-        prompt.use_count += 1;
-        prompt.last_used = Some(Utc::now().to_rfc3339());
+        let mut prompt = store.get_prompt_sync("id-0").unwrap();
+        let original = prompt.content.clone();
+        prompt.content = "List {{n:number|abc}} ideas".to_string();
+        let err = store.save_prompt_sync(&prompt).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+        assert_eq!(store.get_prompt_sync("id-0").unwrap().content, original);
 
-        self.save_index_sync(&index)?;
+        prompt.content = "List {{n:number|3}} ideas".to_string();
+        store.save_prompt_sync(&prompt).unwrap();
 
-        Ok(())
+        fs::remove_dir_all(store.data_dir()).ok();
     }
 
-    async fn search_prompts(&self, query: &str) -> Result<Vec<SearchResult>, String> {
-        let index = self.load_index_sync()?;
-        let query_lower = query.to_lowercase();
+    #[test]
+    fn test_search_folds_unicode_and_segments_cjk() {
+        let store = temp_store();
+        write_fixture(&store, 3);
+
+        let mut resume = store.get_prompt_sync("id-0").unwrap();
+        resume.metadata.name = "Résumé Polish".to_string();
+        store.save_prompt_sync(&resume).unwrap();
+        let mut japanese = store.get_prompt_sync("id-1").unwrap();
+        japanese.content = "この文章を日本語に翻訳してください".to_string();
+        store.save_prompt_sync(&japanese).unwrap();
+
+        for profile in [TextRanking::Classic, TextRanking::Bm25f] {
+            let context = ranked_by(profile);
+            for query in ["resume", "ＲＥＳＵＭＥ", "résumé"] {
+                let results = search_with(&store, query, &context);
+                assert_eq!(results[0].prompt.id, "id-0", "{:?} {}", profile, query);
+                assert_eq!(results[0].matches.name, [MatchRange { start: 0, end: 6 }]);
+            }
 
-        if query_lower.is_empty() {
-            // Return all prompts sorted by recency (never-used at bottom)
-            let mut results: Vec<SearchResult> = index
-                .prompts
-                .into_iter()
-                .map(|prompt| {
-                    let score = calculate_recency_score(&prompt);
-                    SearchResult { prompt, score }
-                })
-                .collect();
+            let results = search_with(&store, "翻訳", &context);
+            assert_eq!(results.len(), 1, "{:?}", profile);
+            assert_eq!(results[0].prompt.id, "id-1");
+            assert_eq!(results[0].snippet.as_ref().unwrap().highlights, [MatchRange { start: 9, end: 11 }]);
+        }
 
-            // Sort by score descending, then by lastUsed descending as tiebreaker
-            results.sort_by(|a, b| {
-                let score_cmp = b.score.partial_cmp(&a.score).unwrap();
-                if score_cmp != std::cmp::Ordering::Equal {
-                    return score_cmp;
-                }
-                // Tiebreaker: compare lastUsed timestamps (more recent first)
-                match (&b.prompt.last_used, &a.prompt.last_used) {
-                    (Some(b_ts), Some(a_ts)) => b_ts.cmp(a_ts),
-                    (Some(_), None) => std::cmp::Ordering::Less,
-                    (None, Some(_)) => std::cmp::Ordering::Greater,
-                    (None, None) => std::cmp::Ordering::Equal,
-                }
+        fs::remove_dir_all(store.data_dir()).ok();
+    }
+
+    /// (id, folder, name, description, tags, content)
+    type GoldenPrompt = (&'static str, &'static str, &'static str, &'static str, &'static [&'static str], &'static str);
+
+    const GOLDEN_LIBRARY: [GoldenPrompt; 12] = [
+        ("code-review", "coding", "Code Review", "A helpful reviewer for pull requests", &["review"],
+            "Review this diff for bugs, naming and missing tests."),
+        ("api-design", "coding", "API Design", "Design a clean REST interface", &[],
+            "Make every endpoint idempotent and safe to retry. Use consistent error shapes."),
+        ("email-reply", "writing", "Email Reply", "A helpful, polite reply to an email", &["email"],
+            "Draft a short reply that answers every question in the email."),
+        ("meeting-notes", "writing", "Meeting Notes Summary", "A helpful summary of meeting notes", &[],
+            "Summarize the notes into decisions, owners and deadlines."),
+        ("k8s-debug", "ops", "Kubernetes Debugging", "A helpful guide for failing pods", &["kubernetes"],
+            "Diagnose a pod stuck in CrashLoopBackOff. Check events, logs and probes."),
+        ("sql-tuning", "coding", "SQL Query Tuning", "A helpful database assistant", &[],
+            "Explain the query plan and suggest indexes for slow joins."),
+        ("blog-outline", "writing", "Blog Post Outline", "Outline a long-form article", &["blog"],
+            "Create an outline with a hook, three sections and a conclusion."),
+        ("translate", "writing", "Translate", "A helpful translator", &[],
+            "Translate the text into Spanish while keeping the tone."),
+        ("commit-message", "coding", "Commit Message", "Write a conventional commit message", &[],
+            "Summarize the staged changes as an imperative commit subject."),
+        ("incident-report", "ops", "Incident Postmortem", "A helpful blameless postmortem", &["incident"],
+            "Write a timeline, root cause, and action items for the outage."),
+        ("regex-helper", "coding", "Regex Helper", "Build and explain regular expressions", &[],
+            "Write a regex and explain each part. Include test strings."),
+        ("email-cold", "writing", "Cold Outreach Email", "A helpful first-contact email for prospects",
+            &["email", "sales"], "Write a concise cold email with one clear call to action."),
+    ];
+
+    /// (query, id expected as the top result)
+    const GOLDEN_QUERIES: [(&str, &str); 11] = [
+        ("code review", "code-review"),
+        ("helpful idempotent", "api-design"),
+        ("crashloopbackoff", "k8s-debug"),
+        ("email", "email-reply"),
+        ("postmortem", "incident-report"),
+        ("outage timeline", "incident-report"),
+        ("slow joins", "sql-tuning"),
+        ("sales email", "email-cold"),
+        ("regular expressions", "regex-helper"),
+        ("commit", "commit-message"),
+        ("translate spanish", "translate"),
+    ];
+
+    /// Golden queries the classic profile is known to get right. Raise this
+    /// when a ranking change fixes more of them; it must never drop.
+    const CLASSIC_GOLDEN_BASELINE: usize = 9;
+
+    fn write_golden_library(store: &LocalDataStore) {
+        let now = Utc::now().to_rfc3339();
+        let mut index = PromptIndex { seeded: true, ..PromptIndex::default() };
+        for (id, folder, name, description, tags, content) in GOLDEN_LIBRARY {
+            let folder_path = store.prompts_dir().join(folder);
+            fs::create_dir_all(&folder_path).unwrap();
+            let filename = format!("{}.md", id);
+            fs::write(folder_path.join(&filename), content).unwrap();
+            if !index.folders.iter().any(|f| f == folder) {
+                index.folders.push(folder.to_string());
+            }
+            index.prompts.push(PromptMetadata {
+                id: id.to_string(),
+                name: name.to_string(),
+                folder: folder.to_string(),
+                description: description.to_string(),
+                filename,
+                use_count: 0,
+                last_used: None,
+                created: now.clone(),
+                updated: now.clone(),
+                icon: None,
+                color: None,
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                pinned: false,
+                keyword: None,
+                aliases: Vec::new(),
             });
-            results.truncate(MAX_RESULTS);
-            return Ok(results);
         }
+        fs::create_dir_all(store.data_dir()).unwrap();
+        fs::write(store.index_path(), serde_json::to_string(&index).unwrap()).unwrap();
+    }
 
-        let mut results: Vec<SearchResult> = index
-            .prompts
-            .into_iter()
-            .filter_map(|prompt| {
-                let score = self.calculate_score(&prompt, &query_lower);
-                if score > 0.0 {
-                    Some(SearchResult { prompt, score })
-                } else {
-                    None
-                }
-            })
-            .collect();
+    /// Run with --nocapture to compare the profiles query by query
+    #[test]
+    fn test_golden_queries_by_ranking_profile() {
+        let store = temp_store();
+        write_golden_library(&store);
+
+        let mut passed = HashMap::new();
+        for ranking in [TextRanking::Classic, TextRanking::Bm25f] {
+            let context = ranked_by(ranking);
+            let mut count = 0;
+            for (query, expected) in GOLDEN_QUERIES {
+                let results = search_with(&store, query, &context);
+                let top = results.first().map_or("-", |r| r.prompt.id.as_str());
+                let ok = top == expected;
+                count += usize::from(ok);
+                println!("{:?} {:<22} {:<16} {}", ranking, query, top, if ok { "ok" } else { "MISS" });
+            }
+            passed.insert(ranking, count);
+        }
 
-        // Sort by score descending
-        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
-        results.truncate(MAX_RESULTS);
+        assert_eq!(passed[&TextRanking::Bm25f], GOLDEN_QUERIES.len());
+        assert!(passed[&TextRanking::Classic] >= CLASSIC_GOLDEN_BASELINE);
 
-        Ok(results)
+        fs::remove_dir_all(store.data_dir()).ok();
     }
-}
 
-/// Recency score for empty query (pure recency sort)
-fn calculate_recency_score(prompt: &PromptMetadata) -> f64 {
-    match &prompt.last_used {
-        Some(ts) => DateTime::parse_from_rfc3339(ts)
-            .map(|last| {
-                let hours = Utc::now()
-                    .signed_duration_since(last.with_timezone(&Utc))
-                    .num_hours() as f64;
-                let decay = 0.693 / RECENCY_HALF_LIFE_HOURS;
-                RECENCY_MAX_SCORE * (-decay * hours.max(0.0)).exp()
-            })
-            .unwrap_or(NEVER_USED_PENALTY),
-        None => NEVER_USED_PENALTY,
-    }
-}
+    #[test]
+    fn test_prompts_used_in_current_app_rank_first() {
+        let store = temp_store();
+        write_fixture(&store, 3);
+        store.record_usage_sync("id-1", UsageAction::Paste, Some("com.apple.mail"), None).unwrap();
+        store.record_usage_sync("id-2", UsageAction::Paste, Some("com.microsoft.VSCode"), None).unwrap();
+
+        let top_in = |app: &str| {
+            let context = SearchContext {
+                app: Some(app.to_string()),
+                ..SearchContext::default()
+            };
+            search_with(&store, "", &context)[0].prompt.id.clone()
+        };
+        assert_eq!(top_in("com.microsoft.VSCode"), "id-2");
+        assert_eq!(top_in("com.apple.mail"), "id-1");
 
-/// Small recency bonus for search results (tie-breaker only)
-fn calculate_recency_tiebreaker(prompt: &PromptMetadata) -> f64 {
-    match &prompt.last_used {
-        Some(ts) => DateTime::parse_from_rfc3339(ts)
-            .map(|last| {
-                let hours = Utc::now()
-                    .signed_duration_since(last.with_timezone(&Utc))
-                    .num_hours() as f64;
-                let decay = 0.693 / RECENCY_HALF_LIFE_HOURS;
-                RECENCY_TIEBREAKER_MAX * (-decay * hours.max(0.0)).exp()
-            })
-            .unwrap_or(0.0),
-        None => 0.0,
+        let events = store.usage_history_sync().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].app.as_deref(), Some("com.apple.mail"));
+
+        fs::remove_dir_all(store.data_dir()).ok();
     }
-}
 
-/// Convert a name to a filename-safe slug
-fn slugify(name: &str) -> String {
-    name.to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .collect::<String>()
-        .split('-')
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .join("-")
-}
+    #[test]
+    fn test_prompts_picked_for_a_query_rank_first() {
+        let store = temp_store();
+        write_fixture(&store, 5);
 
-/// Recursively copy a directory and its contents
-fn copy_dir_recursive(src: &PathBuf, dst: &PathBuf) -> Result<(), String> {
-    fs::create_dir_all(dst)
-        .map_err(|e| format!("Failed to create directory {:?}: {}", dst, e))?;
-
-    for entry in fs::read_dir(src)
-        .map_err(|e| format!("Failed to read directory {:?}: {}", src, e))?
-    {
-        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let src_path = entry.path();
-        let dst_path = dst.join(entry.file_name());
+        // Editor pastes weren't picked from a query
+        store.record_usage_sync("id-3", UsageAction::EditorPaste, None, Some("pr")).unwrap();
+        assert!(!store.selection_history_path().exists());
 
-        if src_path.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
-        } else {
-            fs::copy(&src_path, &dst_path)
-                .map_err(|e| format!("Failed to copy {:?} to {:?}: {}", src_path, dst_path, e))?;
+        store.record_usage_sync("id-3", UsageAction::Paste, None, Some("PR")).unwrap();
+        for ranking in [TextRanking::Classic, TextRanking::Bm25f] {
+            let context = ranked_by(ranking);
+            assert_eq!(search_with(&store, "p", &context)[0].prompt.id, "id-3");
         }
+
+        // Explaining shows where the boost came from
+        let context = SearchContext { explain: true, ..SearchContext::default() };
+        let top = &search_with(&store, "p", &context)[0];
+        let explanation = top.explanation.as_ref().unwrap();
+        let selection = explanation.iter().find(|c| c.signal == Signal::Selection).unwrap();
+        assert!(selection.points > 50.0);
+        let total: f64 = explanation.iter().map(|c| c.points).sum();
+        assert!((total - top.score).abs() < 1e-9);
+
+        let score = |store: &LocalDataStore| {
+            let results = search(store, "p");
+            results.iter().find(|r| r.prompt.id == "id-3").unwrap().score
+        };
+        let boosted = score(&store);
+        // The history is per library and survives a restart
+        let reopened = LocalDataStore::with_data_dir(store.data_dir().clone());
+        assert!((score(&reopened) - boosted).abs() < 1e-6);
+
+        store.clear_selection_history_sync().unwrap();
+        assert!(boosted - score(&store) > 50.0);
+
+        fs::remove_dir_all(store.data_dir()).ok();
     }
 
-    Ok(())
+    /// Search latency on a 10k-prompt library, uncached vs cached.
+    ///
+    /// Run with: cargo test --release bench_search_10k -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_search_10k() {
+        const PROMPTS: usize = 10_000;
+        const ITERATIONS: u32 = 20;
+        // Metadata hit, content-only hit, and a miss
+        let queries = ["prompt 42", "neutral", "no such phrase"];
+
+        let store = temp_store();
+        write_fixture(&store, PROMPTS);
+
+        println!("search latency, {} prompts, mean of {} runs", PROMPTS, ITERATIONS);
+        for query in queries {
+            let mut uncached = Duration::ZERO;
+            for _ in 0..ITERATIONS {
+                // A cold start: empty caches, content index loaded from disk
+                store.cache.clear();
+                *store.content_index.lock().unwrap() = ContentIndexState::default();
+                let start = Instant::now();
+                search(&store, query);
+                uncached += start.elapsed();
+            }
+
+            search(&store, query);
+            let mut cached = Duration::ZERO;
+            for _ in 0..ITERATIONS {
+                let start = Instant::now();
+                search(&store, query);
+                cached += start.elapsed();
+            }
+
+            println!(
+                "  {:<16} uncached {:>9.2?}  cached {:>9.2?}",
+                format!("{:?}", query),
+                uncached / ITERATIONS,
+                cached / ITERATIONS
+            );
+        }
+
+        fs::remove_dir_all(store.data_dir()).ok();
+    }
 }
@@ -0,0 +1,159 @@
+//! Cross-process advisory locking of a data directory.
+//!
+//! Two processes (a dev build next to the installed app, or two instances after
+//! an autostart race) can point at the same library. Every read-modify-write of
+//! `index.json` holds an exclusive lock on `<data_dir>/.lock`; readers take a
+//! shared lock so they never observe a half-finished multi-file update.
+//!
+//! Files are also written via temp file + rename, so even an unlocked reader
+//! never sees a torn `index.json`.
+
+use fs4::fs_std::FileExt;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::error::{AppError, AppResult, ErrorCode};
+
+const LOCK_FILE: &str = ".lock";
+
+/// How long a mutation waits for another process to finish before giving up
+const WRITE_LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long a read waits before falling back to cached data
+const READ_LOCK_TIMEOUT: Duration = Duration::from_millis(250);
+
+const RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A held lock on a data directory, released on drop
+pub struct LibraryLock {
+    file: File,
+}
+
+impl LibraryLock {
+    /// Take the exclusive (write) lock
+    pub fn exclusive(data_dir: &Path) -> AppResult<Self> {
+        Self::acquire(data_dir, true, WRITE_LOCK_TIMEOUT)
+    }
+
+    /// Take the exclusive lock only if no other holder has it right now
+    pub fn try_exclusive(data_dir: &Path) -> AppResult<Self> {
+        Self::acquire(data_dir, true, Duration::ZERO)
+    }
+
+    /// Take a shared (read) lock
+    pub fn shared(data_dir: &Path) -> AppResult<Self> {
+        Self::acquire(data_dir, false, READ_LOCK_TIMEOUT)
+    }
+
+    fn acquire(data_dir: &Path, exclusive: bool, timeout: Duration) -> AppResult<Self> {
+        fs::create_dir_all(data_dir).map_err(|e| {
+            AppError::io("Failed to create data directory", e).with_context("path", data_dir.display())
+        })?;
+
+        let path = data_dir.join(LOCK_FILE);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| AppError::io("Failed to open lock file", e).with_context("path", path.display()))?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            // Fully qualified: newer std has inherent File lock methods with different signatures
+            let acquired = if exclusive {
+                FileExt::try_lock_exclusive(&file)
+            } else {
+                FileExt::try_lock_shared(&file)
+            }
+            .map_err(|e| AppError::io("Failed to lock library", e).with_context("path", path.display()))?;
+
+            if acquired {
+                return Ok(Self { file });
+            }
+            if Instant::now() >= deadline {
+                return Err(AppError::new(
+                    ErrorCode::LibraryLocked,
+                    "Library is in use by another instance",
+                )
+                .with_context("path", data_dir.display()));
+            }
+            std::thread::sleep(RETRY_INTERVAL);
+        }
+    }
+}
+
+impl Drop for LibraryLock {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+/// Write a file by writing a sibling temp file and renaming it into place
+pub fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let tmp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp_path);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("promptlight-lock-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_exclusive_lock_blocks_other_holders() {
+        let dir = temp_dir();
+        let held = LibraryLock::exclusive(&dir).unwrap();
+
+        let err = LibraryLock::shared(&dir).err().expect("shared lock should time out");
+        assert_eq!(err.code, ErrorCode::LibraryLocked);
+        assert!(LibraryLock::exclusive(&dir).is_err());
+        assert!(LibraryLock::try_exclusive(&dir).is_err());
+
+        drop(held);
+        assert!(LibraryLock::exclusive(&dir).is_ok());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_shared_locks_coexist() {
+        let dir = temp_dir();
+        let first = LibraryLock::shared(&dir).unwrap();
+        let second = LibraryLock::shared(&dir);
+        assert!(second.is_ok());
+
+        drop(first);
+        drop(second);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.json");
+
+        write_atomic(&path, b"first").unwrap();
+        write_atomic(&path, b"second").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,20 +1,43 @@
+pub mod adaptive;
+pub mod bm25;
+pub mod builtins;
+pub mod cache;
 pub mod commands;
+pub mod content_index;
 pub mod firestore;
+pub mod frecency;
+pub mod fuzzy;
+pub mod include;
 pub mod index;
 pub mod local;
+pub mod lock;
+pub mod normalize;
+pub mod page;
+pub mod palette;
 pub mod prompt;
-pub mod search;
+pub mod query;
+pub mod query_history;
+pub mod ranking;
 pub mod settings;
+pub mod smart_folder;
+pub mod snippet;
 pub mod stats;
 pub mod store;
 pub mod sync;
+pub mod template;
+pub mod usage;
+pub mod variable_history;
 
-pub use local::LocalDataStore;
 pub use store::DataStore;
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use fuzzy::MatchRange;
+use ranking::{Contribution, RankingProfile};
+use smart_folder::SmartFolder;
+use snippet::Snippet;
+
 /// Metadata for a prompt (stored in index.json)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -32,6 +55,16 @@ pub struct PromptMetadata {
     pub icon: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    /// Short unique code that selects this prompt when typed exactly (e.g. `;rev`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyword: Option<String>,
+    /// Other names the prompt is found by, scored like the name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
 }
 
 /// Full prompt with content
@@ -49,6 +82,37 @@ pub struct Prompt {
 pub struct SearchResult {
     pub prompt: PromptMetadata,
     pub score: f64,
+    #[serde(default)]
+    pub matches: SearchMatches,
+    /// Excerpt of the content around the best content match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<Snippet>,
+    /// Each ranking signal's part of the score, when explaining
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<Vec<Contribution>>,
+}
+
+/// Ranges of each field that matched the query, for highlighting
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchMatches {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub name: Vec<MatchRange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub folder: Vec<MatchRange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub description: Vec<MatchRange>,
+}
+
+/// Context the launcher was opened in, used to personalize ranking
+#[derive(Debug, Clone, Default)]
+pub struct SearchContext {
+    /// Bundle ID or process name of the app the launcher was summoned over
+    pub app: Option<String>,
+    /// Text algorithm and signal weights selected in settings
+    pub ranking: RankingProfile,
+    /// Report each result's score by signal
+    pub explain: bool,
 }
 
 /// Folder metadata
@@ -70,6 +134,9 @@ pub struct PromptIndex {
     pub folders: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub folder_meta: Option<std::collections::HashMap<String, FolderMetadata>>,
+    /// Saved searches listed next to the folders
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub smart_folders: Vec<SmartFolder>,
     /// Tracks whether sample prompts have been shown to this user.
     /// Once seeded, prompts won't be re-created even if all are deleted.
     #[serde(default)]
@@ -82,6 +149,7 @@ impl Default for PromptIndex {
             prompts: Vec::new(),
             folders: vec!["uncategorized".to_string()],
             folder_meta: None,
+            smart_folders: Vec::new(),
             seeded: false,
         }
     }
@@ -118,6 +186,10 @@ pub fn create_sample_prompts() -> (PromptIndex, Vec<(String, String)>) {
             updated: now.clone(),
             icon: None,
             color: None,
+            tags: Vec::new(),
+            pinned: false,
+            keyword: None,
+            aliases: Vec::new(),
         });
         files.push((filename, content.to_string()));
     }
@@ -127,7 +199,7 @@ pub fn create_sample_prompts() -> (PromptIndex, Vec<(String, String)>) {
         "uncategorized".to_string(),
     ];
 
-    (PromptIndex { prompts, folders, folder_meta: None, seeded: true }, files)
+    (PromptIndex { prompts, folders, seeded: true, ..PromptIndex::default() }, files)
 }
 
 /// Get the base data directory path (~/.prompt-launcher)
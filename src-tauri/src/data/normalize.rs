@@ -0,0 +1,154 @@
+//! Unicode normalization for search.
+//!
+//! Every search path compares folded text: NFKC-normalized (full-width
+//! "Ｒｅｓｕｍｅ" becomes "Resume", ligatures are split), with diacritics
+//! removed ("Résumé" matches "resume") and lowercased. Folding works one
+//! grapheme cluster at a time so positions in the folded text can be traced
+//! back to the original for highlighting.
+//!
+//! Only combining diacritical marks are removed. Marks that change a letter
+//! in other scripts, such as the kana voicing marks, are kept.
+
+use std::borrow::Cow;
+use std::ops::Range;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Fold text for comparison: normalized, without diacritics, lowercased
+pub fn fold(text: &str) -> String {
+    fold_cased(text).to_lowercase()
+}
+
+/// Normalize and remove diacritics, keeping case
+pub fn fold_cased(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(text.nfkd().filter(|&c| !is_diacritic(c)).nfc().collect())
+}
+
+/// Grapheme clusters of `text` with their UTF-16 range and folded (cased) form
+pub fn fold_graphemes(text: &str) -> impl Iterator<Item = (Range<usize>, Cow<'_, str>)> {
+    let mut offset = 0;
+    text.graphemes(true).map(move |grapheme| {
+        let start = offset;
+        offset += grapheme.encode_utf16().count();
+        (start..offset, fold_cased(grapheme))
+    })
+}
+
+/// Text folded (keeping case) with a map back to the original
+pub struct Folded<'a> {
+    original: &'a str,
+    pub text: Cow<'a, str>,
+    /// Per grapheme: (byte offset in `text`, byte offset in `original`).
+    /// Empty when folding changed nothing.
+    map: Vec<(usize, usize)>,
+}
+
+impl<'a> Folded<'a> {
+    pub fn new(original: &'a str) -> Self {
+        if original.is_ascii() {
+            return Self { original, text: Cow::Borrowed(original), map: Vec::new() };
+        }
+
+        let mut text = String::with_capacity(original.len());
+        let mut map = Vec::new();
+        for (start, grapheme) in original.grapheme_indices(true) {
+            map.push((text.len(), start));
+            text.push_str(&fold_cased(grapheme));
+        }
+        Self { original, text: Cow::Owned(text), map }
+    }
+
+    /// Byte range in the original text that a range of the folded text came from
+    pub fn original_range(&self, folded: Range<usize>) -> Range<usize> {
+        if self.map.is_empty() {
+            return folded;
+        }
+        let containing = |at: usize, inclusive: bool| {
+            self.map
+                .partition_point(|&(start, _)| if inclusive { start <= at } else { start < at })
+                .saturating_sub(1)
+        };
+
+        let first = containing(folded.start, true);
+        let last = containing(folded.end, false);
+        let end = self.map.get(last + 1).map_or(self.original.len(), |&(_, start)| start);
+        self.map[first].1..end
+    }
+}
+
+/// Whether a character belongs to a script written without spaces between
+/// words (Han, Hiragana, Katakana), where words are matched as bigrams
+pub fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3040}'..='\u{30FF}'     // Hiragana, Katakana
+            | '\u{31F0}'..='\u{31FF}' // Katakana phonetic extensions
+            | '\u{3400}'..='\u{4DBF}' // CJK extension A
+            | '\u{4E00}'..='\u{9FFF}' // CJK unified ideographs
+            | '\u{F900}'..='\u{FAFF}' // CJK compatibility ideographs
+            | '\u{20000}'..='\u{2FA1F}' // CJK extensions B onwards
+    )
+}
+
+/// Combining marks that only add accents to a base letter
+fn is_diacritic(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}' // Combining diacritical marks
+            | '\u{1AB0}'..='\u{1AFF}' // ... extended
+            | '\u{1DC0}'..='\u{1DFF}' // ... supplement
+            | '\u{20D0}'..='\u{20FF}' // ... for symbols
+            | '\u{FE20}'..='\u{FE2F}' // Combining half marks
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_normalizes_width_diacritics_and_case() {
+        assert_eq!(fold("Résumé"), "resume");
+        // Decomposed accents fold the same as precomposed ones
+        assert_eq!(fold("Re\u{301}sume\u{301}"), "resume");
+        assert_eq!(fold("ＲＥＳＵＭＥ ２０２４"), "resume 2024");
+        assert_eq!(fold("ﬁnal Ĳssel"), "final ijssel");
+        // Kana voicing marks are part of the letter
+        assert_eq!(fold("ガイド"), "ガイド");
+    }
+
+    #[test]
+    fn test_original_range_maps_back_through_folding() {
+        let original = "Ａ café ﬁle";
+        let folded = Folded::new(original);
+        assert_eq!(folded.text, "A cafe file");
+
+        let find = |word: &str| {
+            let start = folded.text.find(word).unwrap();
+            let range = folded.original_range(start..start + word.len());
+            &original[range]
+        };
+        assert_eq!(find("A"), "Ａ");
+        assert_eq!(find("cafe"), "café");
+        // Part of a ligature maps to the whole ligature
+        assert_eq!(find("fi"), "ﬁ");
+        assert_eq!(find("file"), "ﬁle");
+
+        let ascii = Folded::new("plain");
+        assert_eq!(ascii.original_range(1..3), 1..3);
+    }
+
+    #[test]
+    fn test_fold_graphemes_reports_utf16_ranges() {
+        let graphemes: Vec<(Range<usize>, String)> = fold_graphemes("é📄x")
+            .map(|(range, folded)| (range, folded.into_owned()))
+            .collect();
+        assert_eq!(
+            graphemes,
+            [(0..1, "e".to_string()), (1..3, "📄".to_string()), (3..4, "x".to_string())]
+        );
+    }
+}
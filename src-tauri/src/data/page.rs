@@ -0,0 +1,168 @@
+//! Pagination and sorting for lists sent to the frontend.
+//!
+//! Search results and the prompt list can both be large; commands return one
+//! page at a time together with the total count, so the UI can load more on
+//! demand instead of receiving the whole library in one IPC payload.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use super::{PromptMetadata, SearchResult};
+
+/// A window into an ordered list: skip `offset` items, take at most `limit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl Page {
+    pub fn new(offset: usize, limit: usize) -> Self {
+        Self { offset, limit }
+    }
+
+    /// This page's items out of the full list
+    pub fn slice<T>(&self, items: Vec<T>) -> Vec<T> {
+        items.into_iter().skip(self.offset).take(self.limit).collect()
+    }
+}
+
+/// One page of search results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPage {
+    pub results: Vec<SearchResult>,
+    /// Number of matching prompts across all pages
+    pub total: usize,
+}
+
+/// One page of the prompt list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexPage {
+    pub prompts: Vec<PromptMetadata>,
+    /// Number of prompts in the library
+    pub total: usize,
+}
+
+/// Field the prompt list is sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PromptSort {
+    #[default]
+    Name,
+    Created,
+    Updated,
+    UseCount,
+    LastUsed,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Sort prompts by a field. Ties fall back to name order, and prompts that
+/// were never used sort after used ones in either direction.
+pub fn sort_prompts(prompts: &mut [&PromptMetadata], sort: PromptSort, direction: SortDirection) {
+    let directed = |ordering: Ordering| match direction {
+        SortDirection::Asc => ordering,
+        SortDirection::Desc => ordering.reverse(),
+    };
+
+    prompts.sort_by(|a, b| {
+        let ordering = match sort {
+            PromptSort::Name => directed(by_name(a, b)),
+            // Timestamps are RFC 3339 in UTC, so they order as strings
+            PromptSort::Created => directed(a.created.cmp(&b.created)),
+            PromptSort::Updated => directed(a.updated.cmp(&b.updated)),
+            PromptSort::UseCount => directed(a.use_count.cmp(&b.use_count)),
+            PromptSort::LastUsed => {
+                match (last_used(a), last_used(b)) {
+                    (Some(a_ts), Some(b_ts)) => directed(a_ts.cmp(b_ts)),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            }
+        };
+        ordering.then_with(|| by_name(a, b))
+    });
+}
+
+/// Case-insensitive name order, then id for a stable result
+fn by_name(a: &PromptMetadata, b: &PromptMetadata) -> Ordering {
+    let lower = |name: &str| name.chars().flat_map(char::to_lowercase).collect::<String>();
+    lower(&a.name).cmp(&lower(&b.name)).then_with(|| a.id.cmp(&b.id))
+}
+
+/// When a prompt was last used, if it ever was (saving also sets `last_used`)
+fn last_used(prompt: &PromptMetadata) -> Option<&str> {
+    prompt.last_used.as_deref().filter(|_| prompt.use_count > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(name: &str, use_count: u32, created: &str) -> PromptMetadata {
+        PromptMetadata {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            folder: "uncategorized".to_string(),
+            description: String::new(),
+            filename: format!("{}.md", name.to_lowercase()),
+            use_count,
+            last_used: Some(created.to_string()),
+            created: created.to_string(),
+            updated: created.to_string(),
+            icon: None,
+            color: None,
+            tags: Vec::new(),
+            pinned: false,
+            keyword: None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn names(prompts: &[&PromptMetadata]) -> Vec<String> {
+        prompts.iter().map(|p| p.name.clone()).collect()
+    }
+
+    #[test]
+    fn test_sort_prompts_by_each_field() {
+        let library = [
+            prompt("beta", 3, "2024-03-01T00:00:00+00:00"),
+            prompt("Alpha", 0, "2024-01-01T00:00:00+00:00"),
+            prompt("gamma", 3, "2024-02-01T00:00:00+00:00"),
+        ];
+        let mut prompts: Vec<&PromptMetadata> = library.iter().collect();
+
+        sort_prompts(&mut prompts, PromptSort::Name, SortDirection::Asc);
+        assert_eq!(names(&prompts), ["Alpha", "beta", "gamma"]);
+
+        sort_prompts(&mut prompts, PromptSort::Created, SortDirection::Desc);
+        assert_eq!(names(&prompts), ["beta", "gamma", "Alpha"]);
+
+        // Equal counts fall back to name order
+        sort_prompts(&mut prompts, PromptSort::UseCount, SortDirection::Desc);
+        assert_eq!(names(&prompts), ["beta", "gamma", "Alpha"]);
+
+        // Never-used prompts stay last in both directions
+        sort_prompts(&mut prompts, PromptSort::LastUsed, SortDirection::Asc);
+        assert_eq!(names(&prompts), ["gamma", "beta", "Alpha"]);
+        sort_prompts(&mut prompts, PromptSort::LastUsed, SortDirection::Desc);
+        assert_eq!(names(&prompts), ["beta", "gamma", "Alpha"]);
+    }
+
+    #[test]
+    fn test_page_slice() {
+        let items: Vec<u32> = (0..10).collect();
+        assert_eq!(Page::new(0, 3).slice(items.clone()), [0, 1, 2]);
+        assert_eq!(Page::new(8, 5).slice(items.clone()), [8, 9]);
+        assert!(Page::new(20, 5).slice(items).is_empty());
+    }
+}
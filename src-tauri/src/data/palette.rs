@@ -0,0 +1,270 @@
+//! Command palette: folders and app actions in launcher search.
+//!
+//! Launcher search returns typed results. Prompts come from the store's
+//! search; folders match by name and drill in (the launcher narrows the query
+//! to `folder:<name>`); actions match by title or keyword and are run with the
+//! `execute_action` command. A query starting with `>` searches actions only.
+//!
+//! Folders and actions are scored like prompt names, so a query naming a
+//! folder lists the folder above the prompts in it.
+
+use serde::{Deserialize, Serialize};
+
+use super::fuzzy::{FuzzyQuery, MatchRange};
+use super::normalize::fold;
+use super::page::{Page, SearchPage};
+use super::query::SearchQuery;
+use super::ranking;
+use super::{PromptIndex, SearchResult};
+
+/// Starts a query that searches actions only
+pub const ACTION_PREFIX: char = '>';
+
+/// An app action the launcher can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LauncherAction {
+    NewPrompt,
+    SyncNow,
+    OpenSettings,
+    SignOut,
+    Import,
+}
+
+impl LauncherAction {
+    /// Every action, in the order listed for a bare `>`
+    pub const ALL: [LauncherAction; 5] = [
+        LauncherAction::NewPrompt,
+        LauncherAction::Import,
+        LauncherAction::SyncNow,
+        LauncherAction::OpenSettings,
+        LauncherAction::SignOut,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            LauncherAction::NewPrompt => "New Prompt",
+            LauncherAction::SyncNow => "Sync Now",
+            LauncherAction::OpenSettings => "Open Settings",
+            LauncherAction::SignOut => "Sign Out",
+            LauncherAction::Import => "Import…",
+        }
+    }
+
+    /// Other words the action is found by
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            LauncherAction::NewPrompt => &["create", "add"],
+            LauncherAction::SyncNow => &["cloud", "upload", "backup"],
+            LauncherAction::OpenSettings => &["preferences", "options", "hotkey"],
+            LauncherAction::SignOut => &["log out", "logout", "account"],
+            LauncherAction::Import => &["files", "markdown"],
+        }
+    }
+
+    /// Syncing and signing out need an account
+    fn available(self, signed_in: bool) -> bool {
+        signed_in || !matches!(self, LauncherAction::SyncNow | LauncherAction::SignOut)
+    }
+}
+
+/// A folder matching the query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderResult {
+    pub name: String,
+    pub prompt_count: usize,
+    pub score: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matches: Vec<MatchRange>,
+}
+
+/// An action matching the query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionResult {
+    pub action: LauncherAction,
+    pub title: String,
+    pub score: f64,
+    /// Ranges of the title that matched (none if a keyword matched)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matches: Vec<MatchRange>,
+}
+
+/// One launcher search result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum LauncherResult {
+    Prompt(Box<SearchResult>),
+    Folder(FolderResult),
+    Action(ActionResult),
+}
+
+impl LauncherResult {
+    fn score(&self) -> f64 {
+        match self {
+            LauncherResult::Prompt(result) => result.score,
+            LauncherResult::Folder(folder) => folder.score,
+            LauncherResult::Action(action) => action.score,
+        }
+    }
+}
+
+/// One page of launcher search results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherPage {
+    pub results: Vec<LauncherResult>,
+    /// Number of results across all pages
+    pub total: usize,
+}
+
+/// The rest of a query that searches actions only
+pub fn action_query(query: &str) -> Option<&str> {
+    query.trim_start().strip_prefix(ACTION_PREFIX)
+}
+
+/// Actions matching `query`, best first. A blank query lists them all.
+pub fn matching_actions(query: &str, signed_in: bool) -> Vec<LauncherResult> {
+    let text = fold(query.trim());
+    let available = LauncherAction::ALL.into_iter().filter(|action| action.available(signed_in));
+    let action_result = |action: LauncherAction, score: f64, matches: Vec<MatchRange>| {
+        LauncherResult::Action(ActionResult { action, title: action.title().to_string(), score, matches })
+    };
+
+    if text.is_empty() {
+        return available.map(|action| action_result(action, 0.0, Vec::new())).collect();
+    }
+
+    let fuzzy = FuzzyQuery::new(&text);
+    let mut results: Vec<LauncherResult> = available
+        .filter_map(|action| {
+            let title = ranking::title_score(action.title(), &fuzzy);
+            let keyword = action
+                .keywords()
+                .iter()
+                .filter_map(|keyword| ranking::title_score(keyword, &fuzzy))
+                .map(|(score, _)| score)
+                .fold(0.0, f64::max);
+            match title {
+                Some((score, ranges)) if score >= keyword => Some(action_result(action, score, ranges)),
+                _ if keyword > 0.0 => Some(action_result(action, keyword, Vec::new())),
+                _ => None,
+            }
+        })
+        .collect();
+    sort_by_score(&mut results);
+    results
+}
+
+/// Folders whose name matches the query's text. Queries with filters
+/// (`folder:`, `tag:`, ...) are about prompts, so match no folders.
+pub fn matching_folders(index: &PromptIndex, query: &str) -> Vec<LauncherResult> {
+    let parsed = SearchQuery::parse(query);
+    if parsed.text.is_empty() || !parsed.clauses.is_empty() {
+        return Vec::new();
+    }
+
+    let fuzzy = FuzzyQuery::new(&parsed.text);
+    index
+        .folders
+        .iter()
+        .filter_map(|name| {
+            let (score, matches) = ranking::title_score(name, &fuzzy)?;
+            let prompt_count = index.prompts.iter().filter(|p| &p.folder == name).count();
+            Some(LauncherResult::Folder(FolderResult { name: name.clone(), prompt_count, score, matches }))
+        })
+        .collect()
+}
+
+/// Merge prompt results with folders and actions into one page. `prompts`
+/// must hold the first `page.offset + page.limit` prompts, so every result
+/// that can land on the page is there.
+pub fn merge(prompts: SearchPage, others: Vec<LauncherResult>, page: Page) -> LauncherPage {
+    let total = prompts.total + others.len();
+    let mut results: Vec<LauncherResult> =
+        prompts.results.into_iter().map(|r| LauncherResult::Prompt(Box::new(r))).chain(others).collect();
+    // Stable, so prompts keep their own order (e.g. an exact keyword first)
+    sort_by_score(&mut results);
+    LauncherPage { results: page.slice(results), total }
+}
+
+/// One page of results that are all already at hand
+pub fn paginate(results: Vec<LauncherResult>, page: Page) -> LauncherPage {
+    LauncherPage { total: results.len(), results: page.slice(results) }
+}
+
+fn sort_by_score(results: &mut [LauncherResult]) {
+    results.sort_by(|a, b| b.score().total_cmp(&a.score()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::PromptMetadata;
+
+    fn actions(results: &[LauncherResult]) -> Vec<LauncherAction> {
+        results
+            .iter()
+            .filter_map(|result| match result {
+                LauncherResult::Action(action) => Some(action.action),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn prompt_result(id: &str, folder: &str, score: f64) -> SearchResult {
+        let prompt: PromptMetadata = serde_json::from_value(serde_json::json!({
+            "id": id, "name": id, "folder": folder, "description": "", "filename": "",
+            "useCount": 0, "lastUsed": null, "created": "", "updated": ""
+        }))
+        .unwrap();
+        SearchResult { prompt, score, matches: Default::default(), snippet: None, explanation: None }
+    }
+
+    #[test]
+    fn test_actions_match_title_or_keyword() {
+        assert_eq!(action_query(" >sync"), Some("sync"));
+        assert_eq!(action_query("sync"), None);
+
+        assert_eq!(actions(&matching_actions("", false)).len(), 3);
+        assert_eq!(actions(&matching_actions("", true)), LauncherAction::ALL);
+
+        assert_eq!(actions(&matching_actions("settings", false)), [LauncherAction::OpenSettings]);
+        assert_eq!(actions(&matching_actions("preferences", false)), [LauncherAction::OpenSettings]);
+        // Signing out needs an account
+        assert!(actions(&matching_actions("sign out", false)).is_empty());
+        assert_eq!(actions(&matching_actions("logout", true)), [LauncherAction::SignOut]);
+    }
+
+    #[test]
+    fn test_folders_rank_with_prompts() {
+        let index = PromptIndex {
+            folders: vec!["coding".to_string(), "writing".to_string()],
+            prompts: vec![prompt_result("review", "coding", 0.0).prompt],
+            ..Default::default()
+        };
+
+        let folders = matching_folders(&index, "coding");
+        let [LauncherResult::Folder(folder)] = folders.as_slice() else {
+            panic!("expected one folder, got {:?}", folders);
+        };
+        assert_eq!((folder.name.as_str(), folder.prompt_count), ("coding", 1));
+        // Filters are about prompts
+        assert!(matching_folders(&index, "folder:coding").is_empty());
+        assert!(matching_folders(&index, "").is_empty());
+
+        let prompts = SearchPage {
+            results: vec![prompt_result("a", "coding", 150.0), prompt_result("b", "coding", 50.0)],
+            total: 3,
+        };
+        let page = merge(prompts, folders, Page::new(0, 2));
+        assert_eq!(page.total, 4);
+        assert!(matches!(&page.results[0], LauncherResult::Folder(_)));
+        assert!(matches!(&page.results[1], LauncherResult::Prompt(r) if r.prompt.id == "a"));
+
+        let json = serde_json::to_value(&page.results[0]).unwrap();
+        assert_eq!(json["kind"], "folder");
+        assert_eq!(json["promptCount"], 1);
+    }
+}
@@ -62,6 +62,10 @@ pub fn save_prompt(prompt: Prompt) -> Result<PromptMetadata, String> {
             updated: now,
             icon: prompt.metadata.icon,
             color: prompt.metadata.color,
+            tags: Vec::new(),
+            pinned: false,
+            keyword: None,
+            aliases: Vec::new(),
         };
 
         // Ensure folder exists in index
@@ -0,0 +1,452 @@
+//! Search query language.
+//!
+//! Queries typed in the launcher can mix free text with structured clauses:
+//! - `folder:writing`: prompt is in the folder
+//! - `tag:review`: prompt has the tag
+//! - `is:pinned`, `is:used`, `is:unused`: prompt flags
+//! - `used:>5` (also `>=`, `<`, `<=`, `=`): use count comparison
+//! - `unused:60d` (or `8w`): not used in the last 60 days (or 8 weeks), going
+//!   by the usage log as the usage views do (see `usage::is_unused_since`)
+//! - `"exact phrase"`: text appears verbatim in name, description or content
+//! - `/regex/`: case-insensitive regex over name, description and content
+//! - `-term`, `-folder:drafts`, `-"phrase"`: exclude matches
+//!
+//! Field values may be quoted (`folder:"client work"`). Words with an unknown
+//! `key:` prefix are treated as plain text. Invalid syntax (an unterminated
+//! quote or regex, a bad `is:`/`used:`/`unused:` value, a regex that doesn't compile)
+//! makes the whole query plain text, so typing never produces an error.
+
+use chrono::{DateTime, Duration, Utc};
+use regex::{Regex, RegexBuilder};
+
+use super::cache::CachedContent;
+use super::normalize::fold;
+use super::usage::{self, LastUses};
+use super::PromptMetadata;
+
+/// A parsed search query
+#[derive(Debug, Default)]
+pub struct SearchQuery {
+    /// Free text to rank by (terms and positive phrases, folded)
+    pub text: String,
+    /// Filters every result must satisfy
+    pub clauses: Vec<Clause>,
+}
+
+/// What clauses test besides a prompt's own metadata and content
+#[derive(Debug, Default)]
+pub struct FilterContext<'a> {
+    /// Each prompt's last use in the usage log
+    pub last_uses: LastUses<'a>,
+}
+
+/// One filter of a query, possibly negated
+#[derive(Debug)]
+pub struct Clause {
+    pub negated: bool,
+    pub condition: Condition,
+}
+
+/// What a clause tests
+#[derive(Debug)]
+pub enum Condition {
+    Folder(String),
+    Tag(String),
+    Flag(Flag),
+    UseCount(Comparison, u32),
+    /// Not used since this time (a never-used prompt counts once created before it)
+    UnusedSince(DateTime<Utc>),
+    /// Lowercased text that must appear in name, description or content
+    Phrase(String),
+    /// Lowercased text that must appear in any field (only produced by `-term`)
+    Term(String),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flag {
+    Pinned,
+    Used,
+    Unused,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+}
+
+impl Comparison {
+    fn test(self, value: u32, target: u32) -> bool {
+        match self {
+            Comparison::Greater => value > target,
+            Comparison::GreaterOrEqual => value >= target,
+            Comparison::Less => value < target,
+            Comparison::LessOrEqual => value <= target,
+            Comparison::Equal => value == target,
+        }
+    }
+}
+
+impl SearchQuery {
+    /// Parse a query, falling back to plain text if the syntax is invalid
+    pub fn parse(input: &str) -> Self {
+        parse_structured(input).unwrap_or_else(|| Self {
+            text: fold(input.trim()),
+            clauses: Vec::new(),
+        })
+    }
+
+    /// Whether a prompt passes every clause. `load_content` is called at
+    /// most once, and only if a clause needs the prompt's content.
+    pub fn accepts(
+        &self,
+        prompt: &PromptMetadata,
+        context: &FilterContext,
+        load_content: impl FnOnce() -> Option<CachedContent>,
+    ) -> bool {
+        let mut load_content = Some(load_content);
+        let mut content: Option<CachedContent> = None;
+
+        self.clauses.iter().all(|clause| {
+            if clause.condition.needs_content(prompt) {
+                if let Some(load) = load_content.take() {
+                    content = load();
+                }
+            }
+            clause.condition.test(prompt, context, content.as_ref()) != clause.negated
+        })
+    }
+}
+
+impl Condition {
+    /// Whether testing this condition may need the prompt content
+    fn needs_content(&self, prompt: &PromptMetadata) -> bool {
+        match self {
+            Condition::Phrase(text) | Condition::Term(text) => !metadata_contains(prompt, text),
+            Condition::Regex(re) => !re.is_match(&prompt.name) && !re.is_match(&prompt.description),
+            _ => false,
+        }
+    }
+
+    fn test(&self, prompt: &PromptMetadata, context: &FilterContext, content: Option<&CachedContent>) -> bool {
+        match self {
+            Condition::Folder(folder) => fold(&prompt.folder) == *folder,
+            Condition::Tag(tag) => prompt.tags.iter().any(|t| fold(t) == *tag),
+            Condition::Flag(Flag::Pinned) => prompt.pinned,
+            Condition::Flag(Flag::Used) => prompt.use_count > 0,
+            Condition::Flag(Flag::Unused) => prompt.use_count == 0,
+            Condition::UseCount(cmp, target) => cmp.test(prompt.use_count, *target),
+            Condition::UnusedSince(cutoff) => usage::is_unused_since(prompt, &context.last_uses, *cutoff),
+            Condition::Phrase(text) | Condition::Term(text) => {
+                metadata_contains(prompt, text)
+                    || content.is_some_and(|c| c.folded.contains(text.as_str()))
+            }
+            Condition::Regex(re) => {
+                re.is_match(&prompt.name)
+                    || re.is_match(&prompt.description)
+                    || content.is_some_and(|c| re.is_match(&c.content))
+            }
+        }
+    }
+}
+
+fn metadata_contains(prompt: &PromptMetadata, text: &str) -> bool {
+    fold(&prompt.name).contains(text)
+        || fold(&prompt.description).contains(text)
+        || fold(&prompt.folder).contains(text)
+        || prompt.tags.iter().any(|t| fold(t).contains(text))
+        || prompt.aliases.iter().any(|a| fold(a).contains(text))
+}
+
+/// Parse the structured syntax; None if it's invalid
+fn parse_structured(input: &str) -> Option<SearchQuery> {
+    let mut text_parts: Vec<String> = Vec::new();
+    let mut clauses = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(&first) = chars.peek() else {
+            break;
+        };
+
+        // A leading '-' negates, unless it's the whole word
+        let mut negated = false;
+        if first == '-' {
+            chars.next();
+            match chars.peek() {
+                Some(c) if !c.is_whitespace() => negated = true,
+                _ => {
+                    text_parts.push("-".to_string());
+                    continue;
+                }
+            }
+        }
+
+        let condition = match chars.peek() {
+            Some('"') => {
+                chars.next();
+                let phrase = fold(&read_until(&mut chars, '"')?);
+                if phrase.is_empty() {
+                    continue;
+                }
+                if !negated {
+                    text_parts.push(phrase.clone());
+                }
+                Condition::Phrase(phrase)
+            }
+            Some('/') => {
+                chars.next();
+                let pattern = read_until(&mut chars, '/')?;
+                let re = RegexBuilder::new(&pattern).case_insensitive(true).build().ok()?;
+                Condition::Regex(re)
+            }
+            _ => {
+                let word = read_word(&mut chars)?;
+                match parse_field(&word)? {
+                    Some(condition) => condition,
+                    None if negated => Condition::Term(fold(&word)),
+                    None => {
+                        text_parts.push(fold(&word));
+                        continue;
+                    }
+                }
+            }
+        };
+
+        clauses.push(Clause { negated, condition });
+    }
+
+    Some(SearchQuery { text: text_parts.join(" "), clauses })
+}
+
+/// Read up to an unescaped `end` (consumed). None if it never comes.
+/// `\end` yields a literal `end`; other escapes are kept for the regex engine.
+fn read_until(chars: &mut std::iter::Peekable<std::str::Chars>, end: char) -> Option<String> {
+    let mut out = String::new();
+    while let Some(c) = chars.next() {
+        if c == end {
+            return Some(out);
+        }
+        if c == '\\' {
+            match chars.next() {
+                Some(next) if next == end => out.push(next),
+                Some(next) => {
+                    out.push('\\');
+                    out.push(next);
+                }
+                None => out.push('\\'),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    None
+}
+
+/// Read a word up to whitespace. A quote after `key:` extends the value
+/// to the closing quote, which is kept so `parse_field` can strip it.
+fn read_word(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<String> {
+    let mut word = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            break;
+        }
+        chars.next();
+        if c == '"' && word.ends_with(':') {
+            word.push('"');
+            word.push_str(&read_until(chars, '"')?);
+            word.push('"');
+        } else {
+            word.push(c);
+        }
+    }
+    Some(word)
+}
+
+/// Parse `key:value`. Some(None) for words that aren't a known field;
+/// None for a known field with an invalid value.
+fn parse_field(word: &str) -> Option<Option<Condition>> {
+    let Some((key, value)) = word.split_once(':') else {
+        return Some(None);
+    };
+    let value = fold(value.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(value));
+
+    let condition = match key.to_lowercase().as_str() {
+        "folder" if !value.is_empty() => Condition::Folder(value),
+        "tag" if !value.is_empty() => Condition::Tag(value),
+        "is" => Condition::Flag(match value.as_str() {
+            "pinned" => Flag::Pinned,
+            "used" => Flag::Used,
+            "unused" => Flag::Unused,
+            _ => return None,
+        }),
+        "used" => {
+            let (cmp, number) = [
+                (">=", Comparison::GreaterOrEqual),
+                ("<=", Comparison::LessOrEqual),
+                (">", Comparison::Greater),
+                ("<", Comparison::Less),
+                ("=", Comparison::Equal),
+            ]
+            .into_iter()
+            .find_map(|(op, cmp)| value.strip_prefix(op).map(|rest| (cmp, rest)))
+            .unwrap_or((Comparison::Equal, value.as_str()));
+            Condition::UseCount(cmp, number.parse().ok()?)
+        }
+        "unused" => Condition::UnusedSince(Utc::now() - parse_period(&value)?),
+        "folder" | "tag" => return None,
+        _ => return Some(None),
+    };
+    Some(Some(condition))
+}
+
+/// Parse a period like `60d` or `8w` (a bare number is days)
+fn parse_period(value: &str) -> Option<Duration> {
+    let (number, days_per_unit) = match value.strip_suffix('w') {
+        Some(weeks) => (weeks, 7),
+        None => (value.strip_suffix('d').unwrap_or(value), 1),
+    };
+    let days: i64 = number.parse().ok()?;
+    Duration::try_days(days.checked_mul(days_per_unit)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::usage::{UsageAction, UsageEvent};
+    use std::sync::Arc;
+
+    fn prompt(name: &str, folder: &str, tags: &[&str], use_count: u32) -> PromptMetadata {
+        PromptMetadata {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            folder: folder.to_string(),
+            description: String::new(),
+            filename: format!("{}.md", name.to_lowercase()),
+            use_count,
+            last_used: None,
+            created: String::new(),
+            updated: String::new(),
+            icon: None,
+            color: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            pinned: false,
+            keyword: None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn content(text: &str) -> Option<CachedContent> {
+        Some(CachedContent {
+            content: Arc::from(text),
+            folded: Arc::from(fold(text)),
+        })
+    }
+
+    #[test]
+    fn test_parse_full_example() {
+        let q = SearchQuery::parse(r#"folder:writing tag:review "Exact Phrase" -draft is:pinned used:>5 tone"#);
+        assert_eq!(q.text, "exact phrase tone");
+
+        let summary: Vec<String> = q
+            .clauses
+            .iter()
+            .map(|c| format!("{}{:?}", if c.negated { "-" } else { "" }, c.condition))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                r#"Folder("writing")"#,
+                r#"Tag("review")"#,
+                r#"Phrase("exact phrase")"#,
+                r#"-Term("draft")"#,
+                "Flag(Pinned)",
+                "UseCount(Greater, 5)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_syntax_falls_back_to_text() {
+        for input in [r#"say "hello"#, "/unclosed", "/(/", "is:starred", "used:>lots", "unused:soon", "folder:"] {
+            let q = SearchQuery::parse(input);
+            assert!(q.clauses.is_empty(), "{:?} should be plain text", input);
+            assert_eq!(q.text, input.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn test_unknown_fields_and_lone_dash_are_text() {
+        let q = SearchQuery::parse("re: meeting - notes");
+        assert!(q.clauses.is_empty());
+        assert_eq!(q.text, "re: meeting - notes");
+    }
+
+    #[test]
+    fn test_quoted_field_values() {
+        let q = SearchQuery::parse(r#"folder:"Client Work" -tag:"old stuff""#);
+        assert!(matches!(&q.clauses[0].condition, Condition::Folder(f) if f == "client work"));
+        assert!(q.clauses[1].negated);
+        assert!(matches!(&q.clauses[1].condition, Condition::Tag(t) if t == "old stuff"));
+    }
+
+    #[test]
+    fn test_accepts_filters_and_exclusions() {
+        let context = FilterContext::default();
+        let review = prompt("Code Review", "coding", &["review"], 8);
+        let draft = prompt("Draft Email", "writing", &[], 0);
+
+        let q = SearchQuery::parse("tag:review used:>=8");
+        assert!(q.accepts(&review, &context, || None));
+        assert!(!q.accepts(&draft, &context, || None));
+
+        let q = SearchQuery::parse("-draft is:unused");
+        assert!(!q.accepts(&draft, &context, || None));
+
+        // Content is only loaded when metadata can't decide
+        let q = SearchQuery::parse(r#""be concise""#);
+        assert!(q.accepts(&review, &context, || content("Please BE CONCISE.")));
+        assert!(!q.accepts(&review, &context, || content("Be thorough.")));
+
+        let q = SearchQuery::parse(r"/colou?r\s+scheme/");
+        assert!(q.accepts(&draft, &context, || content("Pick a Color  scheme")));
+        assert!(SearchQuery::parse("folder:coding").accepts(&review, &context, || panic!("not needed")));
+    }
+
+    #[test]
+    fn test_unused_for_a_period() {
+        let days_ago = |days: i64| (Utc::now() - Duration::days(days)).to_rfc3339();
+        let mut recent = prompt("Recent", "coding", &[], 3);
+        recent.last_used = Some(days_ago(10));
+        let mut stale = prompt("Stale", "coding", &[], 3);
+        stale.last_used = Some(days_ago(90));
+        let never = prompt("Never", "coding", &[], 0);
+        let mut new = prompt("New", "coding", &[], 0);
+        new.created = days_ago(1);
+        // Saving an edit sets `last_used`; the log knows it was last used long ago
+        let mut edited = prompt("Edited", "coding", &[], 3);
+        edited.last_used = Some(days_ago(1));
+
+        let events = [UsageEvent {
+            prompt_id: edited.id.clone(),
+            timestamp: days_ago(90),
+            action: UsageAction::Paste,
+            app: None,
+        }];
+        let context = FilterContext { last_uses: usage::last_use_by_prompt(&events) };
+
+        let q = SearchQuery::parse("unused:60d");
+        assert!(!q.accepts(&recent, &context, || None));
+        assert!(q.accepts(&stale, &context, || None));
+        assert!(q.accepts(&never, &context, || None));
+        assert!(!q.accepts(&new, &context, || None));
+        assert!(q.accepts(&edited, &context, || None));
+        assert!(SearchQuery::parse("unused:1w").accepts(&recent, &context, || None));
+        assert!(!SearchQuery::parse("-unused:1w").accepts(&recent, &context, || None));
+    }
+}
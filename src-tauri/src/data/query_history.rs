@@ -0,0 +1,184 @@
+//! Recent launcher queries, offered as suggestions.
+//!
+//! A query is recorded when a prompt is picked for it, so half-typed queries
+//! don't pile up. Queries that fold to the same text are kept once, with the
+//! latest spelling. The history is saved to `query_history.json` in the
+//! library's data directory.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use super::lock::write_atomic;
+use super::normalize::fold;
+use crate::error::{AppError, AppResult};
+
+const FORMAT_VERSION: u32 = 1;
+
+/// Most queries kept; the least recently used are dropped first
+const MAX_QUERIES: usize = 100;
+
+/// Longest query recorded, in characters
+const MAX_QUERY_CHARS: usize = 200;
+
+/// A past query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentQuery {
+    /// As last typed, trimmed
+    pub query: String,
+    pub last_used: DateTime<Utc>,
+    /// Times a prompt was picked for it
+    pub count: u32,
+}
+
+#[derive(Deserialize)]
+struct StoredHistory {
+    version: u32,
+    queries: Vec<RecentQuery>,
+}
+
+#[derive(Serialize)]
+struct StoredHistoryRef<'a> {
+    version: u32,
+    queries: &'a [RecentQuery],
+}
+
+/// Past queries, most recent first
+#[derive(Default)]
+pub struct QueryHistory {
+    queries: Vec<RecentQuery>,
+}
+
+/// The form queries are compared in: folded, with runs of whitespace collapsed
+fn comparison_key(query: &str) -> String {
+    fold(query).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl QueryHistory {
+    /// Load the history, starting empty if it's missing, unreadable or from
+    /// another format version
+    pub fn load(path: &Path) -> Self {
+        let stored = fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<StoredHistory>(&json).ok())
+            .filter(|stored| stored.version == FORMAT_VERSION);
+        Self { queries: stored.map(|stored| stored.queries).unwrap_or_default() }
+    }
+
+    pub fn save(&self, path: &Path) -> AppResult<()> {
+        let stored = StoredHistoryRef { version: FORMAT_VERSION, queries: &self.queries };
+        let json = serde_json::to_string(&stored)
+            .map_err(|e| AppError::parse("Failed to serialize query history", e))?;
+        write_atomic(path, json.as_bytes()).map_err(|e| {
+            AppError::io("Failed to write query history", e).with_context("path", path.display())
+        })
+    }
+
+    /// Record a query, moving it to the front. Blank and overly long queries
+    /// are ignored.
+    pub fn record(&mut self, query: &str, now: DateTime<Utc>) {
+        let query = query.trim();
+        if query.is_empty() || query.chars().count() > MAX_QUERY_CHARS {
+            return;
+        }
+
+        let key = comparison_key(query);
+        let count = match self.queries.iter().position(|q| comparison_key(&q.query) == key) {
+            Some(i) => self.queries.remove(i).count + 1,
+            None => 1,
+        };
+        self.queries.insert(0, RecentQuery { query: query.to_string(), last_used: now, count });
+        self.queries.truncate(MAX_QUERIES);
+    }
+
+    /// Past queries to offer for `input`: the most recent ones when it's
+    /// empty, otherwise those starting with it, then those containing it.
+    /// The input itself isn't suggested back.
+    pub fn suggestions(&self, input: &str, limit: usize) -> Vec<RecentQuery> {
+        let key = comparison_key(input);
+        if key.is_empty() {
+            return self.queries.iter().take(limit).cloned().collect();
+        }
+
+        let candidates: Vec<(String, &RecentQuery)> = self
+            .queries
+            .iter()
+            .map(|q| (comparison_key(&q.query), q))
+            .filter(|(past, _)| *past != key)
+            .collect();
+        let prefixed = candidates.iter().filter(|(past, _)| past.starts_with(&key));
+        let containing = candidates
+            .iter()
+            .filter(|(past, _)| !past.starts_with(&key) && past.contains(&key));
+
+        prefixed.chain(containing).take(limit).map(|(_, q)| (*q).clone()).collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.queries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn queries(suggestions: Vec<RecentQuery>) -> Vec<String> {
+        suggestions.into_iter().map(|q| q.query).collect()
+    }
+
+    #[test]
+    fn test_records_deduplicated_most_recent_first() {
+        let now = Utc::now();
+        let mut history = QueryHistory::default();
+        history.record("git commit", now - Duration::minutes(3));
+        history.record("review", now - Duration::minutes(2));
+        history.record("  Git  Commit ", now);
+        history.record("   ", now);
+
+        let recent = history.suggestions("", 10);
+        assert_eq!(queries(recent.clone()), ["Git  Commit", "review"]);
+        assert_eq!(recent[0].count, 2);
+        assert_eq!(history.suggestions("", 1).len(), 1);
+
+        for i in 0..MAX_QUERIES + 5 {
+            history.record(&format!("query {}", i), now);
+        }
+        assert_eq!(history.suggestions("", usize::MAX).len(), MAX_QUERIES);
+    }
+
+    #[test]
+    fn test_suggests_prefix_matches_before_substring_matches() {
+        let now = Utc::now();
+        let mut history = QueryHistory::default();
+        history.record("code review", now - Duration::minutes(2));
+        history.record("résumé", now - Duration::minutes(1));
+        history.record("review", now);
+
+        assert_eq!(queries(history.suggestions("rev", 10)), ["review", "code review"]);
+        assert_eq!(queries(history.suggestions("RESU", 10)), ["résumé"]);
+        // What's already typed isn't suggested back
+        assert_eq!(queries(history.suggestions("review", 10)), ["code review"]);
+    }
+
+    #[test]
+    fn test_persists_and_clears() {
+        let dir = std::env::temp_dir().join(format!("promptlight-queries-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("query_history.json");
+
+        let mut history = QueryHistory::default();
+        history.record("review", Utc::now());
+        history.save(&path).unwrap();
+
+        let mut loaded = QueryHistory::load(&path);
+        assert_eq!(queries(loaded.suggestions("", 10)), ["review"]);
+        loaded.clear();
+        assert!(loaded.suggestions("", 10).is_empty());
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,422 @@
+//! Ranking: the one place search results are scored.
+//!
+//! A result's score is the sum of independent signals: how well the text
+//! matched, how much and how recently the prompt was used, whether it's
+//! pinned, how often it was pasted into the current app, and how often it was
+//! picked for the query before. Each signal yields a value (mostly 0..1) that
+//! is scaled to points, then multiplied by its weight in the `RankingProfile`.
+//! When browsing (empty query) usage decides the order; when searching the
+//! text match does and the other signals mostly break ties.
+//!
+//! With `explain` set, search results carry each signal's contribution.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::bm25::{Corpus, DocumentTerms, Field};
+use super::content_index::{self, ContentMatch};
+use super::frecency;
+use super::fuzzy::{FuzzyQuery, MatchKind, MatchRange, TextMatch};
+use super::usage::{self, UsageEvent};
+use super::{PromptMetadata, SearchMatches};
+
+// Classic text scoring: field weights (where the match was found)
+const SCORE_NAME_MATCH: f64 = 100.0;
+const SCORE_FOLDER_MATCH: f64 = 50.0;
+const SCORE_DESCRIPTION_MATCH: f64 = 30.0;
+const SCORE_CONTENT_MATCH: f64 = 15.0;
+
+// Classic text scoring: match quality multipliers
+const MULT_EXACT: f64 = 2.0;
+const MULT_PREFIX: f64 = 1.5;
+const MULT_WORD: f64 = 0.5;
+
+/// Brings BM25F scores (a few points per matched term) into the range of the
+/// classic field scores, so the other signals weigh the same under both
+const BM25_SCALE: f64 = 10.0;
+
+// Most points each signal adds at weight 1, when (browsing, searching)
+const USAGE_MAX: (f64, f64) = (100.0, 10.0);
+const RECENCY_MAX: (f64, f64) = (10.0, 5.0);
+const PINNED_MAX: (f64, f64) = (200.0, 20.0);
+const APP_CONTEXT_MAX: f64 = 60.0;
+/// Enough to lift a prompt picked for the query before above other, similar
+/// text matches
+const SELECTION_MAX: f64 = 120.0;
+
+/// Score of an exact keyword match, which is always listed first
+pub const KEYWORD_SCORE: f64 = 10_000.0;
+
+/// Text relevance algorithm
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TextRanking {
+    /// Hand-tuned field boosts with fuzzy and typo-tolerant matching
+    #[default]
+    Classic,
+    /// BM25F: term rarity and field length aware scoring across all fields
+    Bm25f,
+}
+
+/// Something a result's score is made of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Signal {
+    /// How well the query matched the prompt's text
+    Text,
+    /// Frecency: how often the prompt was used, weighted by how recently
+    Usage,
+    /// How recently the prompt was last used
+    Recency,
+    Pinned,
+    /// Uses in the app the launcher was opened over
+    AppContext,
+    /// Picks for this query (or a longer one starting with it) before
+    Selection,
+    /// The query is the prompt's keyword. Overrides every other signal.
+    Keyword,
+}
+
+/// The weighted signals, in the order they're explained
+const SIGNALS: [Signal; 6] = [
+    Signal::Text,
+    Signal::Usage,
+    Signal::Recency,
+    Signal::Pinned,
+    Signal::AppContext,
+    Signal::Selection,
+];
+
+/// How much each signal counts. 1 is the default; 0 turns a signal off.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SignalWeights {
+    pub text: f64,
+    pub usage: f64,
+    pub recency: f64,
+    pub pinned: f64,
+    pub app_context: f64,
+    pub selection: f64,
+}
+
+impl Default for SignalWeights {
+    fn default() -> Self {
+        Self { text: 1.0, usage: 1.0, recency: 1.0, pinned: 1.0, app_context: 1.0, selection: 1.0 }
+    }
+}
+
+impl SignalWeights {
+    fn get(&self, signal: Signal) -> f64 {
+        match signal {
+            Signal::Text => self.text,
+            Signal::Usage => self.usage,
+            Signal::Recency => self.recency,
+            Signal::Pinned => self.pinned,
+            Signal::AppContext => self.app_context,
+            Signal::Selection => self.selection,
+            Signal::Keyword => 1.0,
+        }
+    }
+}
+
+/// How search results are ranked. Stored flat in the search settings, so
+/// `ranking` keeps naming the text algorithm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankingProfile {
+    #[serde(rename = "ranking", default)]
+    pub text: TextRanking,
+    #[serde(default)]
+    pub weights: SignalWeights,
+}
+
+/// One signal's part of a result's score
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Contribution {
+    pub signal: Signal,
+    /// The signal's value: text points, otherwise 0..1
+    pub value: f64,
+    /// Points added to the score, after scaling and weighting
+    pub points: f64,
+}
+
+/// A result's score, by signal
+#[derive(Debug, Clone, Copy)]
+pub struct Score {
+    /// (value, points) per signal in `SIGNALS`
+    parts: [(f64, f64); SIGNALS.len()],
+}
+
+impl Score {
+    pub fn total(&self) -> f64 {
+        self.parts.iter().map(|(_, points)| points).sum()
+    }
+
+    /// Each signal's contribution, for explaining the ranking
+    pub fn explain(&self) -> Vec<Contribution> {
+        SIGNALS
+            .iter()
+            .zip(self.parts)
+            .map(|(&signal, (value, points))| Contribution { signal, value, points })
+            .collect()
+    }
+}
+
+/// Explanation of a keyword match
+pub fn keyword_explanation() -> Vec<Contribution> {
+    vec![Contribution { signal: Signal::Keyword, value: 1.0, points: KEYWORD_SCORE }]
+}
+
+/// Scores prompts for one search
+pub struct Ranker<'a> {
+    profile: RankingProfile,
+    /// Whether the query is empty
+    browsing: bool,
+    now: DateTime<Utc>,
+    recent_uses: HashMap<&'a str, Vec<DateTime<Utc>>>,
+    app_uses: HashMap<&'a str, u32>,
+    /// Decayed selection weight per prompt id
+    selections: HashMap<String, f64>,
+}
+
+impl<'a> Ranker<'a> {
+    pub fn new(
+        profile: RankingProfile,
+        browsing: bool,
+        events: &'a [UsageEvent],
+        app: Option<&str>,
+        selections: HashMap<String, f64>,
+    ) -> Self {
+        Self {
+            profile,
+            browsing,
+            now: Utc::now(),
+            recent_uses: frecency::recent_uses(events),
+            app_uses: app.map(|app| usage::uses_in_app(events, app)).unwrap_or_default(),
+            selections,
+        }
+    }
+
+    /// Whether the prompt was picked for the query before, so it's listed
+    /// even if its text doesn't match
+    pub fn was_selected(&self, prompt: &PromptMetadata) -> bool {
+        self.selections.contains_key(&prompt.id)
+    }
+
+    /// Score a prompt given its text points (0 when browsing)
+    pub fn score(&self, prompt: &PromptMetadata, text: f64) -> Score {
+        let pick = |(browsing, searching): (f64, f64)| if self.browsing { browsing } else { searching };
+        let usage = frecency::normalize(frecency::prompt_frecency(prompt, &self.recent_uses, self.now));
+        let app_uses = self.app_uses.get(prompt.id.as_str()).copied().unwrap_or(0);
+        let selection = self.selections.get(&prompt.id).copied().unwrap_or(0.0);
+        let recency = frecency::recency(prompt, &self.recent_uses, self.now);
+
+        // (value, points at weight 1) per signal, in `SIGNALS` order
+        let unweighted = [
+            (text, text),
+            (usage, pick(USAGE_MAX) * usage),
+            (recency, pick(RECENCY_MAX) * recency),
+            (f64::from(u8::from(prompt.pinned)), if prompt.pinned { pick(PINNED_MAX) } else { 0.0 }),
+            saturating(APP_CONTEXT_MAX, f64::from(app_uses.min(32))),
+            saturating(SELECTION_MAX, selection),
+        ];
+
+        let mut parts = [(0.0, 0.0); SIGNALS.len()];
+        for ((part, (value, points)), signal) in parts.iter_mut().zip(unweighted).zip(SIGNALS) {
+            *part = (value, points * self.profile.weights.get(signal));
+        }
+        Score { parts }
+    }
+}
+
+/// A count-like signal whose first few units matter most, as (value in 0..1, points)
+fn saturating(max: f64, count: f64) -> (f64, f64) {
+    let value = 1.0 - 0.5f64.powf(count);
+    (value, max * value)
+}
+
+/// Classic text score of a prompt, with the ranges of each field that matched.
+/// `content` is the prompt's content match and the number of query terms.
+pub fn classic_text_score(
+    prompt: &PromptMetadata,
+    fuzzy: &FuzzyQuery,
+    content: Option<(&ContentMatch, usize)>,
+) -> (f64, SearchMatches) {
+    let mut score = 0.0;
+    let mut matches = SearchMatches::default();
+
+    // The name and its aliases count once, by the best match
+    let mut name_multiplier: f64 = 0.0;
+    if let Some(m) = fuzzy.match_text(&prompt.name) {
+        name_multiplier = match_multiplier(&m);
+        matches.name = m.ranges;
+    }
+    for alias in &prompt.aliases {
+        if let Some(m) = fuzzy.match_text(alias) {
+            name_multiplier = name_multiplier.max(match_multiplier(&m));
+        }
+    }
+    score += SCORE_NAME_MATCH * name_multiplier;
+
+    let fields = [
+        (&prompt.folder, SCORE_FOLDER_MATCH, &mut matches.folder),
+        (&prompt.description, SCORE_DESCRIPTION_MATCH, &mut matches.description),
+    ];
+    for (text, weight, ranges) in fields {
+        let Some(m) = fuzzy.match_text(text) else {
+            continue;
+        };
+        score += weight * match_multiplier(&m);
+        *ranges = m.ranges;
+    }
+
+    // Content matches add to metadata matches
+    if let Some((hit, terms)) = content {
+        score += if hit.matched == terms {
+            SCORE_CONTENT_MATCH * hit.quality / terms as f64
+        } else {
+            SCORE_CONTENT_MATCH * MULT_WORD * hit.quality
+        };
+    }
+
+    (score, matches)
+}
+
+/// BM25F text scores of the candidates matching any query term, relative
+/// to the candidates as a whole
+pub fn bm25f_text_scores<'a>(
+    candidates: &[&'a PromptMetadata],
+    terms: &[String],
+    content_hits: &HashMap<String, ContentMatch>,
+    average_content_length: f64,
+) -> Vec<(&'a PromptMetadata, f64)> {
+    let mut corpus = Corpus::new(terms.len());
+    // Content lengths are only looked up for documents that matched
+    corpus.set_average_length(Field::Content, average_content_length);
+
+    let mut matched = Vec::new();
+    for prompt in candidates {
+        let mut document = DocumentTerms::new(terms.len());
+        document.add_text(Field::Name, &prompt.name, terms);
+        for alias in &prompt.aliases {
+            document.add_text(Field::Name, alias, terms);
+        }
+        document.add_text(Field::Folder, &prompt.folder, terms);
+        document.add_text(Field::Description, &prompt.description, terms);
+        for tag in &prompt.tags {
+            document.add_text(Field::Tags, tag, terms);
+        }
+        let key = content_index::document_key(&prompt.folder, &prompt.filename);
+        if let Some(hit) = content_hits.get(&key) {
+            document.set_counts(Field::Content, f64::from(hit.length), &hit.frequencies);
+        }
+
+        corpus.add(&document);
+        if document.has_match() {
+            matched.push((*prompt, document));
+        }
+    }
+
+    matched
+        .into_iter()
+        .map(|(prompt, document)| (prompt, BM25_SCALE * corpus.score(&document)))
+        .collect()
+}
+
+/// Score of a folder or action title, matched like a prompt name, with the
+/// matched ranges
+pub fn title_score(title: &str, fuzzy: &FuzzyQuery) -> Option<(f64, Vec<MatchRange>)> {
+    let m = fuzzy.match_text(title)?;
+    Some((SCORE_NAME_MATCH * match_multiplier(&m), m.ranges))
+}
+
+/// How strongly a field match counts, relative to a plain substring match
+fn match_multiplier(m: &TextMatch) -> f64 {
+    match m.kind {
+        MatchKind::Exact => MULT_EXACT,
+        MatchKind::Prefix => MULT_PREFIX,
+        MatchKind::Substring => 1.0,
+        // Every term found in this field (possibly fuzzily)
+        MatchKind::Terms { matched, total } if matched == total => m.quality,
+        // Some terms found: partial credit per term
+        MatchKind::Terms { matched, .. } => MULT_WORD * m.quality * matched as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::usage::UsageAction;
+
+    fn prompt(id: &str, pinned: bool, last_used: Option<String>) -> PromptMetadata {
+        PromptMetadata {
+            id: id.to_string(),
+            name: id.to_string(),
+            folder: "uncategorized".to_string(),
+            description: String::new(),
+            filename: format!("{}.md", id),
+            use_count: 0,
+            last_used,
+            created: String::new(),
+            updated: String::new(),
+            icon: None,
+            color: None,
+            tags: Vec::new(),
+            pinned,
+            keyword: None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn used_now(id: &str) -> UsageEvent {
+        UsageEvent {
+            prompt_id: id.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            action: UsageAction::Paste,
+            app: None,
+        }
+    }
+
+    #[test]
+    fn test_weights_scale_each_signal() {
+        let pinned = prompt("a", true, None);
+        let events = [used_now("a")];
+        let ranker = Ranker::new(RankingProfile::default(), false, &events, None, HashMap::new());
+        let score = ranker.score(&pinned, 50.0);
+        // One use just now: full recency, plus a little usage
+        let usage = USAGE_MAX.1 * frecency::normalize(100.0);
+        assert!((score.total() - (50.0 + 20.0 + 5.0 + usage)).abs() < 1e-9);
+
+        let profile = RankingProfile {
+            weights: SignalWeights { text: 2.0, pinned: 0.0, ..SignalWeights::default() },
+            ..RankingProfile::default()
+        };
+        let ranker = Ranker::new(profile, false, &events, None, HashMap::new());
+        let explained = ranker.score(&pinned, 50.0).explain();
+        let points = |signal| explained.iter().find(|c| c.signal == signal).unwrap().points;
+        assert_eq!(points(Signal::Text), 100.0);
+        assert_eq!(points(Signal::Pinned), 0.0);
+        assert!((points(Signal::Recency) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_browsing_puts_pinned_first() {
+        let events = [used_now("b")];
+        let ranker = Ranker::new(RankingProfile::default(), true, &events, None, HashMap::new());
+        let pinned = ranker.score(&prompt("a", true, None), 0.0).total();
+        let recent = ranker.score(&prompt("b", false, None), 0.0).total();
+        assert!(pinned > recent, "{} should beat {}", pinned, recent);
+    }
+
+    #[test]
+    fn test_profile_reads_legacy_settings() {
+        let profile: RankingProfile = serde_json::from_str(r#"{"ranking":"bm25f"}"#).unwrap();
+        assert_eq!(profile, RankingProfile { text: TextRanking::Bm25f, ..RankingProfile::default() });
+
+        let json = serde_json::to_value(RankingProfile::default()).unwrap();
+        assert_eq!(json["ranking"], "classic");
+        assert_eq!(json["weights"]["appContext"], 1.0);
+    }
+}
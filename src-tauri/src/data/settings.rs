@@ -1,7 +1,13 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
 
+use crate::error::{AppError, AppResult, ErrorCode};
+
+use super::cache::FileStamp;
 use super::get_base_data_dir;
+use super::ranking::RankingProfile;
 
 /// General application settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +84,96 @@ impl Default for AppearanceSettings {
     }
 }
 
+/// Number of search results the launcher shows before loading more
+pub const DEFAULT_RESULT_LIMIT: usize = 15;
+
+/// Search settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchSettings {
+    /// Text algorithm (`ranking`) and signal weights (`weights`)
+    #[serde(flatten)]
+    pub ranking: RankingProfile,
+    /// Results per page when the caller doesn't ask for a limit
+    #[serde(default = "default_result_limit")]
+    pub result_limit: usize,
+    /// Whether launcher queries are remembered and offered as suggestions
+    #[serde(default = "default_history_enabled")]
+    pub history_enabled: bool,
+}
+
+fn default_result_limit() -> usize {
+    DEFAULT_RESULT_LIMIT
+}
+
+fn default_history_enabled() -> bool {
+    true
+}
+
+impl Default for SearchSettings {
+    fn default() -> Self {
+        Self {
+            ranking: RankingProfile::default(),
+            result_limit: default_result_limit(),
+            history_enabled: default_history_enabled(),
+        }
+    }
+}
+
+/// Template variable settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableSettings {
+    /// Whether values entered for variables are remembered and offered again
+    #[serde(default = "default_remember_values")]
+    pub remember_values: bool,
+    /// Values remembered per variable
+    #[serde(default = "default_remembered_values")]
+    pub remembered_values: usize,
+    /// Whether a value entered in one prompt is offered for the same variable
+    /// name in others
+    #[serde(default)]
+    pub share_across_prompts: bool,
+    /// Variables never remembered: those whose names contain one of these,
+    /// ignoring case
+    #[serde(default = "default_sensitive_variables")]
+    pub sensitive: Vec<String>,
+}
+
+fn default_remember_values() -> bool {
+    true
+}
+
+fn default_remembered_values() -> usize {
+    5
+}
+
+fn default_sensitive_variables() -> Vec<String> {
+    ["password", "secret", "token"].map(String::from).to_vec()
+}
+
+impl VariableSettings {
+    /// Whether values of the variable `name` must not be remembered
+    pub fn is_sensitive(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.sensitive
+            .iter()
+            .map(|s| s.trim().to_lowercase())
+            .any(|s| !s.is_empty() && name.contains(&s))
+    }
+}
+
+impl Default for VariableSettings {
+    fn default() -> Self {
+        Self {
+            remember_values: default_remember_values(),
+            remembered_values: default_remembered_values(),
+            share_across_prompts: false,
+            sensitive: default_sensitive_variables(),
+        }
+    }
+}
+
 /// Complete application settings
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -86,62 +182,130 @@ pub struct AppSettings {
     pub sync: SyncSettings,
     #[serde(default)]
     pub appearance: AppearanceSettings,
+    #[serde(default)]
+    pub search: SearchSettings,
+    #[serde(default)]
+    pub variables: VariableSettings,
 }
 
 impl AppSettings {
-    /// Load settings from disk, returns defaults if file doesn't exist
+    /// Load settings from disk, returns defaults if file doesn't exist.
+    /// The parsed file is cached until it changes on disk.
     pub fn load() -> Self {
-        let path = get_base_data_dir().join("settings.json");
-        if path.exists() {
-            fs::read_to_string(&path)
-                .ok()
-                .and_then(|s| serde_json::from_str(&s).ok())
-                .unwrap_or_default()
-        } else {
-            Self::default()
-        }
+        static CACHE: SettingsCache = Mutex::new(None);
+        load_cached(&get_base_data_dir().join("settings.json"), &CACHE)
     }
 
     /// Save settings to disk
-    pub fn save(&self) -> Result<(), String> {
+    pub fn save(&self) -> AppResult<()> {
         let dir = get_base_data_dir();
-        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        fs::create_dir_all(&dir).map_err(|e| {
+            AppError::io("Failed to create settings directory", e).with_context("path", dir.display())
+        })?;
 
         let path = dir.join("settings.json");
-        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
-        fs::write(path, content).map_err(|e| e.to_string())
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| AppError::parse("Failed to serialize settings", e))?;
+        fs::write(&path, content).map_err(|e| {
+            AppError::io("Failed to write settings", e).with_context("path", path.display())
+        })
+    }
+}
+
+/// The last settings read and the stamp of the file they were read from
+type SettingsCache = Mutex<Option<(FileStamp, AppSettings)>>;
+
+/// Read settings from `path`, reusing the cached copy while the file is unchanged
+fn load_cached(path: &Path, cache: &SettingsCache) -> AppSettings {
+    let Some(stamp) = FileStamp::of(path) else {
+        return AppSettings::default();
+    };
+
+    let mut cached = cache.lock().unwrap_or_else(PoisonError::into_inner);
+    match &*cached {
+        Some((seen, settings)) if *seen == stamp => settings.clone(),
+        _ => {
+            let settings: AppSettings = fs::read_to_string(path)
+                .ok()
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default();
+            *cached = Some((stamp, settings.clone()));
+            settings
+        }
     }
 }
 
 /// Get current settings
 #[tauri::command]
-pub fn get_settings() -> Result<AppSettings, String> {
+pub fn get_settings() -> AppResult<AppSettings> {
     Ok(AppSettings::load())
 }
 
 /// Save settings
 #[tauri::command]
-pub fn save_settings(settings: AppSettings) -> Result<(), String> {
+pub fn save_settings(settings: AppSettings) -> AppResult<()> {
     settings.save()
 }
 
 /// Get auto-launch status from the system
 #[tauri::command]
-pub fn get_autostart_enabled(app: tauri::AppHandle) -> Result<bool, String> {
+pub fn get_autostart_enabled(app: tauri::AppHandle) -> AppResult<bool> {
     use tauri_plugin_autostart::ManagerExt;
     app.autolaunch()
         .is_enabled()
-        .map_err(|e| e.to_string())
+        .map_err(|e| autostart_error("Failed to read auto-launch status", e))
 }
 
 /// Set auto-launch status
 #[tauri::command]
-pub fn set_autostart_enabled(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+pub fn set_autostart_enabled(app: tauri::AppHandle, enabled: bool) -> AppResult<()> {
     use tauri_plugin_autostart::ManagerExt;
     let autostart = app.autolaunch();
     if enabled {
-        autostart.enable().map_err(|e| e.to_string())
+        autostart
+            .enable()
+            .map_err(|e| autostart_error("Failed to enable auto-launch", e))
     } else {
-        autostart.disable().map_err(|e| e.to_string())
+        autostart
+            .disable()
+            .map_err(|e| autostart_error("Failed to disable auto-launch", e))
+    }
+}
+
+fn autostart_error(action: &str, err: impl std::fmt::Display) -> AppError {
+    AppError::new(ErrorCode::Platform, format!("{}: {}", action, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_settings_follow_the_file() {
+        let dir = std::env::temp_dir().join(format!("promptlight-settings-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("settings.json");
+        let cache = SettingsCache::new(None);
+        let write = |history_enabled: bool| {
+            let mut settings = AppSettings::default();
+            settings.search.history_enabled = history_enabled;
+            fs::write(&path, serde_json::to_string_pretty(&settings).unwrap()).unwrap();
+        };
+
+        write(false);
+        assert!(!load_cached(&path, &cache).search.history_enabled);
+        assert!(cache.lock().unwrap().is_some());
+
+        // Another window (or instance) saved new settings
+        write(true);
+        assert!(load_cached(&path, &cache).search.history_enabled);
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            load_cached(&path, &cache).search.history_enabled,
+            SearchSettings::default().history_enabled
+        );
+
+        fs::remove_dir_all(&dir).ok();
     }
 }
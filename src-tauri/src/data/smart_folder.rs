@@ -0,0 +1,143 @@
+//! Smart folders: saved searches listed next to real folders.
+//!
+//! A smart folder keeps a launcher query, filters included (`unused:60d`,
+//! `folder:work review`, ...). Its prompts aren't stored: they're found by
+//! running the query each time the folders are read, so they're always
+//! current. The definitions live in the index and sync with the user meta
+//! document.
+
+use serde::{Deserialize, Serialize};
+
+use super::page::Page;
+use super::store::DataStore;
+use super::{PromptIndex, SearchContext};
+use crate::error::{AppError, AppResult};
+
+/// A saved search shown as a folder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartFolder {
+    pub name: String,
+    /// Launcher query, filters included
+    pub query: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Ids of the prompts matching the query, best first. Filled in when
+    /// folders are read; never stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_ids: Option<Vec<String>>,
+}
+
+/// Real and smart folders, as returned by `get_folders`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderList {
+    pub folders: Vec<String>,
+    pub smart_folders: Vec<SmartFolder>,
+}
+
+/// Add `folder` to the index, or replace the smart folder named `previous`
+/// (to edit or rename it). Names are unique across real and smart folders.
+pub fn upsert(index: &mut PromptIndex, folder: &SmartFolder, previous: Option<&str>) -> AppResult<()> {
+    let name = folder.name.trim();
+    let query = folder.query.trim();
+    if name.is_empty() {
+        return Err(AppError::invalid_input("Smart folder name cannot be empty"));
+    }
+    if query.is_empty() {
+        return Err(AppError::invalid_input("Smart folder query cannot be empty"));
+    }
+
+    let position = match previous {
+        Some(previous) => Some(position(index, previous).ok_or_else(|| not_found(previous))?),
+        None => None,
+    };
+
+    let taken = index.folders.iter().any(|f| f.eq_ignore_ascii_case(name))
+        || index
+            .smart_folders
+            .iter()
+            .enumerate()
+            .any(|(i, f)| Some(i) != position && f.name.eq_ignore_ascii_case(name));
+    if taken {
+        return Err(AppError::already_exists("A folder with that name already exists")
+            .with_context("folder", name));
+    }
+
+    let saved = SmartFolder {
+        name: name.to_string(),
+        query: query.to_string(),
+        icon: folder.icon.clone(),
+        color: folder.color.clone(),
+        prompt_ids: None,
+    };
+    match position {
+        Some(i) => index.smart_folders[i] = saved,
+        None => index.smart_folders.push(saved),
+    }
+    Ok(())
+}
+
+/// Remove the smart folder named `name` (its prompts are untouched)
+pub fn remove(index: &mut PromptIndex, name: &str) -> AppResult<()> {
+    let i = position(index, name).ok_or_else(|| not_found(name))?;
+    index.smart_folders.remove(i);
+    Ok(())
+}
+
+/// Whether a smart folder is named `name`, which real folders can't also use
+pub fn exists(index: &PromptIndex, name: &str) -> bool {
+    position(index, name).is_some()
+}
+
+/// Fill in each smart folder's prompts by running its query
+pub async fn evaluate<S: DataStore + ?Sized>(store: &S, folders: &mut [SmartFolder]) -> AppResult<()> {
+    // Contents shouldn't depend on the app the launcher was last opened over
+    let context = SearchContext::default();
+    for folder in folders {
+        let page = store.search_prompts(&folder.query, &context, Page::new(0, usize::MAX)).await?;
+        folder.prompt_ids = Some(page.results.into_iter().map(|r| r.prompt.id).collect());
+    }
+    Ok(())
+}
+
+fn position(index: &PromptIndex, name: &str) -> Option<usize> {
+    index.smart_folders.iter().position(|f| f.name.eq_ignore_ascii_case(name.trim()))
+}
+
+fn not_found(name: &str) -> AppError {
+    AppError::folder_not_found(name.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smart(name: &str, query: &str) -> SmartFolder {
+        SmartFolder { name: name.to_string(), query: query.to_string(), icon: None, color: None, prompt_ids: None }
+    }
+
+    #[test]
+    fn test_upsert_and_remove() {
+        let mut index = PromptIndex::default();
+        upsert(&mut index, &smart(" Stale ", "unused:60d"), None).unwrap();
+        assert_eq!(index.smart_folders, [smart("Stale", "unused:60d")]);
+
+        // Names are unique across real and smart folders
+        assert!(upsert(&mut index, &smart("stale", "review"), None).is_err());
+        assert!(upsert(&mut index, &smart("Uncategorized", "review"), None).is_err());
+        assert!(upsert(&mut index, &smart("Reviews", "  "), None).is_err());
+
+        // Editing keeps the name; renaming replaces it
+        upsert(&mut index, &smart("Stale", "unused:30d"), Some("stale")).unwrap();
+        upsert(&mut index, &smart("Forgotten", "unused:30d"), Some("Stale")).unwrap();
+        assert_eq!(index.smart_folders, [smart("Forgotten", "unused:30d")]);
+        assert!(upsert(&mut index, &smart("Other", "x"), Some("Stale")).is_err());
+
+        remove(&mut index, "forgotten").unwrap();
+        assert!(index.smart_folders.is_empty());
+        assert!(remove(&mut index, "forgotten").is_err());
+    }
+}
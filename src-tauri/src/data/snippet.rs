@@ -0,0 +1,295 @@
+//! Content snippets for search results.
+//!
+//! A prompt that matches on its content alone shows nothing in the launcher
+//! that explains the match. A snippet is a short excerpt of the content
+//! around the best match: the stretch covering the most distinct query terms,
+//! trimmed at word boundaries, with ellipses where text was cut and the
+//! matched words highlighted. Terms match tokens the same way the content
+//! index does, so a snippet is found wherever content scored.
+
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use super::content_index::{token_spans, PREFIX_QUALITY};
+use super::fuzzy::MatchRange;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Longest snippet in characters, not counting ellipses
+const SNIPPET_CHARS: usize = 100;
+
+/// Characters of context kept before the first match
+const LEAD_CHARS: usize = 24;
+
+const ELLIPSIS: char = '…';
+
+/// An excerpt of a prompt's content around its best match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snippet {
+    /// Excerpt on a single line, with ellipses where content was cut
+    pub text: String,
+    /// Matched words within `text`, in UTF-16 code units
+    pub highlights: Vec<MatchRange>,
+}
+
+/// A query term found in the content
+struct Hit {
+    /// Byte range of the whole token
+    token: Range<usize>,
+    /// End of the highlighted part (the term's length for a prefix match)
+    highlight_end: usize,
+    term: usize,
+    quality: f64,
+    /// Character offset of the token
+    char_start: usize,
+}
+
+/// Build a snippet around the best match of the query terms in `content`,
+/// or `None` if no term occurs in it
+pub fn content_snippet(content: &str, terms: &[String]) -> Option<Snippet> {
+    let hits = find_hits(content, terms);
+    let best = best_window(&hits, terms.len())?;
+    let anchor = &hits[best];
+
+    let start = window_start(content, anchor.token.start);
+    let end = window_end(content, start, &hits[best..]);
+    let inside = hits[best..]
+        .iter()
+        .take_while(|hit| hit.token.start < end)
+        .filter(|hit| hit.highlight_end <= end);
+
+    Some(render(content, start..end, inside))
+}
+
+fn find_hits(content: &str, terms: &[String]) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    let mut cursor = (0, 0); // (byte, char) of the last hit
+
+    for (token, lowered) in token_spans(content) {
+        let best = terms
+            .iter()
+            .enumerate()
+            .filter_map(|(i, term)| {
+                if lowered == *term {
+                    Some((i, 1.0))
+                } else if lowered.starts_with(term.as_str()) {
+                    Some((i, PREFIX_QUALITY))
+                } else {
+                    None
+                }
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        let Some((term, quality)) = best else {
+            continue;
+        };
+
+        let highlight_end = if quality < 1.0 {
+            // Terms are folded, so count graphemes: "re" covers "Ré" in "Résumé"
+            let prefix_len = terms[term].chars().count();
+            content[token.clone()]
+                .grapheme_indices(true)
+                .nth(prefix_len)
+                .map_or(token.end, |(i, _)| token.start + i)
+        } else {
+            token.end
+        };
+
+        cursor.1 += content[cursor.0..token.start].chars().count();
+        cursor.0 = token.start;
+        hits.push(Hit { token, highlight_end, term, quality, char_start: cursor.1 });
+    }
+
+    hits
+}
+
+/// Index of the hit starting the window that covers the most distinct terms,
+/// then the best matches; the earliest such window wins ties
+fn best_window(hits: &[Hit], term_count: usize) -> Option<usize> {
+    let reach = SNIPPET_CHARS - LEAD_CHARS;
+    let mut best: Option<(usize, usize, f64)> = None;
+
+    for (i, anchor) in hits.iter().enumerate() {
+        let mut seen = vec![false; term_count];
+        let mut quality = 0.0;
+        for hit in hits[i..].iter().take_while(|hit| hit.char_start < anchor.char_start + reach) {
+            if !seen[hit.term] {
+                seen[hit.term] = true;
+                quality += hit.quality;
+            }
+        }
+        let distinct = seen.iter().filter(|&&found| found).count();
+
+        let better = match best {
+            None => true,
+            Some((_, best_distinct, best_quality)) => {
+                distinct > best_distinct || (distinct == best_distinct && quality > best_quality)
+            }
+        };
+        if better {
+            best = Some((i, distinct, quality));
+        }
+    }
+
+    best.map(|(i, _, _)| i)
+}
+
+/// Start a little before the anchor, at the beginning of a word
+fn window_start(content: &str, anchor: usize) -> usize {
+    let Some((lead, _)) = content[..anchor].char_indices().rev().nth(LEAD_CHARS - 1) else {
+        return 0;
+    };
+    match content[lead..anchor].find(char::is_whitespace) {
+        Some(space) => lead + space,
+        // One long word before the match: start at the match itself
+        None => anchor,
+    }
+}
+
+/// End after `SNIPPET_CHARS`, at the end of a word
+fn window_end(content: &str, start: usize, hits: &[Hit]) -> usize {
+    let Some((limit, _)) = content[start..].char_indices().nth(SNIPPET_CHARS) else {
+        return content.len();
+    };
+    let limit = start + limit;
+    // Keep the anchor highlighted even when it sits in one very long word
+    let floor = hits.first().map_or(start, |hit| hit.highlight_end.min(limit));
+    match content[floor..limit].rfind(char::is_whitespace) {
+        Some(space) => floor + space,
+        None => limit,
+    }
+}
+
+/// Lay out the excerpt on one line and locate the highlights in it
+fn render<'a>(content: &str, window: Range<usize>, hits: impl Iterator<Item = &'a Hit>) -> Snippet {
+    let excerpt = &content[window.clone()];
+    let leading = excerpt.len() - excerpt.trim_start().len();
+    let start = window.start + leading;
+    let end = start + excerpt.trim().len();
+
+    // Highlight boundaries in byte order: (byte, index into `bounds`)
+    let mut boundaries: Vec<(usize, usize)> = Vec::new();
+    let mut bounds: Vec<[usize; 2]> = Vec::new();
+    for hit in hits {
+        boundaries.push((hit.token.start, bounds.len() * 2));
+        boundaries.push((hit.highlight_end, bounds.len() * 2 + 1));
+        bounds.push([0, 0]);
+    }
+    boundaries.sort_unstable();
+    let mut boundaries = boundaries.into_iter().peekable();
+
+    let mut text = String::with_capacity(end - start + 2 * ELLIPSIS.len_utf8());
+    let mut utf16_len = 0;
+    if !content[..start].trim().is_empty() {
+        text.push(ELLIPSIS);
+        utf16_len += ELLIPSIS.len_utf16();
+    }
+
+    let mut pending_space = false;
+    for (offset, c) in content[start..end].char_indices() {
+        let byte = start + offset;
+        if c.is_whitespace() {
+            pending_space = true;
+        } else if pending_space {
+            text.push(' ');
+            utf16_len += 1;
+            pending_space = false;
+        }
+        while let Some(&(at, slot)) = boundaries.peek() {
+            if at > byte {
+                break;
+            }
+            bounds[slot / 2][slot % 2] = utf16_len;
+            boundaries.next();
+        }
+        if !c.is_whitespace() {
+            text.push(c);
+            utf16_len += c.len_utf16();
+        }
+    }
+    for (_, slot) in boundaries {
+        bounds[slot / 2][slot % 2] = utf16_len;
+    }
+
+    if !content[end..].trim().is_empty() {
+        text.push(ELLIPSIS);
+    }
+
+    // CJK bigrams overlap, so merge their highlights
+    let mut highlights: Vec<MatchRange> = Vec::with_capacity(bounds.len());
+    for [start, end] in bounds {
+        match highlights.last_mut() {
+            Some(last) if start <= last.end => last.end = last.end.max(end),
+            _ => highlights.push(MatchRange { start, end }),
+        }
+    }
+
+    Snippet { text, highlights }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::content_index::tokenize;
+
+    fn snippet(content: &str, query: &str) -> Option<Snippet> {
+        content_snippet(content, &tokenize(query).collect::<Vec<_>>())
+    }
+
+    fn highlighted(snippet: &Snippet) -> Vec<String> {
+        let units: Vec<u16> = snippet.text.encode_utf16().collect();
+        snippet
+            .highlights
+            .iter()
+            .map(|range| String::from_utf16(&units[range.start..range.end]).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_short_content_is_shown_whole() {
+        let snippet = snippet("Review this\n\n  pull request.", "pull").unwrap();
+        assert_eq!(snippet.text, "Review this pull request.");
+        assert_eq!(highlighted(&snippet), ["pull"]);
+        assert!(self::snippet("Review this pull request.", "merge").is_none());
+    }
+
+    #[test]
+    fn test_long_content_is_trimmed_at_words_with_ellipses() {
+        let filler = "lorem ipsum dolor sit amet ".repeat(10);
+        let content = format!("{}the idempotent retry policy {}", filler, filler);
+        let snippet = snippet(&content, "idempotent").unwrap();
+
+        assert!(snippet.text.starts_with(ELLIPSIS) && snippet.text.ends_with(ELLIPSIS));
+        assert!(snippet.text.chars().count() <= SNIPPET_CHARS + 2);
+
+        // Cut at whole words on both sides
+        let inner = snippet.text.trim_matches(ELLIPSIS);
+        let at = content.find(inner).unwrap();
+        assert!(content[..at].ends_with(' '));
+        assert!(content[at + inner.len()..].starts_with(' '));
+        assert_eq!(highlighted(&snippet), ["idempotent"]);
+    }
+
+    #[test]
+    fn test_window_with_most_distinct_terms_wins() {
+        let filler = "and then some more words to pad the text out ".repeat(4);
+        let content = format!("retry once. {}retry with backoff and jitter.", filler);
+        let snippet = snippet(&content, "retry backoff").unwrap();
+        assert_eq!(highlighted(&snippet), ["retry", "backoff"]);
+    }
+
+    #[test]
+    fn test_prefix_highlights_and_utf16_offsets() {
+        let snippet = snippet("Résumé 📄 summarizer for café notes", "summ cafe").unwrap();
+        assert_eq!(highlighted(&snippet), ["summ", "café"]);
+
+        // Folded terms highlight the original text
+        let snippet = self::snippet("Ｒésumé tips", "resu").unwrap();
+        assert_eq!(highlighted(&snippet), ["Ｒésu"]);
+    }
+
+    #[test]
+    fn test_cjk_bigram_highlights_merge() {
+        let snippet = snippet("这是日本語の翻訳です", "日本語").unwrap();
+        assert_eq!(highlighted(&snippet), ["日本語"]);
+    }
+}
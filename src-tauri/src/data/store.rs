@@ -1,6 +1,12 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
 
-use super::{Prompt, PromptIndex, PromptMetadata, SearchResult};
+use super::page::{IndexPage, Page, PromptSort, SearchPage, SortDirection};
+use super::query_history::RecentQuery;
+use super::smart_folder::SmartFolder;
+use super::usage::{UsageAction, UsageEvent};
+use super::{Prompt, PromptIndex, PromptMetadata, SearchContext};
+use crate::error::AppResult;
 
 /// DataStore trait for abstracting storage backends.
 ///
@@ -11,80 +17,103 @@ pub trait DataStore: Send + Sync {
     // ==================== Index Operations ====================
 
     /// Load the full prompt index
-    async fn get_index(&self) -> Result<PromptIndex, String>;
+    async fn get_index(&self) -> AppResult<PromptIndex>;
 
-    /// Save the full prompt index
-    async fn save_index(&self, index: &PromptIndex) -> Result<(), String>;
+    /// Load one page of the prompt list, sorted
+    async fn get_index_page(
+        &self,
+        sort: PromptSort,
+        direction: SortDirection,
+        page: Page,
+    ) -> AppResult<IndexPage>;
 
     // ==================== Prompt Operations ====================
 
     /// Get a prompt by ID (includes content)
-    async fn get_prompt(&self, id: &str) -> Result<Prompt, String>;
+    async fn get_prompt(&self, id: &str) -> AppResult<Prompt>;
 
     /// Save a prompt (creates or updates)
     /// Returns the updated metadata
-    async fn save_prompt(&self, prompt: &Prompt) -> Result<PromptMetadata, String>;
+    async fn save_prompt(&self, prompt: &Prompt) -> AppResult<PromptMetadata>;
 
     /// Delete a prompt by ID
-    async fn delete_prompt(&self, id: &str) -> Result<(), String>;
+    async fn delete_prompt(&self, id: &str) -> AppResult<()>;
 
     // ==================== Folder Operations ====================
 
     /// Add a new folder
-    async fn add_folder(&self, name: &str) -> Result<(), String>;
+    async fn add_folder(&self, name: &str) -> AppResult<()>;
 
     /// Rename a folder
-    async fn rename_folder(&self, old_name: &str, new_name: &str) -> Result<(), String>;
+    async fn rename_folder(&self, old_name: &str, new_name: &str) -> AppResult<()>;
 
     /// Delete a folder (moves prompts to uncategorized)
-    async fn delete_folder(&self, name: &str) -> Result<(), String>;
+    async fn delete_folder(&self, name: &str) -> AppResult<()>;
 
-    // ==================== Stats Operations ====================
+    /// Create a smart folder, or replace the one named `previous` (to edit or rename it)
+    async fn save_smart_folder(&self, folder: &SmartFolder, previous: Option<&str>) -> AppResult<()>;
 
-    /// Record usage of a prompt (increment count, update last_used)
-    async fn record_usage(&self, id: &str) -> Result<(), String>;
+    /// Delete a smart folder (its prompts are untouched)
+    async fn delete_smart_folder(&self, name: &str) -> AppResult<()>;
 
-    // ==================== Search Operations ====================
+    // ==================== Stats Operations ====================
 
-    /// Search prompts by query
-    /// Empty query returns all prompts sorted by recency
-    async fn search_prompts(&self, query: &str) -> Result<Vec<SearchResult>, String>;
+    /// Record usage of a prompt (increment count, update last_used,
+    /// append to the usage log). `app` is the app it was pasted into, if known,
+    /// and `query` what was typed in the launcher to find it.
+    async fn record_usage(
+        &self,
+        id: &str,
+        action: UsageAction,
+        app: Option<&str>,
+        query: Option<&str>,
+    ) -> AppResult<()>;
 
-    // ==================== Utility Operations ====================
+    /// Forget which prompts were picked for which queries
+    async fn clear_selection_history(&self) -> AppResult<()>;
 
-    /// Get all folder names
-    async fn get_folders(&self) -> Result<Vec<String>, String> {
-        let index = self.get_index().await?;
-        Ok(index.folders)
-    }
-}
+    /// Get every recorded use, oldest first
+    async fn get_usage_history(&self) -> AppResult<Vec<UsageEvent>>;
 
-/// Error type for data store operations
-#[derive(Debug)]
-pub enum DataStoreError {
-    NotFound(String),
-    AlreadyExists(String),
-    InvalidInput(String),
-    IoError(String),
-    ParseError(String),
-}
-
-impl std::fmt::Display for DataStoreError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            DataStoreError::NotFound(msg) => write!(f, "Not found: {}", msg),
-            DataStoreError::AlreadyExists(msg) => write!(f, "Already exists: {}", msg),
-            DataStoreError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
-            DataStoreError::IoError(msg) => write!(f, "IO error: {}", msg),
-            DataStoreError::ParseError(msg) => write!(f, "Parse error: {}", msg),
-        }
-    }
-}
-
-impl std::error::Error for DataStoreError {}
+    // ==================== Search Operations ====================
 
-impl From<DataStoreError> for String {
-    fn from(err: DataStoreError) -> Self {
-        err.to_string()
-    }
+    /// Search prompts by query, returning one page of results
+    /// Empty query returns all prompts sorted by recency
+    async fn search_prompts(
+        &self,
+        query: &str,
+        context: &SearchContext,
+        page: Page,
+    ) -> AppResult<SearchPage>;
+
+    /// Add a launcher query to the recent query history
+    async fn record_query(&self, query: &str) -> AppResult<()>;
+
+    /// Up to `limit` past queries to offer for a partial (or empty) input
+    async fn query_suggestions(&self, input: &str, limit: usize) -> AppResult<Vec<RecentQuery>>;
+
+    /// Forget all recent queries
+    async fn clear_query_history(&self) -> AppResult<()>;
+
+    /// Remember the values a prompt was pasted with, the last `limit` per variable
+    async fn record_variable_values(
+        &self,
+        prompt_id: &str,
+        values: &HashMap<String, String>,
+        limit: usize,
+    ) -> AppResult<()>;
+
+    /// Up to `limit` recent values for each of a prompt's variables in
+    /// `names` (those with none are left out), entered for any prompt with
+    /// the same variable if `shared`
+    async fn recent_variable_values(
+        &self,
+        prompt_id: &str,
+        names: &[&str],
+        shared: bool,
+        limit: usize,
+    ) -> AppResult<HashMap<String, Vec<String>>>;
+
+    /// Forget all values entered for variables
+    async fn clear_variable_history(&self) -> AppResult<()>;
 }
@@ -7,18 +7,24 @@
 //! - Migration from anonymous to user storage on first login
 //! - Download/upload operations for explicit sync
 
+use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use async_trait::async_trait;
 
 use super::firestore::{FirestoreClient, UserMeta};
 use super::local::LocalDataStore;
 use super::store::DataStore;
-use super::{Prompt, PromptIndex, PromptMetadata, SearchResult};
+use super::usage::{UsageAction, UsageEvent};
+use super::page::{IndexPage, Page, PromptSort, SearchPage, SortDirection};
+use super::query_history::RecentQuery;
+use super::smart_folder::SmartFolder;
+use super::{Prompt, PromptIndex, PromptMetadata, SearchContext};
+use crate::error::{AppError, AppResult, ErrorCode};
 
 /// Sync service state
 struct SyncState {
     /// The current local data store (switches based on auth state)
-    local_store: LocalDataStore,
+    local_store: Arc<LocalDataStore>,
     /// Firestore client (always available, but only used when authenticated)
     firestore: FirestoreClient,
     /// Current user ID (None = anonymous)
@@ -39,7 +45,7 @@ impl SyncService {
     pub fn new(project_id: &str) -> Self {
         Self {
             state: RwLock::new(SyncState {
-                local_store: LocalDataStore::new(),
+                local_store: Arc::new(LocalDataStore::new()),
                 firestore: FirestoreClient::new(project_id),
                 user_id: None,
                 id_token: None,
@@ -63,7 +69,7 @@ impl SyncService {
 
                 Self {
                     state: RwLock::new(SyncState {
-                        local_store: user_store,
+                        local_store: Arc::new(user_store),
                         firestore: FirestoreClient::new(project_id),
                         user_id: Some(user_id),
                         id_token: Some(id_token),
@@ -101,7 +107,7 @@ impl SyncService {
             eprintln!("Migration warning: {}", e);
         }
 
-        state.local_store = user_store;
+        state.local_store = Arc::new(user_store);
         state.user_id = Some(user_id.to_string());
         state.id_token = Some(id_token.to_string());
         state.sync_enabled = true;
@@ -111,7 +117,7 @@ impl SyncService {
     /// This switches back to anonymous local storage
     pub fn clear_auth(&self) {
         let mut state = self.state.write().unwrap();
-        state.local_store = LocalDataStore::new();
+        state.local_store = Arc::new(LocalDataStore::new());
         state.user_id = None;
         state.id_token = None;
         state.sync_enabled = false;
@@ -136,103 +142,96 @@ impl SyncService {
         ))
     }
 
+    /// Run `f` on the current local store on a blocking thread: its
+    /// operations wait up to a couple of seconds for the library lock, which
+    /// mustn't stall the async workers
+    async fn with_local_store<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&LocalDataStore) -> AppResult<T> + Send + 'static,
+    ) -> AppResult<T> {
+        let local_store = Arc::clone(&self.state.read().unwrap().local_store);
+        tauri::async_runtime::spawn_blocking(move || f(&local_store))
+            .await
+            .map_err(|e| AppError::new(ErrorCode::Internal, format!("Task join error: {}", e)))?
+    }
+
     /// Sync local data to Firestore (upload all)
     /// This is an explicit sync operation, useful for initial upload
     ///
     /// SAFETY: Refuses to upload empty data to prevent accidental data loss
-    pub async fn sync_to_firestore(&self) -> Result<(), String> {
-        let (user_id, id_token, index, firestore) = {
+    pub async fn sync_to_firestore(&self) -> AppResult<()> {
+        let (user_id, id_token, firestore) = {
             let state = self.state.read().unwrap();
 
             let user_id = state.user_id.clone()
-                .ok_or("Not authenticated")?;
+                .ok_or_else(not_authenticated)?;
             let id_token = state.id_token.clone()
-                .ok_or("No auth token")?;
-
-            let index = state.local_store.load_index_sync()?;
-            let firestore = state.firestore.clone();
+                .ok_or_else(missing_token)?;
 
-            (user_id, id_token, index, firestore)
+            (user_id, id_token, state.firestore.clone())
         };
 
+        let index = self.with_local_store(|store| store.load_index_sync()).await?;
+
         // SAFETY: Never upload empty data - this could wipe out cloud data
         if index.prompts.is_empty() {
             eprintln!("[SYNC SAFETY] Refusing to upload empty local data to cloud. This prevents accidental data loss.");
-            return Err("Cannot sync empty local data to cloud. This is a safety measure to prevent data loss.".to_string());
+            return Err(AppError::new(
+                ErrorCode::SyncRejected,
+                "Cannot sync empty local data to cloud. This is a safety measure to prevent data loss.",
+            ));
         }
 
-        // Load all prompts with content (outside the lock)
-        let prompts = self.load_all_prompts_sync(&index)?;
+        // Load all prompts with content
+        let ids: Vec<String> = index.prompts.iter().map(|p| p.id.clone()).collect();
+        let prompts = self
+            .with_local_store(move |store| {
+                ids.iter().map(|id| store.get_prompt_sync(id)).collect::<AppResult<Vec<_>>>()
+            })
+            .await?;
 
         // Upload to Firestore
         firestore.upload_all(&user_id, &id_token, &index, &prompts).await
     }
 
-    /// Load all prompts synchronously
-    fn load_all_prompts_sync(&self, index: &PromptIndex) -> Result<Vec<Prompt>, String> {
-        let state = self.state.read().unwrap();
-        let mut prompts = Vec::new();
-        for meta in &index.prompts {
-            let prompt = state.local_store.get_prompt_sync(&meta.id)?;
-            prompts.push(prompt);
-        }
-        Ok(prompts)
-    }
-
     /// Sync from Firestore to local (download all)
     /// This replaces local data with Firestore data
     ///
     /// SAFETY: Refuses to replace local data with empty cloud data if local has prompts
-    pub async fn sync_from_firestore(&self) -> Result<(), String> {
-        let (user_id, id_token, firestore, local_prompt_count) = {
+    pub async fn sync_from_firestore(&self) -> AppResult<()> {
+        let (user_id, id_token, firestore) = {
             let state = self.state.read().unwrap();
 
             let user_id = state.user_id.clone()
-                .ok_or("Not authenticated")?;
+                .ok_or_else(not_authenticated)?;
             let id_token = state.id_token.clone()
-                .ok_or("No auth token")?;
-            let firestore = state.firestore.clone();
+                .ok_or_else(missing_token)?;
 
-            // Check how many prompts we have locally (for safety check)
-            let local_index = state.local_store.load_index_sync().ok();
-            let local_prompt_count = local_index.map(|i| i.prompts.len()).unwrap_or(0);
-
-            (user_id, id_token, firestore, local_prompt_count)
+            (user_id, id_token, state.firestore.clone())
         };
 
         // Download from Firestore (outside the lock)
         let (index, prompts) = firestore.download_all(&user_id, &id_token).await?;
 
-        // SAFETY: Don't replace existing local data with empty cloud data
-        // This prevents accidental data loss when cloud is empty or auth fails silently
-        if index.prompts.is_empty() && local_prompt_count > 0 {
-            eprintln!(
-                "[SYNC SAFETY] Cloud returned 0 prompts but local has {}. Skipping sync to prevent data loss.",
-                local_prompt_count
-            );
-            return Ok(()); // Silently succeed - don't wipe local data
-        }
-
-        // Save to local (re-acquire lock)
-        {
-            let state = self.state.read().unwrap();
-            state.local_store.save_index_sync(&index)?;
-
-            // Write prompt content files
-            for prompt in prompts {
-                state.local_store.write_prompt_content_sync(
-                    &prompt.metadata.folder,
-                    &prompt.metadata.filename,
-                    &prompt.content,
-                )?;
+        self.with_local_store(move |store| {
+            // SAFETY: Don't replace existing local data with empty cloud data
+            // This prevents accidental data loss when cloud is empty or auth fails silently
+            let local_prompt_count = store.load_index_sync().map(|i| i.prompts.len()).unwrap_or(0);
+            if index.prompts.is_empty() && local_prompt_count > 0 {
+                eprintln!(
+                    "[SYNC SAFETY] Cloud returned 0 prompts but local has {}. Skipping sync to prevent data loss.",
+                    local_prompt_count
+                );
+                return Ok(()); // Silently succeed - don't wipe local data
             }
-        }
 
-        Ok(())
+            store.save_index_sync(&index, &prompts)
+        })
+        .await
     }
 
     /// Sync a single prompt to Firestore (background operation)
-    async fn sync_prompt_to_firestore(&self, prompt: &Prompt) -> Result<(), String> {
+    async fn sync_prompt_to_firestore(&self, prompt: &Prompt) -> AppResult<()> {
         let ctx = match self.get_sync_context() {
             Some(ctx) => ctx,
             None => return Ok(()), // Sync disabled
@@ -242,7 +241,7 @@ impl SyncService {
     }
 
     /// Delete a prompt from Firestore (background operation)
-    async fn delete_prompt_from_firestore(&self, prompt_id: &str) -> Result<(), String> {
+    async fn delete_prompt_from_firestore(&self, prompt_id: &str) -> AppResult<()> {
         let ctx = match self.get_sync_context() {
             Some(ctx) => ctx,
             None => return Ok(()), // Sync disabled
@@ -252,7 +251,7 @@ impl SyncService {
     }
 
     /// Sync folder metadata to Firestore (background operation)
-    async fn sync_meta_to_firestore(&self, index: &PromptIndex) -> Result<(), String> {
+    async fn sync_meta_to_firestore(&self, index: &PromptIndex) -> AppResult<()> {
         let ctx = match self.get_sync_context() {
             Some(ctx) => ctx,
             None => return Ok(()), // Sync disabled
@@ -262,73 +261,37 @@ impl SyncService {
         let meta = UserMeta {
             folders: index.folders.clone(),
             folder_meta: index.folder_meta.clone(),
+            smart_folders: index.smart_folders.clone(),
         };
 
         firestore.save_meta(&user_id, &id_token, &meta).await
     }
 }
 
-// Add sync version of get_prompt to LocalDataStore
-impl LocalDataStore {
-    /// Get a prompt by ID synchronously
-    pub fn get_prompt_sync(&self, id: &str) -> Result<Prompt, String> {
-        let index = self.load_index_sync()?;
-
-        let metadata = index
-            .prompts
-            .iter()
-            .find(|p| p.id == id)
-            .ok_or_else(|| format!("Prompt not found: {}", id))?
-            .clone();
-
-        let content = self.read_prompt_content_sync(&metadata.folder, &metadata.filename)?;
-
-        Ok(Prompt { metadata, content })
-    }
-
-    /// Read prompt content synchronously
-    fn read_prompt_content_sync(&self, folder: &str, filename: &str) -> Result<String, String> {
-        let file_path = self.data_dir().join("prompts").join(folder).join(filename);
-        if !file_path.exists() {
-            return Ok(String::new());
-        }
-        std::fs::read_to_string(&file_path)
-            .map_err(|e| format!("Failed to read prompt file: {}", e))
-    }
-}
-
 #[async_trait]
 impl DataStore for SyncService {
-    async fn get_index(&self) -> Result<PromptIndex, String> {
-        // Use sync method to avoid lock across await
-        let state = self.state.read().unwrap();
-        state.local_store.load_index_sync()
+    async fn get_index(&self) -> AppResult<PromptIndex> {
+        self.with_local_store(|store| store.load_index_sync()).await
     }
 
-    async fn save_index(&self, index: &PromptIndex) -> Result<(), String> {
-        // Save locally (sync)
-        {
-            let state = self.state.read().unwrap();
-            state.local_store.save_index_sync(index)?;
-        }
-
-        // Sync meta to Firestore (async, outside lock)
-        let _ = self.sync_meta_to_firestore(index).await;
-
-        Ok(())
+    async fn get_index_page(
+        &self,
+        sort: PromptSort,
+        direction: SortDirection,
+        page: Page,
+    ) -> AppResult<IndexPage> {
+        self.with_local_store(move |store| store.index_page_sync(sort, direction, page)).await
     }
 
-    async fn get_prompt(&self, id: &str) -> Result<Prompt, String> {
-        let state = self.state.read().unwrap();
-        state.local_store.get_prompt_sync(id)
+    async fn get_prompt(&self, id: &str) -> AppResult<Prompt> {
+        let id = id.to_string();
+        self.with_local_store(move |store| store.get_prompt_sync(&id)).await
     }
 
-    async fn save_prompt(&self, prompt: &Prompt) -> Result<PromptMetadata, String> {
-        // Save locally (sync)
-        let metadata = {
-            let state = self.state.read().unwrap();
-            state.local_store.save_prompt_sync(prompt)?
-        };
+    async fn save_prompt(&self, prompt: &Prompt) -> AppResult<PromptMetadata> {
+        // Save locally
+        let local_prompt = prompt.clone();
+        let metadata = self.with_local_store(move |store| store.save_prompt_sync(&local_prompt)).await?;
 
         // Create full prompt with updated metadata for sync
         let full_prompt = Prompt {
@@ -336,31 +299,27 @@ impl DataStore for SyncService {
             content: prompt.content.clone(),
         };
 
-        // Sync to Firestore (async, outside lock)
+        // Sync to Firestore
         let _ = self.sync_prompt_to_firestore(&full_prompt).await;
 
         Ok(metadata)
     }
 
-    async fn delete_prompt(&self, id: &str) -> Result<(), String> {
-        // Delete locally (sync)
-        {
-            let state = self.state.read().unwrap();
-            state.local_store.delete_prompt_sync(id)?;
-        }
+    async fn delete_prompt(&self, id: &str) -> AppResult<()> {
+        // Delete locally
+        let local_id = id.to_string();
+        self.with_local_store(move |store| store.delete_prompt_sync(&local_id)).await?;
 
-        // Delete from Firestore (async, outside lock)
+        // Delete from Firestore
         let _ = self.delete_prompt_from_firestore(id).await;
 
         Ok(())
     }
 
-    async fn add_folder(&self, name: &str) -> Result<(), String> {
-        // Add locally (sync)
-        {
-            let state = self.state.read().unwrap();
-            state.local_store.add_folder_sync(name)?;
-        }
+    async fn add_folder(&self, name: &str) -> AppResult<()> {
+        // Add locally
+        let name = name.to_string();
+        self.with_local_store(move |store| store.add_folder_sync(&name)).await?;
 
         // Sync meta to Firestore
         let index = self.get_index().await?;
@@ -369,12 +328,10 @@ impl DataStore for SyncService {
         Ok(())
     }
 
-    async fn rename_folder(&self, old_name: &str, new_name: &str) -> Result<(), String> {
-        // Rename locally (sync)
-        {
-            let state = self.state.read().unwrap();
-            state.local_store.rename_folder_sync(old_name, new_name)?;
-        }
+    async fn rename_folder(&self, old_name: &str, new_name: &str) -> AppResult<()> {
+        // Rename locally
+        let (old_name, new_name) = (old_name.to_string(), new_name.to_string());
+        self.with_local_store(move |store| store.rename_folder_sync(&old_name, &new_name)).await?;
 
         // Sync meta to Firestore
         let index = self.get_index().await?;
@@ -383,12 +340,10 @@ impl DataStore for SyncService {
         Ok(())
     }
 
-    async fn delete_folder(&self, name: &str) -> Result<(), String> {
-        // Delete locally (sync)
-        {
-            let state = self.state.read().unwrap();
-            state.local_store.delete_folder_sync(name)?;
-        }
+    async fn delete_folder(&self, name: &str) -> AppResult<()> {
+        // Delete locally
+        let name = name.to_string();
+        self.with_local_store(move |store| store.delete_folder_sync(&name)).await?;
 
         // Sync meta to Firestore
         let index = self.get_index().await?;
@@ -397,12 +352,44 @@ impl DataStore for SyncService {
         Ok(())
     }
 
-    async fn record_usage(&self, id: &str) -> Result<(), String> {
-        // Record locally (sync)
-        {
-            let state = self.state.read().unwrap();
-            state.local_store.record_usage_sync(id)?;
-        }
+    async fn save_smart_folder(&self, folder: &SmartFolder, previous: Option<&str>) -> AppResult<()> {
+        // Save locally
+        let (folder, previous) = (folder.clone(), previous.map(str::to_string));
+        self.with_local_store(move |store| store.save_smart_folder_sync(&folder, previous.as_deref()))
+            .await?;
+
+        // Sync meta to Firestore
+        let index = self.get_index().await?;
+        let _ = self.sync_meta_to_firestore(&index).await;
+
+        Ok(())
+    }
+
+    async fn delete_smart_folder(&self, name: &str) -> AppResult<()> {
+        // Delete locally
+        let name = name.to_string();
+        self.with_local_store(move |store| store.delete_smart_folder_sync(&name)).await?;
+
+        // Sync meta to Firestore
+        let index = self.get_index().await?;
+        let _ = self.sync_meta_to_firestore(&index).await;
+
+        Ok(())
+    }
+
+    async fn record_usage(
+        &self,
+        id: &str,
+        action: UsageAction,
+        app: Option<&str>,
+        query: Option<&str>,
+    ) -> AppResult<()> {
+        // Record locally
+        let (local_id, app, query) = (id.to_string(), app.map(str::to_string), query.map(str::to_string));
+        self.with_local_store(move |store| {
+            store.record_usage_sync(&local_id, action, app.as_deref(), query.as_deref())
+        })
+        .await?;
 
         // Sync the updated prompt to Firestore
         if let Ok(prompt) = self.get_prompt(id).await {
@@ -412,10 +399,82 @@ impl DataStore for SyncService {
         Ok(())
     }
 
-    async fn search_prompts(&self, query: &str) -> Result<Vec<SearchResult>, String> {
-        let state = self.state.read().unwrap();
-        state.local_store.search_prompts_sync(query)
+    async fn clear_selection_history(&self) -> AppResult<()> {
+        // Selection history is local-only, like the usage log
+        self.with_local_store(|store| store.clear_selection_history_sync()).await
     }
+
+    async fn get_usage_history(&self) -> AppResult<Vec<UsageEvent>> {
+        // The usage log is local-only; Firestore keeps just the counts
+        self.with_local_store(|store| store.usage_history_sync()).await
+    }
+
+    async fn search_prompts(
+        &self,
+        query: &str,
+        context: &SearchContext,
+        page: Page,
+    ) -> AppResult<SearchPage> {
+        let (query, context) = (query.to_string(), context.clone());
+        self.with_local_store(move |store| store.search_prompts_sync(&query, &context, page)).await
+    }
+
+    async fn record_query(&self, query: &str) -> AppResult<()> {
+        // Recent queries are local-only, like the usage log
+        let query = query.to_string();
+        self.with_local_store(move |store| store.record_query_sync(&query)).await
+    }
+
+    async fn query_suggestions(&self, input: &str, limit: usize) -> AppResult<Vec<RecentQuery>> {
+        let input = input.to_string();
+        self.with_local_store(move |store| Ok(store.query_suggestions_sync(&input, limit))).await
+    }
+
+    async fn clear_query_history(&self) -> AppResult<()> {
+        self.with_local_store(|store| store.clear_query_history_sync()).await
+    }
+
+    async fn record_variable_values(
+        &self,
+        prompt_id: &str,
+        values: &HashMap<String, String>,
+        limit: usize,
+    ) -> AppResult<()> {
+        // Entered values stay on this machine, like recent queries
+        let (prompt_id, values) = (prompt_id.to_string(), values.clone());
+        self.with_local_store(move |store| store.record_variable_values_sync(&prompt_id, &values, limit))
+            .await
+    }
+
+    async fn recent_variable_values(
+        &self,
+        prompt_id: &str,
+        names: &[&str],
+        shared: bool,
+        limit: usize,
+    ) -> AppResult<HashMap<String, Vec<String>>> {
+        let prompt_id = prompt_id.to_string();
+        let names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+        self.with_local_store(move |store| {
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            Ok(store.recent_variable_values_sync(&prompt_id, &names, shared, limit))
+        })
+        .await
+    }
+
+    async fn clear_variable_history(&self) -> AppResult<()> {
+        self.with_local_store(|store| store.clear_variable_history_sync()).await
+    }
+}
+
+/// Error for sync operations attempted while signed out
+fn not_authenticated() -> AppError {
+    AppError::new(ErrorCode::NotAuthenticated, "Not authenticated")
+}
+
+/// Error for sync operations attempted without an ID token
+fn missing_token() -> AppError {
+    AppError::new(ErrorCode::NotAuthenticated, "No auth token")
 }
 
 /// Type alias for thread-safe SyncService
@@ -0,0 +1,635 @@
+//! Prompt templates: `{{variable}}` placeholders filled in when pasting.
+//!
+//! `{{name}}` is replaced by the value given for `name`, and `{{name|default}}`
+//! falls back to `default` when no value is given. A default applies to every
+//! placeholder of that variable; the first one declared wins. Spaces around
+//! the name and default are ignored, and `{{name|}}` makes a variable
+//! optional (it renders empty).
+//!
+//! A variable can be declared with a kind after a colon, which the launcher
+//! uses to build its form and `render` checks submitted values against:
+//!
+//! - `{{tone:formal|casual|terse}}`: one of a fixed set of choices
+//! - `{{notes:multiline}}`: a block of text
+//! - `{{count:number}}`, `{{count:1..10}}`, `{{count:0..}}`: a number,
+//!   optionally in a range
+//! - `{{name:text}}`: plain text, the kind of an undeclared variable (and of
+//!   one declared with a kind that isn't any of these)
+//!
+//! All but choices can take a default after the kind (`{{count:1..10|3}}`),
+//! which must be a value of that kind for the prompt to be saved (see
+//! `check_defaults`).
+//! A `?` after the name makes a variable optional (`{{notes?:multiline}}`,
+//! `{{cc?}}`). The first declaration of a variable wins; its other
+//! placeholders can be plain `{{name}}`.
+//!
+//! Reserved names (`{{clipboard}}`, `{{date:%Y-%m-%d}}`, ...) are built-in
+//! variables, filled in from the environment at render time; see `builtins`.
+//! A value given for one still takes precedence.
+//!
+//! `{{> folder/prompt-name}}` or `{{> id}}` includes another prompt; see
+//! `include`. Includes are expanded before variables are filled in.
+//!
+//! Anything that isn't a placeholder is left as written: a `{{` without a
+//! closing `}}`, or one whose name is empty, spans lines or contains braces.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
+
+use super::builtins::{self, Environment};
+use super::fuzzy::MatchRange;
+use super::include;
+use super::settings::VariableSettings;
+use super::store::DataStore;
+use crate::error::{AppError, AppResult};
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+const INCLUDE: char = '>';
+
+/// A variable of a template, as returned by `extract_variables`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Variable {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Filled in at render time rather than by the user
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub builtin: bool,
+    /// What kind of value it takes, for the form to ask for
+    #[serde(flatten)]
+    pub kind: VariableKind,
+    /// Whether a value must be given: not built-in, optional or defaulted
+    pub required: bool,
+    /// Each placeholder of the variable, in UTF-16 code units of the content
+    pub ranges: Vec<MatchRange>,
+    /// Values recently entered for it, most recent first, to prefill and
+    /// suggest (see `add_recent_values`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent: Vec<String>,
+}
+
+/// The kind of value a variable takes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum VariableKind {
+    #[default]
+    Text,
+    Multiline,
+    Number {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<f64>,
+    },
+    Choice { options: Vec<String> },
+}
+
+impl VariableKind {
+    /// The kind declared by `spec` (what follows the colon) and the default
+    /// after it, if any
+    fn parse(spec: &str) -> (Self, Option<&str>) {
+        let (head, default) = match spec.split_once('|') {
+            Some((head, default)) => (head.trim(), Some(default.trim())),
+            None => (spec.trim(), None),
+        };
+        let kind = match head {
+            "text" => Self::Text,
+            "multiline" => Self::Multiline,
+            "number" => Self::Number { min: None, max: None },
+            _ => match number_range(head) {
+                Some((min, max)) => Self::Number { min, max },
+                None if default.is_some() => {
+                    let options = spec.split('|').map(str::trim).filter(|o| !o.is_empty());
+                    return (Self::Choice { options: options.map(str::to_string).collect() }, None);
+                }
+                // Not a kind, and a single word isn't a choice
+                None => return (Self::Text, None),
+            },
+        };
+        (kind, default)
+    }
+
+    /// Whether an empty value is one (rather than no value at all)
+    fn takes_empty(&self) -> bool {
+        matches!(self, Self::Text | Self::Multiline)
+    }
+
+    /// Why `value` isn't one of this kind, if it isn't
+    fn check(&self, value: &str) -> Option<String> {
+        match self {
+            Self::Text | Self::Multiline => None,
+            Self::Number { min, max } => match value.trim().parse::<f64>().ok().filter(|n| n.is_finite()) {
+                None => Some("must be a number".to_string()),
+                Some(n) => match (min, max) {
+                    (Some(min), _) if n < *min => Some(format!("must be at least {}", min)),
+                    (_, Some(max)) if n > *max => Some(format!("must be at most {}", max)),
+                    _ => None,
+                },
+            },
+            Self::Choice { options } => {
+                (!options.iter().any(|o| o == value)).then(|| format!("must be one of {}", options.join(", ")))
+            }
+        }
+    }
+}
+
+/// `min..max`, `min..` or `..max`
+fn number_range(spec: &str) -> Option<(Option<f64>, Option<f64>)> {
+    let (min, max) = spec.split_once("..")?;
+    let bound = |b: &str| match b.trim() {
+        "" => Ok(None),
+        b => b.parse::<f64>().map(Some),
+    };
+    Some((bound(min).ok()?, bound(max).ok()?))
+}
+
+/// A piece of a parsed template
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(Placeholder<'a>),
+    /// `{{> target}}`, replaced by another prompt's content
+    Include { target: &'a str, span: Range<usize> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Placeholder<'a> {
+    name: &'a str,
+    default: Option<&'a str>,
+    /// The kind it declares, if it declares one
+    kind: Option<VariableKind>,
+    /// Byte range of the whole `{{...}}`
+    pub span: Range<usize>,
+}
+
+/// The variables of a template, in order of first appearance
+pub fn extract(content: &str) -> Vec<Variable> {
+    let mut variables: Vec<Variable> = Vec::new();
+    let mut declared: Vec<&str> = Vec::new();
+    // Byte and UTF-16 offsets of the last placeholder seen, to count from
+    let mut byte = 0;
+    let mut utf16 = 0;
+    let mut to_utf16 = |offset: usize| {
+        utf16 += content[byte..offset].encode_utf16().count();
+        byte = offset;
+        utf16
+    };
+
+    for segment in parse(content) {
+        let Segment::Placeholder(placeholder) = segment else { continue };
+        let start = to_utf16(placeholder.span.start);
+        let range = MatchRange { start, end: to_utf16(placeholder.span.end) };
+
+        let variable = match variables.iter_mut().position(|v| v.name == placeholder.name) {
+            Some(i) => {
+                let variable = &mut variables[i];
+                variable.ranges.push(range);
+                if variable.default.is_none() {
+                    variable.default = placeholder.default.map(str::to_string);
+                }
+                variable
+            }
+            None => {
+                variables.push(Variable {
+                    name: placeholder.name.to_string(),
+                    default: placeholder.default.map(str::to_string),
+                    builtin: builtins::find(placeholder.name).is_some(),
+                    kind: VariableKind::Text,
+                    required: false,
+                    ranges: vec![range],
+                    recent: Vec::new(),
+                });
+                variables.last_mut().expect("just pushed")
+            }
+        };
+        if let Some(kind) = placeholder.kind {
+            if !declared.contains(&placeholder.name) {
+                declared.push(placeholder.name);
+                variable.kind = kind;
+            }
+        }
+    }
+
+    for variable in &mut variables {
+        variable.required = !variable.builtin && variable.default.is_none();
+    }
+    variables
+}
+
+/// Fill in a template's placeholders from `values`, then built-in variables
+/// from `env`, falling back to each variable's default. Fails, naming them,
+/// if a value isn't one of its variable's kind, or if any variable has none
+/// of these.
+pub fn render(content: &str, values: &HashMap<String, String>, env: &dyn Environment) -> AppResult<String> {
+    let segments = parse(content);
+    let mut defaults: HashMap<&str, &str> = HashMap::new();
+    let mut kinds: HashMap<&str, &VariableKind> = HashMap::new();
+    for segment in &segments {
+        let Segment::Placeholder(placeholder) = segment else { continue };
+        if let Some(default) = placeholder.default {
+            defaults.entry(placeholder.name).or_insert(default);
+        }
+        if let Some(kind) = &placeholder.kind {
+            kinds.entry(placeholder.name).or_insert(kind);
+        }
+    }
+
+    // An empty number or choice counts as not given
+    let values: HashMap<&str, &String> = values
+        .iter()
+        .filter(|(name, value)| !value.is_empty() || kinds.get(name.as_str()).is_none_or(|k| k.takes_empty()))
+        .map(|(name, value)| (name.as_str(), value))
+        .collect();
+    let mut invalid: Vec<String> = kinds
+        .iter()
+        .filter_map(|(name, kind)| Some(format!("{} {}", name, kind.check(values.get(name)?)?)))
+        .collect();
+    if !invalid.is_empty() {
+        invalid.sort();
+        return Err(AppError::invalid_input("Invalid values for template variables")
+            .with_context("variables", invalid.join("; ")));
+    }
+
+    // Built-ins are resolved once, so repeated uses agree (e.g. `{{uuid}}`)
+    let mut resolved: HashMap<&str, String> = HashMap::new();
+    for segment in &segments {
+        let Segment::Placeholder(Placeholder { name, .. }) = segment else { continue };
+        if values.contains_key(name) || resolved.contains_key(name) {
+            continue;
+        }
+        if let Some((builtin, arg)) = builtins::find(name) {
+            let value = builtin
+                .resolve(env, arg)
+                .or_else(|| defaults.get(name).map(|d| d.to_string()))
+                .unwrap_or_else(|| builtin.fallback.to_string());
+            resolved.insert(name, value);
+        }
+    }
+
+    let mut rendered = String::with_capacity(content.len());
+    let mut missing: Vec<&str> = Vec::new();
+    for segment in &segments {
+        match segment {
+            Segment::Text(text) => rendered.push_str(text),
+            // Left as written: includes are expanded before rendering
+            Segment::Include { span, .. } => rendered.push_str(&content[span.clone()]),
+            Segment::Placeholder(placeholder) => {
+                let value = values
+                    .get(placeholder.name)
+                    .copied()
+                    .or_else(|| resolved.get(placeholder.name))
+                    .map(String::as_str)
+                    .or_else(|| defaults.get(placeholder.name).copied());
+                match value {
+                    Some(value) => rendered.push_str(value),
+                    None if !missing.contains(&placeholder.name) => missing.push(placeholder.name),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(AppError::invalid_input("Missing values for template variables")
+            .with_context("variables", missing.join(", ")));
+    }
+    Ok(rendered)
+}
+
+/// Check that each default declared in a template is a value of its
+/// variable's kind, naming the variables whose defaults aren't
+pub fn check_defaults(content: &str) -> AppResult<()> {
+    let invalid: Vec<String> = extract(content)
+        .iter()
+        .filter(|v| !v.builtin)
+        .filter_map(|v| {
+            // An empty default makes a variable optional whatever its kind
+            let default = v.default.as_deref().filter(|d| !d.is_empty())?;
+            Some(format!("{} {}", v.name, v.kind.check(default)?))
+        })
+        .collect();
+    if invalid.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::invalid_input("Invalid defaults for template variables")
+        .with_context("variables", invalid.join("; ")))
+}
+
+/// The variables of the prompt with the given id, its includes' included.
+/// Ranges are within the prompt's own content, so a variable that only
+/// appears in an included prompt has none.
+pub async fn extract_prompt<S: DataStore + ?Sized>(store: &S, id: &str) -> AppResult<Vec<Variable>> {
+    let prompt = store.get_prompt(id).await?;
+    let mut variables = extract(&prompt.content);
+    for mut variable in extract(&include::expand(store, &prompt).await?) {
+        match variables.iter_mut().find(|v| v.name == variable.name) {
+            Some(own) => {
+                if own.default.is_none() {
+                    own.default = variable.default;
+                    own.required = variable.required;
+                }
+                if own.kind == VariableKind::Text {
+                    own.kind = variable.kind;
+                }
+            }
+            None => {
+                variable.ranges.clear();
+                variables.push(variable);
+            }
+        }
+    }
+    Ok(variables)
+}
+
+/// Render the template of the prompt with the given id, includes expanded
+pub async fn render_prompt<S: DataStore + ?Sized>(
+    store: &S,
+    id: &str,
+    values: &HashMap<String, String>,
+    env: &dyn Environment,
+) -> AppResult<String> {
+    let prompt = store.get_prompt(id).await?;
+    let content = include::expand(store, &prompt).await?;
+    render(&content, values, env).map_err(|e| e.with_context("promptId", id))
+}
+
+/// Fill in the values recently entered for a prompt's variables, leaving out
+/// built-in and sensitive ones. Nothing is filled in while remembering values
+/// is turned off.
+pub async fn add_recent_values<S: DataStore + ?Sized>(
+    store: &S,
+    prompt_id: &str,
+    variables: &mut [Variable],
+    settings: &VariableSettings,
+) -> AppResult<()> {
+    if !settings.remember_values {
+        return Ok(());
+    }
+    let names: Vec<&str> = variables
+        .iter()
+        .filter(|v| !v.builtin && !settings.is_sensitive(&v.name))
+        .map(|v| v.name.as_str())
+        .collect();
+    let mut recent = store
+        .recent_variable_values(prompt_id, &names, settings.share_across_prompts, settings.remembered_values)
+        .await?;
+    for variable in variables {
+        if let Some(values) = recent.remove(&variable.name) {
+            variable.recent = values;
+        }
+    }
+    Ok(())
+}
+
+/// Remember the values a prompt was pasted with, leaving out sensitive
+/// variables
+pub async fn remember_values<S: DataStore + ?Sized>(
+    store: &S,
+    prompt_id: &str,
+    values: &HashMap<String, String>,
+    settings: &VariableSettings,
+) -> AppResult<()> {
+    if !settings.remember_values || settings.remembered_values == 0 {
+        return Ok(());
+    }
+    let values: HashMap<String, String> = values
+        .iter()
+        .filter(|(name, _)| !settings.is_sensitive(name))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    store.record_variable_values(prompt_id, &values, settings.remembered_values).await
+}
+
+/// The targets of a template's `{{> includes}}`, in order
+pub(crate) fn includes(content: &str) -> Vec<&str> {
+    parse(content)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Include { target, .. } => Some(target),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Split a template into text, placeholders and includes
+pub(crate) fn parse(content: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut text_start = 0;
+    let mut pos = 0;
+
+    while let Some(found) = content[pos..].find(OPEN) {
+        let mut open = pos + found;
+        let Some(close) = content[open + OPEN.len()..].find(CLOSE).map(|i| open + OPEN.len() + i) else {
+            break;
+        };
+        // In `{{{{name}}` the placeholder is the innermost `{{name}}`
+        if let Some(inner) = content[open + OPEN.len()..close].rfind(OPEN) {
+            open += OPEN.len() + inner;
+        }
+
+        let inner = &content[open + OPEN.len()..close];
+        let end = close + CLOSE.len();
+        let segment = match inner.trim_start().strip_prefix(INCLUDE) {
+            Some(target) => include_target(target).map(|target| Segment::Include { target, span: open..end }),
+            None => placeholder(inner).map(|(name, default, kind)| {
+                Segment::Placeholder(Placeholder { name, default, kind, span: open..end })
+            }),
+        };
+
+        match segment {
+            Some(segment) => {
+                if text_start < open {
+                    segments.push(Segment::Text(&content[text_start..open]));
+                }
+                segments.push(segment);
+                text_start = end;
+                pos = end;
+            }
+            None => pos = open + 1,
+        }
+    }
+
+    if text_start < content.len() {
+        segments.push(Segment::Text(&content[text_start..]));
+    }
+    segments
+}
+
+/// The name, default and declared kind between `{{` and `}}`, if it's a
+/// valid placeholder
+fn placeholder(inner: &str) -> Option<(&str, Option<&str>, Option<VariableKind>)> {
+    if inner.contains(['{', '}', '\n']) {
+        return None;
+    }
+    let (head, default) = match inner.split_once('|') {
+        Some((head, default)) => (head.trim(), Some(default.trim())),
+        None => (inner.trim(), None),
+    };
+    // Built-ins take their argument after the colon (`{{date:%B}}`)
+    let (name, default, kind) = match head.split_once(':') {
+        Some(_) if builtins::find(head).is_some() => (head, default, None),
+        Some((name, _)) => {
+            let (_, spec) = inner.split_once(':')?;
+            let (kind, default) = VariableKind::parse(spec);
+            (name.trim(), default, Some(kind))
+        }
+        None => (head, default, None),
+    };
+    let (name, default) = match name.strip_suffix('?') {
+        Some(name) => (name.trim_end(), default.or(Some(""))),
+        None => (name, default),
+    };
+    (!name.is_empty()).then_some((name, default, kind))
+}
+
+/// The prompt an include names, if it's a valid one
+fn include_target(target: &str) -> Option<&str> {
+    let target = target.trim();
+    (!target.is_empty() && !target.contains(['{', '}', '\n'])).then_some(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::builtins::TestEnvironment;
+
+    fn render(content: &str, values: &HashMap<String, String>) -> AppResult<String> {
+        super::render(content, values, &TestEnvironment::default())
+    }
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_extract_names_defaults_and_ranges() {
+        let content = "Résumé for {{ role }} at {{company|Acme}}.\nDear {{role|hiring manager}}";
+        let variables = extract(content);
+
+        assert_eq!(variables.len(), 2);
+        assert_eq!(variables[0].name, "role");
+        // The default is shared by every placeholder of the variable
+        assert_eq!(variables[0].default.as_deref(), Some("hiring manager"));
+        assert_eq!(variables[1].default.as_deref(), Some("Acme"));
+
+        let units: Vec<u16> = content.encode_utf16().collect();
+        let text = |r: &MatchRange| String::from_utf16(&units[r.start..r.end]).unwrap();
+        assert_eq!(variables[0].ranges.iter().map(text).collect::<Vec<_>>(), ["{{ role }}", "{{role|hiring manager}}"]);
+        assert_eq!(text(&variables[1].ranges[0]), "{{company|Acme}}");
+    }
+
+    #[test]
+    fn test_render_values_then_defaults() {
+        let content = "Write {{tone|formal}} notes on {{topic}}{{suffix|}}. {{topic}}!";
+        assert_eq!(
+            render(content, &values(&[("topic", "Rust")])).unwrap(),
+            "Write formal notes on Rust. Rust!"
+        );
+        assert_eq!(
+            render(content, &values(&[("topic", "Go"), ("tone", "casual"), ("suffix", "?")])).unwrap(),
+            "Write casual notes on Go?. Go!"
+        );
+    }
+
+    #[test]
+    fn test_render_lists_missing_variables() {
+        let err = render("{{a}} {{b|x}} {{c}} {{a}}", &HashMap::new()).unwrap_err();
+        assert_eq!(err.context.get("variables").map(String::as_str), Some("a, c"));
+    }
+
+    #[test]
+    fn test_render_builtins() {
+        let content = "{{date}} {{time:%H.%M}} [{{clipboard|empty}}] [{{app}}] {{uuid}} {{uuid}}";
+        let rendered = render(content, &HashMap::new()).unwrap();
+        let (fixed, ids) = rendered.split_at("2024-03-09 14.05 [empty] [] ".len());
+        assert_eq!(fixed, "2024-03-09 14.05 [empty] [] ");
+        let (first, second) = ids.split_once(' ').unwrap();
+        assert_eq!(first, second);
+
+        // Given values win over the environment
+        let env = TestEnvironment { clipboard: Some("copied".to_string()), ..Default::default() };
+        assert_eq!(super::render("{{clipboard}} {{library}}", &HashMap::new(), &env).unwrap(), "copied Local");
+        assert_eq!(super::render("{{clipboard}}", &values(&[("clipboard", "given")]), &env).unwrap(), "given");
+
+        let variables = extract("{{date:%Y}} {{topic}}");
+        assert_eq!(variables.iter().map(|v| v.builtin).collect::<Vec<_>>(), [true, false]);
+    }
+
+    #[test]
+    fn test_non_placeholders_are_left_as_text() {
+        let content = "{{}} {{ }} {{a\nb}} fn() {{ x }} {{{y}}} {{unclosed";
+        assert_eq!(
+            extract(content).iter().map(|v| v.name.as_str()).collect::<Vec<_>>(),
+            ["x", "y"]
+        );
+        assert_eq!(
+            render(content, &values(&[("x", "1"), ("y", "2")])).unwrap(),
+            "{{}} {{ }} {{a\nb}} fn() 1 {2} {{unclosed"
+        );
+    }
+
+    #[test]
+    fn test_includes_are_not_variables() {
+        let content = "{{> shared/Style }} {{>abc-123}} {{>}} {{x}}";
+        assert_eq!(includes(content), ["shared/Style", "abc-123"]);
+        assert_eq!(extract(content).iter().map(|v| v.name.as_str()).collect::<Vec<_>>(), ["x"]);
+        // Includes are expanded before rendering, so render leaves them be
+        assert_eq!(render(content, &values(&[("x", "1")])).unwrap(), "{{> shared/Style }} {{>abc-123}} {{>}} 1");
+    }
+
+    #[test]
+    fn test_declared_kinds() {
+        let content = "{{tone:formal|casual}} {{count?:1..10|3}} {{notes?:multiline}} {{size:number}} {{tone}} {{x:y:z}}";
+        let variables = extract(content);
+        let kinds: Vec<(&str, &VariableKind, bool)> =
+            variables.iter().map(|v| (v.name.as_str(), &v.kind, v.required)).collect();
+        assert_eq!(
+            kinds,
+            [
+                ("tone", &VariableKind::Choice { options: vec!["formal".into(), "casual".into()] }, true),
+                ("count", &VariableKind::Number { min: Some(1.0), max: Some(10.0) }, false),
+                ("notes", &VariableKind::Multiline, false),
+                ("size", &VariableKind::Number { min: None, max: None }, true),
+                // An unknown kind is plain text
+                ("x", &VariableKind::Text, true),
+            ]
+        );
+        assert_eq!(variables[1].default.as_deref(), Some("3"));
+        assert_eq!(variables[2].default.as_deref(), Some(""));
+        assert_eq!(variables[0].ranges.len(), 2);
+    }
+
+    #[test]
+    fn test_render_validates_values() {
+        let content = "{{tone:formal|casual}} {{count:1..10|3}} {{notes?:multiline}}";
+        assert_eq!(render(content, &values(&[("tone", "casual")])).unwrap(), "casual 3 ");
+        // An empty number falls back to its default
+        assert_eq!(render(content, &values(&[("tone", "formal"), ("count", "")])).unwrap(), "formal 3 ");
+
+        let err = render(content, &values(&[("tone", "rude"), ("count", "11")])).unwrap_err();
+        assert_eq!(err.message, "Invalid values for template variables");
+        assert_eq!(
+            err.context.get("variables").map(String::as_str),
+            Some("count must be at most 10; tone must be one of formal, casual")
+        );
+        let err = render(content, &values(&[("tone", "")])).unwrap_err();
+        assert_eq!(err.context.get("variables").map(String::as_str), Some("tone"));
+    }
+
+    #[test]
+    fn test_check_defaults() {
+        let valid = "{{tone:formal|casual}} {{tone|casual}} {{count?:1..10}} {{n:number|4}} {{date|soon}}";
+        assert!(check_defaults(valid).is_ok());
+
+        let invalid = "{{n:number|abc}} {{count:1..10|30}} {{tone:formal|casual}} {{tone|rude}}";
+        let err = check_defaults(invalid).unwrap_err();
+        assert_eq!(err.message, "Invalid defaults for template variables");
+        assert_eq!(
+            err.context.get("variables").map(String::as_str),
+            Some("n must be a number; count must be at most 10; tone must be one of formal, casual")
+        );
+    }
+}
@@ -0,0 +1,478 @@
+//! Per-use usage history and analytics.
+//!
+//! Every use of a prompt is appended as one JSON line to `usage.jsonl` in the
+//! data directory. The log is append-only; `use_count`/`last_used` in the index
+//! remain the quick summary, while analytics are computed from the log.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use super::{PromptIndex, PromptMetadata};
+use crate::error::{AppError, AppResult};
+
+/// How a prompt was used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum UsageAction {
+    /// Pasted into the previous app from the launcher
+    #[default]
+    Paste,
+    /// Copied as a markdown file from the launcher
+    CopyFile,
+    /// Pasted from the editor window
+    EditorPaste,
+}
+
+impl UsageAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageAction::Paste => "paste",
+            UsageAction::CopyFile => "copyFile",
+            UsageAction::EditorPaste => "editorPaste",
+        }
+    }
+}
+
+/// One line of the usage log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageEvent {
+    pub prompt_id: String,
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+    #[serde(default)]
+    pub action: UsageAction,
+    /// Bundle ID or process name of the app the prompt was pasted into
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
+}
+
+impl UsageEvent {
+    pub fn now(prompt_id: &str, action: UsageAction, app: Option<String>) -> Self {
+        Self {
+            prompt_id: prompt_id.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            action,
+            app,
+        }
+    }
+
+    fn time(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.timestamp)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    }
+}
+
+/// Use count for a prompt over a time window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptUsage {
+    pub prompt: PromptMetadata,
+    pub count: u32,
+    pub last_used: String,
+}
+
+/// Number of uses on one local calendar day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyUsage {
+    /// Local date, YYYY-MM-DD
+    pub date: String,
+    pub count: u32,
+}
+
+// ==================== Log File ====================
+
+/// Append one event to the log
+pub fn append_event(path: &Path, event: &UsageEvent) -> AppResult<()> {
+    let mut line = serde_json::to_string(event)
+        .map_err(|e| AppError::parse("Failed to serialize usage event", e))?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| AppError::io("Failed to open usage log", e).with_context("path", path.display()))?;
+    file.write_all(line.as_bytes())
+        .map_err(|e| AppError::io("Failed to write usage log", e).with_context("path", path.display()))
+}
+
+/// Read all events, oldest first. Malformed lines (e.g. a torn write) are skipped.
+pub fn read_events(path: &Path) -> AppResult<Vec<UsageEvent>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| AppError::io("Failed to read usage log", e).with_context("path", path.display()))?;
+
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+// ==================== Analytics ====================
+
+/// Most-used prompts since `since`, highest count first (ties: most recent first).
+/// Events for deleted prompts are ignored.
+pub fn top_prompts(
+    index: &PromptIndex,
+    events: &[UsageEvent],
+    since: DateTime<Utc>,
+    limit: usize,
+) -> Vec<PromptUsage> {
+    let mut counts: HashMap<&str, (u32, &str)> = HashMap::new();
+    for event in events {
+        if event.time().is_none_or(|t| t < since) {
+            continue;
+        }
+        let entry = counts.entry(&event.prompt_id).or_insert((0, &event.timestamp));
+        entry.0 += 1;
+        if event.timestamp.as_str() > entry.1 {
+            entry.1 = &event.timestamp;
+        }
+    }
+
+    let mut usage: Vec<PromptUsage> = index
+        .prompts
+        .iter()
+        .filter_map(|prompt| {
+            let (count, last_used) = counts.get(prompt.id.as_str())?;
+            Some(PromptUsage {
+                prompt: prompt.clone(),
+                count: *count,
+                last_used: last_used.to_string(),
+            })
+        })
+        .collect();
+
+    usage.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| b.last_used.cmp(&a.last_used)));
+    usage.truncate(limit);
+    usage
+}
+
+/// Uses per local day for the last `days` days (including today), oldest first.
+/// Days without uses are included with a count of 0.
+pub fn daily_counts(events: &[UsageEvent], days: u32, today: NaiveDate) -> Vec<DailyUsage> {
+    let days = days.max(1);
+    let first = today - Duration::days(i64::from(days) - 1);
+
+    let mut counts: HashMap<NaiveDate, u32> = HashMap::new();
+    for event in events {
+        if let Some(time) = event.time() {
+            let date = time.with_timezone(&Local).date_naive();
+            if date >= first && date <= today {
+                *counts.entry(date).or_insert(0) += 1;
+            }
+        }
+    }
+
+    (0..days)
+        .map(|offset| {
+            let date = first + Duration::days(i64::from(offset));
+            DailyUsage {
+                date: date.format("%Y-%m-%d").to_string(),
+                count: counts.get(&date).copied().unwrap_or(0),
+            }
+        })
+        .collect()
+}
+
+/// Number of uses of each prompt in the given app
+pub fn uses_in_app<'a>(events: &'a [UsageEvent], app: &str) -> HashMap<&'a str, u32> {
+    let mut counts: HashMap<&str, u32> = HashMap::new();
+    for event in events {
+        if event.app.as_deref() == Some(app) {
+            *counts.entry(&event.prompt_id).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Prompts that have never been used
+pub fn never_used(index: &PromptIndex, events: &[UsageEvent]) -> Vec<PromptMetadata> {
+    let last_uses = last_use_by_prompt(events);
+    index
+        .prompts
+        .iter()
+        .filter(|p| p.use_count == 0 && !last_uses.contains_key(p.id.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Prompts not used since `cutoff`, including never-used prompts created before it.
+/// Least recently used first.
+pub fn unused_since(
+    index: &PromptIndex,
+    events: &[UsageEvent],
+    cutoff: DateTime<Utc>,
+) -> Vec<PromptMetadata> {
+    let last_uses = last_use_by_prompt(events);
+
+    let mut unused: Vec<(Option<DateTime<Utc>>, &PromptMetadata)> = index
+        .prompts
+        .iter()
+        .filter(|prompt| is_unused_since(prompt, &last_uses, cutoff))
+        .map(|prompt| (last_use(prompt, &last_uses), prompt))
+        .collect();
+
+    // Never-used (None) sorts first, then oldest use
+    unused.sort_by_key(|(last_use, _)| *last_use);
+    unused.into_iter().map(|(_, p)| p.clone()).collect()
+}
+
+/// Whether a prompt hasn't been used since `cutoff`. A never-used prompt
+/// counts once it was created before `cutoff`.
+pub fn is_unused_since(prompt: &PromptMetadata, last_uses: &LastUses, cutoff: DateTime<Utc>) -> bool {
+    match last_use(prompt, last_uses) {
+        Some(time) => time < cutoff,
+        None => parse_time(&prompt.created).is_none_or(|created| created < cutoff),
+    }
+}
+
+/// When a prompt was last used, from the log. Uses recorded before the log
+/// existed only survive in the index. (`last_used` is also touched on save,
+/// so it's only trusted when the log is silent.)
+pub fn last_use(prompt: &PromptMetadata, last_uses: &LastUses) -> Option<DateTime<Utc>> {
+    last_uses.get(prompt.id.as_str()).copied().or_else(|| {
+        (prompt.use_count > 0)
+            .then(|| parse_time(prompt.last_used.as_deref()?))
+            .flatten()
+    })
+}
+
+/// Export the log as CSV: timestamp, prompt id, prompt name, folder, action, app.
+/// Name and folder are empty for prompts that have since been deleted, and app
+/// for uses where it wasn't known.
+pub fn export_csv(index: &PromptIndex, events: &[UsageEvent]) -> String {
+    let prompts: HashMap<&str, &PromptMetadata> =
+        index.prompts.iter().map(|p| (p.id.as_str(), p)).collect();
+
+    let mut csv = String::from("timestamp,prompt_id,prompt_name,folder,action,app\n");
+    for event in events {
+        let prompt = prompts.get(event.prompt_id.as_str());
+        let fields = [
+            event.timestamp.as_str(),
+            event.prompt_id.as_str(),
+            prompt.map_or("", |p| p.name.as_str()),
+            prompt.map_or("", |p| p.folder.as_str()),
+            event.action.as_str(),
+            event.app.as_deref().unwrap_or(""),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Each prompt's most recent use in the log, by prompt id
+pub type LastUses<'a> = HashMap<&'a str, DateTime<Utc>>;
+
+pub fn last_use_by_prompt(events: &[UsageEvent]) -> LastUses<'_> {
+    let mut last_uses = LastUses::new();
+    for event in events {
+        if let Some(time) = event.time() {
+            let entry = last_uses.entry(&event.prompt_id).or_insert(time);
+            if time > *entry {
+                *entry = time;
+            }
+        }
+    }
+    last_uses
+}
+
+fn parse_time(ts: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(ts).ok().map(|t| t.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(id: &str, name: &str, use_count: u32, created: &str) -> PromptMetadata {
+        PromptMetadata {
+            id: id.to_string(),
+            name: name.to_string(),
+            folder: "uncategorized".to_string(),
+            description: String::new(),
+            filename: format!("{}.md", id),
+            use_count,
+            last_used: None,
+            created: created.to_string(),
+            updated: created.to_string(),
+            icon: None,
+            color: None,
+            tags: Vec::new(),
+            pinned: false,
+            keyword: None,
+            aliases: Vec::new(),
+        }
+    }
+
+    fn event(id: &str, timestamp: &str) -> UsageEvent {
+        UsageEvent {
+            prompt_id: id.to_string(),
+            timestamp: timestamp.to_string(),
+            action: UsageAction::Paste,
+            app: None,
+        }
+    }
+
+    fn index(prompts: Vec<PromptMetadata>) -> PromptIndex {
+        PromptIndex {
+            prompts,
+            ..PromptIndex::default()
+        }
+    }
+
+    fn utc(ts: &str) -> DateTime<Utc> {
+        parse_time(ts).unwrap()
+    }
+
+    #[test]
+    fn test_top_prompts_counts_window_and_skips_deleted() {
+        let idx = index(vec![
+            prompt("a", "Alpha", 3, "2024-01-01T00:00:00Z"),
+            prompt("b", "Beta", 1, "2024-01-01T00:00:00Z"),
+        ]);
+        let events = vec![
+            event("a", "2024-05-01T10:00:00Z"),
+            event("a", "2024-06-02T10:00:00Z"),
+            event("b", "2024-06-03T10:00:00Z"),
+            event("a", "2024-06-04T10:00:00Z"),
+            event("deleted", "2024-06-04T11:00:00Z"),
+        ];
+
+        let top = top_prompts(&idx, &events, utc("2024-06-01T00:00:00Z"), 10);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].prompt.id, "a");
+        assert_eq!(top[0].count, 2);
+        assert_eq!(top[0].last_used, "2024-06-04T10:00:00Z");
+        assert_eq!(top[1].prompt.id, "b");
+
+        assert_eq!(top_prompts(&idx, &events, utc("2024-06-01T00:00:00Z"), 1).len(), 1);
+    }
+
+    #[test]
+    fn test_daily_counts_fills_empty_days() {
+        let today = Local::now().date_naive();
+        let now = Utc::now().to_rfc3339();
+        let events = vec![event("a", &now), event("b", &now)];
+
+        let days = daily_counts(&events, 3, today);
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[0].count, 0);
+        assert_eq!(days[1].count, 0);
+        assert_eq!(days[2].date, today.format("%Y-%m-%d").to_string());
+        assert_eq!(days[2].count, 2);
+    }
+
+    #[test]
+    fn test_never_used_and_unused_since() {
+        let mut legacy = prompt("legacy", "Legacy", 4, "2024-01-01T00:00:00Z");
+        legacy.last_used = Some("2024-02-01T00:00:00Z".to_string());
+        let idx = index(vec![
+            prompt("fresh", "Fresh", 0, "2024-06-09T00:00:00Z"),
+            prompt("old", "Old", 0, "2024-01-01T00:00:00Z"),
+            prompt("active", "Active", 1, "2024-01-01T00:00:00Z"),
+            legacy,
+        ]);
+        let events = vec![event("active", "2024-06-08T00:00:00Z")];
+
+        let never: Vec<_> = never_used(&idx, &events).into_iter().map(|p| p.id).collect();
+        assert_eq!(never, vec!["fresh", "old"]);
+
+        let unused: Vec<_> = unused_since(&idx, &events, utc("2024-06-01T00:00:00Z"))
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+        assert_eq!(unused, vec!["old", "legacy"]);
+    }
+
+    #[test]
+    fn test_export_csv_escapes_fields() {
+        let idx = index(vec![prompt("a", "Say \"hi\", then go", 1, "2024-01-01T00:00:00Z")]);
+        let events = vec![
+            UsageEvent {
+                app: Some("com.apple.Notes".to_string()),
+                ..event("a", "2024-06-01T00:00:00Z")
+            },
+            UsageEvent {
+                action: UsageAction::CopyFile,
+                ..event("gone", "2024-06-02T00:00:00Z")
+            },
+        ];
+
+        let csv = export_csv(&idx, &events);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "timestamp,prompt_id,prompt_name,folder,action,app");
+        assert_eq!(
+            lines[1],
+            "2024-06-01T00:00:00Z,a,\"Say \"\"hi\"\", then go\",uncategorized,paste,com.apple.Notes"
+        );
+        assert_eq!(lines[2], "2024-06-02T00:00:00Z,gone,,,copyFile,");
+    }
+
+    #[test]
+    fn test_uses_in_app_counts_matching_app_only() {
+        let in_app = |id: &str, app: &str| UsageEvent {
+            app: Some(app.to_string()),
+            ..event(id, "2024-06-01T00:00:00Z")
+        };
+        let events = vec![
+            in_app("review", "com.microsoft.VSCode"),
+            in_app("review", "com.microsoft.VSCode"),
+            in_app("reply", "com.apple.mail"),
+            event("review", "2024-06-02T00:00:00Z"),
+        ];
+
+        let counts = uses_in_app(&events, "com.microsoft.VSCode");
+        assert_eq!(counts.get("review"), Some(&2));
+        assert_eq!(counts.get("reply"), None);
+
+        // Events without an app round-trip without the field
+        let line = serde_json::to_string(&events[3]).unwrap();
+        assert!(!line.contains("app"));
+    }
+
+    #[test]
+    fn test_log_round_trip_skips_torn_lines() {
+        let dir = std::env::temp_dir().join(format!("promptlight-usage-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("usage.jsonl");
+
+        append_event(&path, &event("a", "2024-06-01T00:00:00Z")).unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"promptId\":\"tor")
+            .unwrap();
+        fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"\n").unwrap();
+        append_event(&path, &event("b", "2024-06-02T00:00:00Z")).unwrap();
+
+        let events = read_events(&path).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].prompt_id, "b");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
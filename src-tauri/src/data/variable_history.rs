@@ -0,0 +1,159 @@
+//! Values recently entered for template variables, offered when the same
+//! prompt is pasted again.
+//!
+//! Values are recorded when a prompt is pasted, most recent first, both for
+//! the prompt's variable and for the variable name on its own, so a value can
+//! be suggested for the same name in other prompts when that's turned on. The
+//! caller leaves out sensitive variables (see `VariableSettings`). The history
+//! is saved to `variable_history.json` in the library's data directory.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use super::lock::write_atomic;
+use crate::error::{AppError, AppResult};
+
+const FORMAT_VERSION: u32 = 1;
+
+/// Longest value remembered, in characters
+const MAX_VALUE_CHARS: usize = 2000;
+
+/// Values by variable name, most recent first
+type Values = HashMap<String, Vec<String>>;
+
+#[derive(Serialize, Deserialize)]
+struct StoredHistory {
+    version: u32,
+    #[serde(flatten)]
+    history: VariableHistory,
+}
+
+#[derive(Serialize)]
+struct StoredHistoryRef<'a> {
+    version: u32,
+    #[serde(flatten)]
+    history: &'a VariableHistory,
+}
+
+/// Recent values of each variable, by prompt and across prompts
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableHistory {
+    /// By prompt id
+    prompts: HashMap<String, Values>,
+    /// By variable name alone
+    shared: Values,
+}
+
+impl VariableHistory {
+    /// Load the history, starting empty if it's missing, unreadable or from
+    /// another format version
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<StoredHistory>(&json).ok())
+            .filter(|stored| stored.version == FORMAT_VERSION)
+            .map(|stored| stored.history)
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> AppResult<()> {
+        let stored = StoredHistoryRef { version: FORMAT_VERSION, history: self };
+        let json = serde_json::to_string(&stored)
+            .map_err(|e| AppError::parse("Failed to serialize variable history", e))?;
+        write_atomic(path, json.as_bytes()).map_err(|e| {
+            AppError::io("Failed to write variable history", e).with_context("path", path.display())
+        })
+    }
+
+    /// Record the values a prompt was pasted with, keeping the last `limit`
+    /// of each variable. Empty and overly long values are ignored.
+    pub fn record(&mut self, prompt_id: &str, values: &HashMap<String, String>, limit: usize) {
+        for (name, value) in values {
+            if value.trim().is_empty() || value.chars().count() > MAX_VALUE_CHARS {
+                continue;
+            }
+            let by_prompt = self.prompts.entry(prompt_id.to_string()).or_default();
+            push_recent(by_prompt.entry(name.clone()).or_default(), value, limit);
+            push_recent(self.shared.entry(name.clone()).or_default(), value, limit);
+        }
+    }
+
+    /// Up to `limit` recent values of a prompt's variable, most recent first.
+    /// With `shared`, values entered for the same name in other prompts count.
+    pub fn recent(&self, prompt_id: &str, name: &str, shared: bool, limit: usize) -> Vec<String> {
+        let values = if shared {
+            self.shared.get(name)
+        } else {
+            self.prompts.get(prompt_id).and_then(|values| values.get(name))
+        };
+        values.map(|values| values.iter().take(limit).cloned().collect()).unwrap_or_default()
+    }
+
+    /// Forget the values entered for a prompt (its variables' shared values
+    /// are kept, since other prompts may have entered them too)
+    pub fn remove_prompt(&mut self, prompt_id: &str) {
+        self.prompts.remove(prompt_id);
+    }
+
+    pub fn clear(&mut self) {
+        self.prompts.clear();
+        self.shared.clear();
+    }
+}
+
+/// Move `value` to the front of `values`, keeping `limit` of them
+fn push_recent(values: &mut Vec<String>, value: &str, limit: usize) {
+    values.retain(|v| v != value);
+    values.insert(0, value.to_string());
+    values.truncate(limit);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_records_most_recent_first_per_prompt_and_shared() {
+        let mut history = VariableHistory::default();
+        history.record("a", &values(&[("topic", "Rust"), ("tone", "")]), 2);
+        history.record("a", &values(&[("topic", "Go")]), 2);
+        history.record("b", &values(&[("topic", "Zig")]), 2);
+        history.record("a", &values(&[("topic", "Rust")]), 2);
+
+        assert_eq!(history.recent("a", "topic", false, 5), ["Rust", "Go"]);
+        assert_eq!(history.recent("b", "topic", false, 5), ["Zig"]);
+        assert_eq!(history.recent("b", "topic", true, 5), ["Rust", "Zig"]);
+        assert_eq!(history.recent("a", "topic", false, 1), ["Rust"]);
+        // Empty values aren't remembered
+        assert!(history.recent("a", "tone", false, 5).is_empty());
+
+        history.remove_prompt("a");
+        assert!(history.recent("a", "topic", false, 5).is_empty());
+        assert_eq!(history.recent("a", "topic", true, 5), ["Rust", "Zig"]);
+    }
+
+    #[test]
+    fn test_persists_and_clears() {
+        let dir = std::env::temp_dir().join(format!("promptlight-variables-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("variable_history.json");
+
+        let mut history = VariableHistory::default();
+        history.record("a", &values(&[("topic", "Rust")]), 5);
+        history.save(&path).unwrap();
+
+        let mut loaded = VariableHistory::load(&path);
+        assert_eq!(loaded.recent("a", "topic", false, 5), ["Rust"]);
+        loaded.clear();
+        assert!(loaded.recent("a", "topic", true, 5).is_empty());
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,167 @@
+//! Structured error type shared by every Tauri command.
+//!
+//! Errors cross the IPC boundary as `{ code, message, context }`:
+//! - `code` is a stable identifier the frontend can branch on (e.g. `NotFound`)
+//! - `message` is a human-readable description for display/logging
+//! - `context` carries identifying details such as the prompt id or file path
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Stable, machine-readable error codes.
+///
+/// Serialized as the variant name, so renaming a variant is a breaking
+/// change for the frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ErrorCode {
+    /// A prompt, folder or other item does not exist
+    NotFound,
+    /// An item with the same name or key already exists
+    AlreadyExists,
+    /// Input failed validation
+    InvalidInput,
+    /// Reading or writing local files failed
+    Io,
+    /// Stored or received data could not be (de)serialized
+    Parse,
+    /// The operation requires a signed-in user
+    NotAuthenticated,
+    /// The auth session expired or was rejected; the user must re-authenticate
+    AuthExpired,
+    /// The sign-in flow failed (OAuth, token exchange)
+    AuthFailed,
+    /// A network request could not be completed
+    Network,
+    /// The remote service (Firestore, Firebase) returned an error response
+    Remote,
+    /// Sync refused to run because it would lose data
+    SyncRejected,
+    /// Another process holds the library lock
+    LibraryLocked,
+    /// The OS denied a permission we need (e.g. macOS Accessibility). Only
+    /// raised on macOS so far.
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    PermissionDenied,
+    /// Reading or writing the system clipboard failed
+    Clipboard,
+    /// Creating, showing or hiding a window failed
+    Window,
+    /// Registering or parsing the global hotkey failed
+    Hotkey,
+    /// A platform-specific OS integration failed or is not implemented
+    Platform,
+    /// Unexpected internal failure
+    Internal,
+}
+
+/// Error returned by store, sync, auth and OS operations
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub context: BTreeMap<String, String>,
+}
+
+/// Convenience alias for results carrying an `AppError`
+pub type AppResult<T> = Result<T, AppError>;
+
+impl AppError {
+    /// Create an error with the given code and message
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            context: BTreeMap::new(),
+        }
+    }
+
+    /// Attach a piece of context (e.g. `promptId`, `path`)
+    pub fn with_context(mut self, key: &str, value: impl ToString) -> Self {
+        self.context.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub fn already_exists(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::AlreadyExists, message)
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidInput, message)
+    }
+
+    /// IO failure, formatted as "<action>: <cause>"
+    pub fn io(action: &str, err: impl fmt::Display) -> Self {
+        Self::new(ErrorCode::Io, format!("{}: {}", action, err))
+    }
+
+    /// (De)serialization failure, formatted as "<action>: <cause>"
+    pub fn parse(action: &str, err: impl fmt::Display) -> Self {
+        Self::new(ErrorCode::Parse, format!("{}: {}", action, err))
+    }
+
+    /// Network failure, formatted as "<action>: <cause>"
+    pub fn network(action: &str, err: impl fmt::Display) -> Self {
+        Self::new(ErrorCode::Network, format!("{}: {}", action, err))
+    }
+
+    pub fn platform(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Platform, message)
+    }
+
+    pub fn window(action: &str, err: impl fmt::Display) -> Self {
+        Self::new(ErrorCode::Window, format!("{}: {}", action, err))
+    }
+
+    pub fn clipboard(action: &str, err: impl fmt::Display) -> Self {
+        Self::new(ErrorCode::Clipboard, format!("{}: {}", action, err))
+    }
+
+    /// Shorthand for the common "Prompt not found: <id>" error
+    pub fn prompt_not_found(id: &str) -> Self {
+        Self::not_found(format!("Prompt not found: {}", id)).with_context("promptId", id)
+    }
+
+    /// Shorthand for the common "Folder does not exist" error
+    pub fn folder_not_found(name: &str) -> Self {
+        Self::not_found("Folder does not exist").with_context("folder", name)
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_code_message_and_context() {
+        let err = AppError::prompt_not_found("abc");
+        let json = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(json["code"], "NotFound");
+        assert_eq!(json["message"], "Prompt not found: abc");
+        assert_eq!(json["context"]["promptId"], "abc");
+    }
+
+    #[test]
+    fn test_empty_context_is_omitted() {
+        let err = AppError::new(ErrorCode::AuthExpired, "Session expired");
+        let json = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(json["code"], "AuthExpired");
+        assert!(json.get("context").is_none());
+    }
+}
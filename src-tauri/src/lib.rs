@@ -4,6 +4,7 @@ extern crate objc;
 
 mod auth;
 mod data;
+mod error;
 mod os;
 
 use std::sync::Arc;
@@ -48,6 +49,10 @@ pub fn run() {
     );
 
     tauri::Builder::default()
+        // Must be registered first: a second launch forwards its args here and exits
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            crate::os::cli::handle_forwarded_args(app, &argv);
+        }))
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_clipboard_manager::init())
@@ -115,20 +120,39 @@ pub fn run() {
                 eprintln!("Failed to register global hotkey from settings: {}", e);
             }
 
+            // Run any actions passed on the command line (e.g. --new-prompt)
+            crate::os::cli::handle_startup_args(app_handle);
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             // Data commands (use DataStore trait via SyncService)
             data::commands::get_folders,
             data::commands::get_index,
+            data::commands::get_index_page,
             data::commands::add_folder,
             data::commands::rename_folder,
             data::commands::delete_folder,
+            data::commands::save_smart_folder,
+            data::commands::delete_smart_folder,
             data::commands::get_prompt,
             data::commands::save_prompt,
             data::commands::delete_prompt,
+            data::commands::extract_variables,
+            data::commands::clear_variable_history,
+            data::commands::render_prompt,
+            data::commands::get_included_by,
             data::commands::search_prompts,
+            data::commands::search_launcher,
             data::commands::record_usage,
+            data::commands::clear_selection_history,
+            data::commands::get_query_suggestions,
+            data::commands::clear_query_history,
+            data::commands::get_top_prompts,
+            data::commands::get_daily_usage,
+            data::commands::get_never_used_prompts,
+            data::commands::get_unused_prompts,
+            data::commands::export_usage_csv,
             // Sync commands
             data::commands::set_sync_auth,
             data::commands::clear_sync_auth,
@@ -143,12 +167,14 @@ pub fn run() {
             data::settings::set_autostart_enabled,
             // OS commands
             os::paste::paste_and_dismiss,
+            os::paste::paste_prompt,
             os::paste::dismiss_window,
             os::paste::copy_to_clipboard,
             os::paste::paste_from_editor,
             os::paste::copy_as_markdown_file,
             os::window::open_editor_window,
             os::window::close_editor_window,
+            os::actions::execute_action,
             // Welcome window commands
             os::welcome::open_welcome_window,
             os::welcome::close_welcome_window,
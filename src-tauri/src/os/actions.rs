@@ -0,0 +1,42 @@
+//! Launcher actions
+//!
+//! Launcher search lists app actions (see `data::palette`) next to prompts.
+//! Picking one runs it here, so everything the app does can start from the
+//! launcher.
+
+use tauri::{AppHandle, Emitter, State};
+
+use crate::data::palette::LauncherAction;
+use crate::data::sync::SyncServiceState;
+use crate::error::AppResult;
+use crate::os::{cli, launcher, window};
+
+/// Run an action picked in the launcher. The launcher is hidden first, as
+/// for a paste.
+#[tauri::command]
+pub async fn execute_action(
+    app: AppHandle,
+    sync: State<'_, SyncServiceState>,
+    action: LauncherAction,
+) -> AppResult<()> {
+    println!("[actions] Running {:?}", action);
+    launcher::hide_launcher(&app);
+
+    match action {
+        LauncherAction::NewPrompt => cli::open_editor(&app, None),
+        LauncherAction::OpenSettings => {
+            window::open_editor_window(app, None, None, Some("settings".to_string()), None).await?
+        }
+        LauncherAction::Import => {
+            window::open_editor_window(app, None, None, Some("import".to_string()), None).await?
+        }
+        LauncherAction::SyncNow => sync.sync_to_firestore().await?,
+        LauncherAction::SignOut => {
+            crate::auth::sign_out().await?;
+            sync.clear_auth();
+            // Let open windows drop the signed-in user
+            let _ = app.emit("signed-out", ());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,227 @@
+//! Command-line actions
+//!
+//! PromptLight runs as a single instance. Launching it again forwards the new
+//! process's arguments to the running instance, which performs the action.
+//! This lets desktops without global hotkey support (e.g. Wayland) bind a
+//! system shortcut to `promptlight --toggle`.
+//!
+//! Supported arguments:
+//! - `--toggle`: show or hide the launcher
+//! - `--show`: show the launcher
+//! - `--new-prompt`: open the editor with a new prompt
+//! - `--open <id>`: open a prompt in the editor
+//! - `--paste <id>`: paste a prompt, its `{{variables}}` filled in from the
+//!   `--var name=value` arguments that follow it (or their defaults)
+//!
+//! Unknown arguments are ignored (the OS and `--autostart` add their own).
+
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::data::sync::SyncServiceState;
+use crate::data::template;
+use crate::os::environment::AppEnvironment;
+use crate::os::{launcher, paste};
+
+/// An action requested on the command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchAction {
+    Toggle,
+    Show,
+    NewPrompt,
+    Open(String),
+    Paste {
+        id: String,
+        values: HashMap<String, String>,
+    },
+}
+
+/// Parse launch arguments (excluding the program name) into actions, in order
+pub fn parse_args<I, S>(args: I) -> Vec<LaunchAction>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut actions = Vec::new();
+    let mut args = args.into_iter().peekable();
+
+    while let Some(arg) = args.next() {
+        match arg.as_ref() {
+            "--toggle" => actions.push(LaunchAction::Toggle),
+            "--show" => actions.push(LaunchAction::Show),
+            "--new-prompt" => actions.push(LaunchAction::NewPrompt),
+            "--open" => match args.next_if(|next| !next.as_ref().starts_with("--")) {
+                Some(id) => actions.push(LaunchAction::Open(id.as_ref().to_string())),
+                None => eprintln!("[cli] --open requires a prompt id"),
+            },
+            "--paste" => match args.next_if(|next| !next.as_ref().starts_with("--")) {
+                Some(id) => actions.push(paste_action(id.as_ref())),
+                None => eprintln!("[cli] --paste requires a prompt id"),
+            },
+            "--var" => match args.next_if(|next| !next.as_ref().starts_with("--")) {
+                Some(var) => add_var(&mut actions, var.as_ref()),
+                None => eprintln!("[cli] --var requires name=value"),
+            },
+            other => {
+                if let Some(id) = other.strip_prefix("--open=") {
+                    if !id.is_empty() {
+                        actions.push(LaunchAction::Open(id.to_string()));
+                    }
+                } else if let Some(id) = other.strip_prefix("--paste=") {
+                    if !id.is_empty() {
+                        actions.push(paste_action(id));
+                    }
+                } else if let Some(var) = other.strip_prefix("--var=") {
+                    add_var(&mut actions, var);
+                }
+            }
+        }
+    }
+
+    actions
+}
+
+fn paste_action(id: &str) -> LaunchAction {
+    LaunchAction::Paste { id: id.to_string(), values: HashMap::new() }
+}
+
+/// Add a `name=value` variable to the `--paste` before it
+fn add_var(actions: &mut [LaunchAction], var: &str) {
+    let Some((name, value)) = var.split_once('=') else {
+        eprintln!("[cli] --var expects name=value, got {:?}", var);
+        return;
+    };
+    match actions.last_mut() {
+        Some(LaunchAction::Paste { values, .. }) => {
+            values.insert(name.trim().to_string(), value.to_string());
+        }
+        _ => eprintln!("[cli] --var must follow --paste"),
+    }
+}
+
+/// Handle arguments forwarded from a second launch.
+/// A bare relaunch (no actions) brings up the launcher.
+pub fn handle_forwarded_args(app: &AppHandle, argv: &[String]) {
+    let actions = parse_args(argv.iter().skip(1));
+    println!("[cli] Forwarded actions: {:?}", actions);
+
+    if actions.is_empty() {
+        launcher::show_launcher(app);
+        return;
+    }
+
+    for action in &actions {
+        run_action(app, action);
+    }
+}
+
+/// Handle this process's own arguments on first launch.
+/// `--toggle` shows the launcher, since there is nothing to hide yet.
+pub fn handle_startup_args(app: &AppHandle) {
+    let actions = parse_args(std::env::args().skip(1));
+
+    for action in &actions {
+        match action {
+            LaunchAction::Toggle => run_action(app, &LaunchAction::Show),
+            other => run_action(app, other),
+        }
+    }
+}
+
+fn run_action(app: &AppHandle, action: &LaunchAction) {
+    match action {
+        LaunchAction::Toggle => launcher::toggle_launcher(app),
+        LaunchAction::Show => launcher::show_launcher(app),
+        LaunchAction::NewPrompt => open_editor(app, None),
+        LaunchAction::Open(id) => open_editor(app, Some(id.clone())),
+        LaunchAction::Paste { id, values } => paste_prompt(app, id.clone(), values.clone()),
+    }
+}
+
+/// Render a prompt and paste it like the launcher does. Errors (unknown
+/// prompt, missing values) are logged, since there's no window to show them.
+fn paste_prompt(app: &AppHandle, id: String, values: HashMap<String, String>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let store = app.state::<SyncServiceState>();
+        let sync = store.inner().as_ref();
+        let env = AppEnvironment::new(&app, sync);
+        let result = template::render_prompt(sync, &id, &values, &env)
+            .await
+            .and_then(|text| paste::paste_text(&app, &text));
+        match result {
+            Ok(()) => paste::remember_values(sync, &id, &values).await,
+            Err(e) => eprintln!("[cli] Failed to paste {}: {}", id, e),
+        }
+    });
+}
+
+/// Open the editor window on a prompt, or on a new prompt if `prompt_id` is None
+pub(crate) fn open_editor(app: &AppHandle, prompt_id: Option<String>) {
+    launcher::hide_launcher(app);
+
+    // An existing editor only shows itself for a None id, so ask it for a new prompt
+    if prompt_id.is_none() {
+        if let Some(editor) = app.get_webview_window("editor") {
+            let _ = editor.emit("new-prompt", ());
+        }
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) =
+            crate::os::window::open_editor_window(app, prompt_id, None, None, None).await
+        {
+            eprintln!("[cli] Failed to open editor: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_actions_in_order() {
+        let actions = parse_args(["--show", "--open", "abc-123", "--toggle"]);
+        assert_eq!(
+            actions,
+            vec![
+                LaunchAction::Show,
+                LaunchAction::Open("abc-123".to_string()),
+                LaunchAction::Toggle,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_open_with_equals() {
+        assert_eq!(parse_args(["--open=xyz"]), vec![LaunchAction::Open("xyz".to_string())]);
+        assert!(parse_args(["--open="]).is_empty());
+    }
+
+    #[test]
+    fn test_open_without_id_is_ignored() {
+        assert!(parse_args(["--open"]).is_empty());
+        assert_eq!(parse_args(["--open", "--new-prompt"]), vec![LaunchAction::NewPrompt]);
+    }
+
+    #[test]
+    fn test_unknown_args_are_ignored() {
+        let actions = parse_args(["--autostart", "-psn_0_12345", "--new-prompt"]);
+        assert_eq!(actions, vec![LaunchAction::NewPrompt]);
+    }
+
+    #[test]
+    fn test_parse_paste_with_vars() {
+        let actions = parse_args(["--var", "x=0", "--paste", "abc", "--var", "topic=a=b", "--var=tone=", "--paste=def"]);
+        let values = HashMap::from([("topic".to_string(), "a=b".to_string()), ("tone".to_string(), String::new())]);
+        assert_eq!(
+            actions,
+            vec![
+                LaunchAction::Paste { id: "abc".to_string(), values },
+                LaunchAction::Paste { id: "def".to_string(), values: HashMap::new() },
+            ]
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! The app's environment for built-in template variables
+
+use chrono::{DateTime, Local};
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::data::builtins::Environment;
+use crate::data::sync::SyncService;
+use crate::os::previous_app;
+
+/// Resolves built-ins from the clipboard, the captured previous app and the
+/// signed-in account
+pub struct AppEnvironment<'a> {
+    app: &'a AppHandle,
+    sync: &'a SyncService,
+}
+
+impl<'a> AppEnvironment<'a> {
+    pub fn new(app: &'a AppHandle, sync: &'a SyncService) -> Self {
+        Self { app, sync }
+    }
+}
+
+impl Environment for AppEnvironment<'_> {
+    fn clipboard(&self) -> Option<String> {
+        self.app.clipboard().read_text().ok()
+    }
+
+    fn app(&self) -> Option<String> {
+        previous_app::get_previous_app_key()
+    }
+
+    /// The account's email (or user id) for an account library; None for
+    /// the local one
+    fn library(&self) -> Option<String> {
+        let user_id = self.sync.current_user_id()?;
+        let email = crate::auth::load_auth_session().and_then(|session| session.user.email);
+        Some(email.unwrap_or(user_id))
+    }
+
+    fn now(&self) -> DateTime<Local> {
+        Local::now()
+    }
+}
@@ -7,20 +7,8 @@ use tauri::{AppHandle, Manager};
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
 
 use crate::data::settings::AppSettings;
-use crate::os::focus::get_key_window_screen_bounds;
-use crate::os::previous_app;
-
-/// Close the editor window if it exists and always-on-top mode is enabled
-fn close_editor_if_always_on_top(app: &AppHandle) {
-    let settings = AppSettings::load();
-    if settings.general.editor_always_on_top {
-        if let Some(editor) = app.get_webview_window("editor") {
-            let _ = editor.close();
-        }
-    }
-}
-
-const WINDOW_WIDTH: f64 = 650.0;
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::os::launcher;
 
 /// State to track the currently registered shortcut
 pub struct HotkeyState {
@@ -36,10 +24,10 @@ impl Default for HotkeyState {
 }
 
 /// Parse a hotkey string like "CommandOrControl+Shift+Space" into Tauri's Shortcut
-pub fn parse_hotkey(hotkey_str: &str) -> Result<Shortcut, String> {
+pub fn parse_hotkey(hotkey_str: &str) -> AppResult<Shortcut> {
     let parts: Vec<&str> = hotkey_str.split('+').collect();
     if parts.is_empty() {
-        return Err("Empty hotkey string".to_string());
+        return Err(AppError::invalid_input("Empty hotkey string"));
     }
 
     let mut modifiers = Modifiers::empty();
@@ -70,12 +58,14 @@ pub fn parse_hotkey(hotkey_str: &str) -> Result<Shortcut, String> {
         }
     }
 
-    let code = key_code.ok_or("No key code found in hotkey string")?;
+    let code = key_code.ok_or_else(|| {
+        AppError::invalid_input("No key code found in hotkey string").with_context("hotkey", hotkey_str)
+    })?;
     Ok(Shortcut::new(Some(modifiers), code))
 }
 
 /// Parse a key name into a Tauri Code
-fn parse_key_code(key: &str) -> Result<Code, String> {
+fn parse_key_code(key: &str) -> AppResult<Code> {
     let key_lower = key.to_lowercase();
     match key_lower.as_str() {
         // Letters
@@ -158,12 +148,16 @@ fn parse_key_code(key: &str) -> Result<Code, String> {
         "comma" | "," => Ok(Code::Comma),
         "period" | "." => Ok(Code::Period),
         "slash" | "/" => Ok(Code::Slash),
-        _ => Err(format!("Unknown key code: {}", key)),
+        _ => Err(AppError::invalid_input(format!("Unknown key code: {}", key)).with_context("key", key)),
     }
 }
 
+fn hotkey_error(action: &str, err: impl std::fmt::Display) -> AppError {
+    AppError::new(ErrorCode::Hotkey, format!("{}: {}", action, err))
+}
+
 /// Register the global hotkey for showing/hiding the launcher
-pub fn register_hotkey(app: &AppHandle, hotkey_str: &str) -> Result<(), String> {
+pub fn register_hotkey(app: &AppHandle, hotkey_str: &str) -> AppResult<()> {
     let shortcut = parse_hotkey(hotkey_str)?;
 
     // Unregister any existing shortcut first
@@ -178,72 +172,9 @@ pub fn register_hotkey(app: &AppHandle, hotkey_str: &str) -> Result<(), String>
                 return;
             }
 
-            // Dismiss welcome window if visible
-            if crate::os::welcome::is_welcome_visible(&app_handle) {
-                if let Some(welcome) = app_handle.get_webview_window("welcome") {
-                    let _ = welcome.close();
-                }
-                // Note: Don't save dismissal preference here - let user decide via checkbox
-            }
-
-            if let Some(window) = app_handle.get_webview_window("launcher") {
-                // Toggle window visibility
-                if window.is_visible().unwrap_or(false) {
-                    let _ = window.hide();
-                } else {
-                    // Close editor window if in always-on-top mode (they shouldn't coexist)
-                    close_editor_if_always_on_top(&app_handle);
-
-                    // Capture previous app before showing (for paste-back feature)
-                    if let Err(e) = previous_app::capture_previous_app() {
-                        eprintln!("[hotkey] Failed to capture previous app: {}", e);
-                    }
-
-                    // Position on the screen with the key window (uses fast native NSScreen API)
-                    let positioned = if let Some(bounds) = get_key_window_screen_bounds() {
-                        let x = bounds.x + (bounds.width - WINDOW_WIDTH) / 2.0;
-                        let y = bounds.y + bounds.height / 4.0;
-                        let _ = window.set_position(tauri::LogicalPosition::new(x, y));
-                        true
-                    } else {
-                        false
-                    };
-
-                    // Fallback: position on monitor with cursor
-                    if !positioned {
-                        if let Ok(cursor_pos) = window.cursor_position() {
-                            if let Ok(monitors) = window.available_monitors() {
-                                for monitor in monitors {
-                                    let mon_pos = monitor.position();
-                                    let mon_size = monitor.size();
-                                    let scale = monitor.scale_factor();
-
-                                    let mon_x = mon_pos.x as f64;
-                                    let mon_y = mon_pos.y as f64;
-                                    let mon_w = mon_size.width as f64 / scale;
-                                    let mon_h = mon_size.height as f64 / scale;
-
-                                    if cursor_pos.x >= mon_x
-                                        && cursor_pos.x < mon_x + mon_w
-                                        && cursor_pos.y >= mon_y
-                                        && cursor_pos.y < mon_y + mon_h
-                                    {
-                                        let x = mon_x + (mon_w - WINDOW_WIDTH) / 2.0;
-                                        let y = mon_y + mon_h / 4.0;
-                                        let _ = window.set_position(tauri::LogicalPosition::new(x, y));
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                    }
-
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
-            }
+            launcher::toggle_launcher(&app_handle);
         })
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| hotkey_error("Failed to register hotkey", e).with_context("hotkey", hotkey_str))?;
 
     // Store the registered shortcut
     if let Some(state) = app.try_state::<HotkeyState>() {
@@ -256,13 +187,13 @@ pub fn register_hotkey(app: &AppHandle, hotkey_str: &str) -> Result<(), String>
 }
 
 /// Unregister the currently registered hotkey
-pub fn unregister_current_hotkey(app: &AppHandle) -> Result<(), String> {
+pub fn unregister_current_hotkey(app: &AppHandle) -> AppResult<()> {
     if let Some(state) = app.try_state::<HotkeyState>() {
         if let Ok(mut current) = state.current_shortcut.lock() {
             if let Some(shortcut) = current.take() {
                 app.global_shortcut()
                     .unregister(shortcut)
-                    .map_err(|e| e.to_string())?;
+                    .map_err(|e| hotkey_error("Failed to unregister hotkey", e))?;
             }
         }
     }
@@ -271,14 +202,14 @@ pub fn unregister_current_hotkey(app: &AppHandle) -> Result<(), String> {
 
 /// Get the current hotkey from settings
 #[tauri::command]
-pub fn get_current_hotkey() -> Result<Option<String>, String> {
+pub fn get_current_hotkey() -> AppResult<Option<String>> {
     let settings = AppSettings::load();
     Ok(settings.general.hotkey)
 }
 
 /// Set and register a new hotkey (or clear it if None)
 #[tauri::command]
-pub fn set_hotkey(app: AppHandle, hotkey: Option<String>) -> Result<(), String> {
+pub fn set_hotkey(app: AppHandle, hotkey: Option<String>) -> AppResult<()> {
     // Unregister current hotkey first
     unregister_current_hotkey(&app)?;
 
@@ -296,7 +227,7 @@ pub fn set_hotkey(app: AppHandle, hotkey: Option<String>) -> Result<(), String>
 }
 
 /// Initialize hotkey from settings on app startup
-pub fn init_hotkey_from_settings(app: &AppHandle) -> Result<(), String> {
+pub fn init_hotkey_from_settings(app: &AppHandle) -> AppResult<()> {
     let settings = AppSettings::load();
     if let Some(ref hotkey_str) = settings.general.hotkey {
         register_hotkey(app, hotkey_str)?;
@@ -307,12 +238,12 @@ pub fn init_hotkey_from_settings(app: &AppHandle) -> Result<(), String> {
 /// Temporarily pause the global hotkey (for recording a new one)
 /// This unregisters the shortcut but doesn't change settings
 #[tauri::command]
-pub fn pause_hotkey(app: AppHandle) -> Result<(), String> {
+pub fn pause_hotkey(app: AppHandle) -> AppResult<()> {
     unregister_current_hotkey(&app)
 }
 
 /// Resume the global hotkey from settings (after recording)
 #[tauri::command]
-pub fn resume_hotkey(app: AppHandle) -> Result<(), String> {
+pub fn resume_hotkey(app: AppHandle) -> AppResult<()> {
     init_hotkey_from_settings(&app)
 }
@@ -0,0 +1,114 @@
+//! Showing and hiding the launcher window
+//!
+//! Shared by the global hotkey and by actions forwarded from a second launch
+//! (e.g. `promptlight --toggle`).
+
+use tauri::{AppHandle, Manager};
+
+use crate::data::settings::AppSettings;
+use crate::os::focus::get_key_window_screen_bounds;
+use crate::os::previous_app;
+
+const WINDOW_WIDTH: f64 = 650.0;
+
+/// Close the editor window if it exists and always-on-top mode is enabled
+fn close_editor_if_always_on_top(app: &AppHandle) {
+    let settings = AppSettings::load();
+    if settings.general.editor_always_on_top {
+        if let Some(editor) = app.get_webview_window("editor") {
+            let _ = editor.close();
+        }
+    }
+}
+
+/// Toggle launcher visibility
+pub fn toggle_launcher(app: &AppHandle) {
+    let visible = app
+        .get_webview_window("launcher")
+        .map(|window| window.is_visible().unwrap_or(false))
+        .unwrap_or(false);
+
+    if visible {
+        hide_launcher(app);
+    } else {
+        show_launcher(app);
+    }
+}
+
+/// Hide the launcher window
+pub fn hide_launcher(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("launcher") {
+        let _ = window.hide();
+    }
+}
+
+/// Show the launcher on the active screen and focus it
+pub fn show_launcher(app: &AppHandle) {
+    // Dismiss welcome window if visible
+    if crate::os::welcome::is_welcome_visible(app) {
+        if let Some(welcome) = app.get_webview_window("welcome") {
+            let _ = welcome.close();
+        }
+        // Note: Don't save dismissal preference here - let user decide via checkbox
+    }
+
+    let Some(window) = app.get_webview_window("launcher") else {
+        return;
+    };
+
+    // Already showing: just bring it forward
+    if window.is_visible().unwrap_or(false) {
+        let _ = window.set_focus();
+        return;
+    }
+
+    // Close editor window if in always-on-top mode (they shouldn't coexist)
+    close_editor_if_always_on_top(app);
+
+    // Capture previous app before showing (for paste-back feature)
+    if let Err(e) = previous_app::capture_previous_app() {
+        eprintln!("[launcher] Failed to capture previous app: {}", e);
+    }
+
+    // Position on the screen with the key window (uses fast native NSScreen API)
+    let positioned = if let Some(bounds) = get_key_window_screen_bounds() {
+        let x = bounds.x + (bounds.width - WINDOW_WIDTH) / 2.0;
+        let y = bounds.y + bounds.height / 4.0;
+        let _ = window.set_position(tauri::LogicalPosition::new(x, y));
+        true
+    } else {
+        false
+    };
+
+    // Fallback: position on monitor with cursor
+    if !positioned {
+        if let Ok(cursor_pos) = window.cursor_position() {
+            if let Ok(monitors) = window.available_monitors() {
+                for monitor in monitors {
+                    let mon_pos = monitor.position();
+                    let mon_size = monitor.size();
+                    let scale = monitor.scale_factor();
+
+                    let mon_x = mon_pos.x as f64;
+                    let mon_y = mon_pos.y as f64;
+                    let mon_w = mon_size.width as f64 / scale;
+                    let mon_h = mon_size.height as f64 / scale;
+
+                    if cursor_pos.x >= mon_x
+                        && cursor_pos.x < mon_x + mon_w
+                        && cursor_pos.y >= mon_y
+                        && cursor_pos.y < mon_y + mon_h
+                    {
+                        let x = mon_x + (mon_w - WINDOW_WIDTH) / 2.0;
+                        let y = mon_y + mon_h / 4.0;
+                        let _ = window.set_position(tauri::LogicalPosition::new(x, y));
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    let _ = window.show();
+    let _ = window.set_focus();
+}
@@ -1,5 +1,9 @@
+pub mod actions;
 pub mod focus;
+pub mod cli;
+pub mod environment;
 pub mod hotkey;
+pub mod launcher;
 pub mod paste;
 pub mod platform;
 pub mod previous_app;
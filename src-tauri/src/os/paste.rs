@@ -1,6 +1,12 @@
-use tauri::{AppHandle, Manager};
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager, State};
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
+use crate::data::settings::AppSettings;
+use crate::data::sync::{SyncService, SyncServiceState};
+use crate::data::template;
+use crate::error::{AppError, AppResult};
+use crate::os::environment::AppEnvironment;
 use crate::os::previous_app;
 
 /// Check and log accessibility permission status (macOS only)
@@ -25,16 +31,55 @@ fn log_accessibility_status() {
 /// Uses graceful degradation - each step is attempted even if previous fails.
 /// Only clipboard failure returns an error; other failures are silent.
 #[tauri::command]
-pub async fn paste_and_dismiss(app: AppHandle, text: String) -> Result<(), String> {
-    println!("[paste] Starting paste_and_dismiss, text length: {}", text.len());
+pub async fn paste_and_dismiss(app: AppHandle, text: String) -> AppResult<()> {
+    paste_text(&app, &text)
+}
+
+/// Paste a prompt with its `{{variables}}` filled in from `values`, the
+/// environment (built-ins) or their defaults, followed by `rider_text` if
+/// given. Nothing is pasted if a variable has no value. The values are
+/// remembered for next time, unless turned off in settings.
+#[tauri::command]
+pub async fn paste_prompt(
+    app: AppHandle,
+    store: State<'_, SyncServiceState>,
+    prompt_id: String,
+    values: Option<HashMap<String, String>>,
+    rider_text: Option<String>,
+) -> AppResult<()> {
+    let sync = store.inner().as_ref();
+    let env = AppEnvironment::new(&app, sync);
+    let values = values.unwrap_or_default();
+    let mut text = template::render_prompt(sync, &prompt_id, &values, &env).await?;
+    if let Some(rider) = rider_text.filter(|r| !r.is_empty()) {
+        text.push(' ');
+        text.push_str(&rider);
+    }
+    paste_text(&app, &text)?;
+    remember_values(sync, &prompt_id, &values).await;
+    Ok(())
+}
+
+/// Remember the values a prompt was pasted with. Failures are logged, since
+/// the paste already happened.
+pub(crate) async fn remember_values(sync: &SyncService, prompt_id: &str, values: &HashMap<String, String>) {
+    let settings = AppSettings::load().variables;
+    if let Err(e) = template::remember_values(sync, prompt_id, values, &settings).await {
+        eprintln!("[paste] Failed to remember variable values: {}", e);
+    }
+}
+
+/// The paste steps shared by `paste_and_dismiss`, `paste_prompt` and `--paste`
+pub(crate) fn paste_text(app: &AppHandle, text: &str) -> AppResult<()> {
+    println!("[paste] Starting paste, text length: {}", text.len());
 
     // Log accessibility status upfront for debugging
     log_accessibility_status();
 
     // 1. Copy text to clipboard (MUST succeed for paste to work)
     app.clipboard()
-        .write_text(&text)
-        .map_err(|e| format!("Clipboard write failed: {}", e))?;
+        .write_text(text)
+        .map_err(|e| AppError::clipboard("Clipboard write failed", e))?;
     println!("[paste] Clipboard write successful");
 
     // 2. Small delay for clipboard propagation to system
@@ -89,35 +134,39 @@ pub async fn paste_and_dismiss(app: AppHandle, text: String) -> Result<(), Strin
 
 /// Hide the launcher window
 #[tauri::command]
-pub async fn dismiss_window(app: AppHandle) -> Result<(), String> {
+pub async fn dismiss_window(app: AppHandle) -> AppResult<()> {
     if let Some(window) = app.get_webview_window("launcher") {
-        window.hide().map_err(|e| format!("Failed to hide window: {}", e))?;
+        window
+            .hide()
+            .map_err(|e| AppError::window("Failed to hide window", e))?;
     }
     Ok(())
 }
 
 /// Copy text to clipboard only (no window action)
 #[tauri::command]
-pub async fn copy_to_clipboard(app: AppHandle, text: String) -> Result<(), String> {
+pub async fn copy_to_clipboard(app: AppHandle, text: String) -> AppResult<()> {
     app.clipboard()
         .write_text(&text)
-        .map_err(|e| format!("Failed to copy to clipboard: {}", e))
+        .map_err(|e| AppError::clipboard("Failed to copy to clipboard", e))
 }
 
 /// Copy text to clipboard and close the editor window
 #[tauri::command]
-pub async fn paste_from_editor(app: AppHandle, text: String) -> Result<(), String> {
+pub async fn paste_from_editor(app: AppHandle, text: String) -> AppResult<()> {
     // 1. Copy text to clipboard
     app.clipboard()
         .write_text(&text)
-        .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
+        .map_err(|e| AppError::clipboard("Failed to write to clipboard", e))?;
 
     // Small delay to ensure clipboard write is processed
     std::thread::sleep(std::time::Duration::from_millis(50));
 
     // 2. Close the editor window
     if let Some(window) = app.get_webview_window("editor") {
-        window.close().map_err(|e| format!("Failed to close editor: {}", e))?;
+        window
+            .close()
+            .map_err(|e| AppError::window("Failed to close editor", e))?;
     }
 
     Ok(())
@@ -161,7 +210,7 @@ fn to_snake_case(s: &str) -> String {
 
 /// Copy prompt as a markdown file to clipboard and dismiss
 #[tauri::command]
-pub async fn copy_as_markdown_file(app: AppHandle, name: String, content: String) -> Result<(), String> {
+pub async fn copy_as_markdown_file(app: AppHandle, name: String, content: String) -> AppResult<()> {
     println!("[paste] Starting copy_as_markdown_file, name: {}", name);
 
     // 1. Create temp file with snake_case name
@@ -170,7 +219,9 @@ pub async fn copy_as_markdown_file(app: AppHandle, name: String, content: String
     let file_path = temp_dir.join(&filename);
 
     std::fs::write(&file_path, &content)
-        .map_err(|e| format!("Failed to write temp file: {}", e))?;
+        .map_err(|e| {
+            AppError::io("Failed to write temp file", e).with_context("path", file_path.display())
+        })?;
 
     println!("[paste] Created temp file: {:?}", file_path);
 
@@ -185,7 +236,7 @@ pub async fn copy_as_markdown_file(app: AppHandle, name: String, content: String
         // Fallback: just copy the content as text
         app.clipboard()
             .write_text(&content)
-            .map_err(|e| format!("Clipboard write failed: {}", e))?;
+            .map_err(|e| AppError::clipboard("Clipboard write failed", e))?;
     }
 
     // 3. Longer delay for file clipboard propagation (files take longer than text)
@@ -238,7 +289,7 @@ pub async fn copy_as_markdown_file(app: AppHandle, name: String, content: String
 }
 
 #[cfg(target_os = "macos")]
-fn copy_file_to_clipboard_macos(file_path: &std::path::Path) -> Result<(), String> {
+fn copy_file_to_clipboard_macos(file_path: &std::path::Path) -> AppResult<()> {
     use cocoa::base::{id, nil};
     use cocoa::foundation::{NSArray, NSString};
     use objc::{class, msg_send, sel, sel_impl};
@@ -247,7 +298,7 @@ fn copy_file_to_clipboard_macos(file_path: &std::path::Path) -> Result<(), Strin
         // Get NSPasteboard
         let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
         if pasteboard == nil {
-            return Err("Failed to get NSPasteboard".to_string());
+            return Err(AppError::clipboard("Failed to get NSPasteboard", "pasteboard unavailable"));
         }
 
         // Clear pasteboard
@@ -255,12 +306,15 @@ fn copy_file_to_clipboard_macos(file_path: &std::path::Path) -> Result<(), Strin
 
         // Create file URL
         let path_str = file_path.to_str()
-            .ok_or("Invalid file path")?;
+            .ok_or_else(|| {
+                AppError::invalid_input("Invalid file path").with_context("path", file_path.display())
+            })?;
         let ns_path: id = NSString::alloc(nil).init_str(path_str);
         let file_url: id = msg_send![class!(NSURL), fileURLWithPath: ns_path];
 
         if file_url == nil {
-            return Err("Failed to create file URL".to_string());
+            return Err(AppError::platform("Failed to create file URL")
+                .with_context("path", file_path.display()));
         }
 
         // Create array with the URL
@@ -270,7 +324,10 @@ fn copy_file_to_clipboard_macos(file_path: &std::path::Path) -> Result<(), Strin
         let success: bool = msg_send![pasteboard, writeObjects: objects];
 
         if !success {
-            return Err("Failed to write file URL to pasteboard".to_string());
+            return Err(AppError::clipboard(
+                "Failed to write file URL to pasteboard",
+                "writeObjects returned false",
+            ));
         }
 
         println!("[paste] File URL copied to clipboard: {:?}", file_path);
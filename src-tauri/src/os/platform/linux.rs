@@ -4,6 +4,7 @@
 //! ydotool or libei for Wayland.
 
 use super::{AppFocusTracker, AppId, InputSimulator};
+use crate::error::{AppError, AppResult};
 
 /// Linux app focus tracker (stub implementation).
 pub struct LinuxFocusTracker;
@@ -15,14 +16,14 @@ impl LinuxFocusTracker {
 }
 
 impl AppFocusTracker for LinuxFocusTracker {
-    fn capture_focused_app(&self) -> Result<Option<AppId>, String> {
+    fn capture_focused_app(&self) -> AppResult<Option<AppId>> {
         // TODO: For X11, use XGetInputFocus to get window ID
         // For Wayland, this is more complex (wlr-foreign-toplevel)
         println!("[platform:linux] capture_focused_app not implemented");
         Ok(None)
     }
 
-    fn activate_app(&self, _app_id: &AppId) -> Result<bool, String> {
+    fn activate_app(&self, _app_id: &AppId) -> AppResult<bool> {
         // TODO: For X11, use XSetInputFocus or _NET_ACTIVE_WINDOW
         // For Wayland, use wlr-foreign-toplevel-management
         println!("[platform:linux] activate_app not implemented");
@@ -40,10 +41,10 @@ impl LinuxInputSimulator {
 }
 
 impl InputSimulator for LinuxInputSimulator {
-    fn simulate_paste(&self) -> Result<(), String> {
+    fn simulate_paste(&self) -> AppResult<()> {
         // TODO: For X11, use XTest extension (fake_input)
         // For Wayland, use ydotool as fallback
         println!("[platform:linux] simulate_paste not implemented");
-        Err("Linux paste simulation not yet implemented".to_string())
+        Err(AppError::platform("Linux paste simulation not yet implemented"))
     }
 }
@@ -4,6 +4,7 @@
 //! This is faster and more reliable than AppleScript (~50ms vs ~200ms).
 
 use super::{AppFocusTracker, AppId, InputSimulator};
+use crate::error::{AppError, AppResult, ErrorCode};
 use cocoa::base::{id, nil};
 use cocoa::foundation::NSString;
 use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation, CGKeyCode};
@@ -58,12 +59,12 @@ impl MacOSFocusTracker {
 }
 
 impl AppFocusTracker for MacOSFocusTracker {
-    fn capture_focused_app(&self) -> Result<Option<AppId>, String> {
+    fn capture_focused_app(&self) -> AppResult<Option<AppId>> {
         unsafe {
             // Get NSWorkspace.sharedWorkspace
             let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
             if workspace == nil {
-                return Err("Failed to get NSWorkspace".to_string());
+                return Err(AppError::platform("Failed to get NSWorkspace"));
             }
 
             // Get the frontmost application
@@ -104,7 +105,7 @@ impl AppFocusTracker for MacOSFocusTracker {
         }
     }
 
-    fn activate_app(&self, app_id: &AppId) -> Result<bool, String> {
+    fn activate_app(&self, app_id: &AppId) -> AppResult<bool> {
         unsafe {
             // Try bundle ID first if available
             if let Some(ref bundle_id) = app_id.bundle_id {
@@ -176,7 +177,7 @@ impl MacOSInputSimulator {
 }
 
 impl InputSimulator for MacOSInputSimulator {
-    fn simulate_paste(&self) -> Result<(), String> {
+    fn simulate_paste(&self) -> AppResult<()> {
         println!("[platform:macos] Simulating Cmd+V with CGEvent");
 
         // Check accessibility permissions first
@@ -196,25 +197,29 @@ impl InputSimulator for MacOSInputSimulator {
 
             // Return error - paste will fail without permission
             // The content is already in the clipboard, so user can manually paste
-            return Err("Accessibility permission required for paste. Content copied to clipboard - use Cmd+V to paste manually.".to_string());
+            return Err(AppError::new(
+                ErrorCode::PermissionDenied,
+                "Accessibility permission required for paste. Content copied to clipboard - use Cmd+V to paste manually.",
+            )
+            .with_context("permission", "accessibility"));
         } else {
             println!("[platform:macos] Accessibility permission: GRANTED");
         }
 
         // Create event source
         let source = CGEventSource::new(CGEventSourceStateID::CombinedSessionState)
-            .map_err(|_| "Failed to create CGEventSource")?;
+            .map_err(|_| AppError::platform("Failed to create CGEventSource"))?;
 
         // Create key down event for 'V'
         let key_down = CGEvent::new_keyboard_event(source.clone(), VK_V, true)
-            .map_err(|_| "Failed to create key down event")?;
+            .map_err(|_| AppError::platform("Failed to create key down event"))?;
 
         // Set Command modifier flag
         key_down.set_flags(CGEventFlags::CGEventFlagCommand);
 
         // Create key up event for 'V'
         let key_up = CGEvent::new_keyboard_event(source, VK_V, false)
-            .map_err(|_| "Failed to create key up event")?;
+            .map_err(|_| AppError::platform("Failed to create key up event"))?;
 
         // Set Command modifier flag on key up too
         key_up.set_flags(CGEventFlags::CGEventFlagCommand);
@@ -3,6 +3,8 @@
 //! This module provides traits that abstract platform-specific operations,
 //! allowing the same high-level code to work across macOS, Windows, and Linux.
 
+use crate::error::AppResult;
+
 #[cfg(target_os = "macos")]
 pub mod macos;
 
@@ -51,6 +53,31 @@ impl AppId {
     pub fn as_str(&self) -> &str {
         self.bundle_id.as_deref().unwrap_or("")
     }
+
+    /// Stable name for the app across launches: the bundle ID, or the
+    /// process name when only a PID is known. Used to key usage history.
+    pub fn context_key(&self) -> Option<String> {
+        self.bundle_id
+            .clone()
+            .or_else(|| self.pid.and_then(process_name))
+    }
+}
+
+/// Best-effort executable name for a running process
+fn process_name(pid: i32) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        std::fs::read_to_string(format!("/proc/{}/comm", pid))
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = pid;
+        None
+    }
 }
 
 /// Trait for tracking and restoring application focus.
@@ -60,11 +87,11 @@ impl AppId {
 pub trait AppFocusTracker: Send + Sync {
     /// Capture the currently focused application.
     /// Returns the app identifier, or None if no app is focused.
-    fn capture_focused_app(&self) -> Result<Option<AppId>, String>;
+    fn capture_focused_app(&self) -> AppResult<Option<AppId>>;
 
     /// Activate (bring to front) a previously captured application.
     /// Returns Ok(true) if successful, Ok(false) if app not found.
-    fn activate_app(&self, app_id: &AppId) -> Result<bool, String>;
+    fn activate_app(&self, app_id: &AppId) -> AppResult<bool>;
 }
 
 /// Trait for simulating keyboard input.
@@ -73,7 +100,7 @@ pub trait AppFocusTracker: Send + Sync {
 /// allowing us to simulate paste (Cmd+V / Ctrl+V) and other shortcuts.
 pub trait InputSimulator: Send + Sync {
     /// Simulate a paste keystroke (Cmd+V on macOS, Ctrl+V on Windows/Linux).
-    fn simulate_paste(&self) -> Result<(), String>;
+    fn simulate_paste(&self) -> AppResult<()>;
 }
 
 /// Create the platform-specific app focus tracker.
@@ -4,6 +4,7 @@
 //! GetForegroundWindow/SetForegroundWindow for app focus.
 
 use super::{AppFocusTracker, AppId, InputSimulator};
+use crate::error::{AppError, AppResult};
 
 /// Windows app focus tracker (stub implementation).
 pub struct WindowsFocusTracker;
@@ -15,14 +16,14 @@ impl WindowsFocusTracker {
 }
 
 impl AppFocusTracker for WindowsFocusTracker {
-    fn capture_focused_app(&self) -> Result<Option<AppId>, String> {
+    fn capture_focused_app(&self) -> AppResult<Option<AppId>> {
         // TODO: Use GetForegroundWindow to get HWND
         // Store HWND as hex string in AppId
         println!("[platform:windows] capture_focused_app not implemented");
         Ok(None)
     }
 
-    fn activate_app(&self, _app_id: &AppId) -> Result<bool, String> {
+    fn activate_app(&self, _app_id: &AppId) -> AppResult<bool> {
         // TODO: Use SetForegroundWindow with stored HWND
         println!("[platform:windows] activate_app not implemented");
         Ok(false)
@@ -39,10 +40,10 @@ impl WindowsInputSimulator {
 }
 
 impl InputSimulator for WindowsInputSimulator {
-    fn simulate_paste(&self) -> Result<(), String> {
+    fn simulate_paste(&self) -> AppResult<()> {
         // TODO: Use SendInput to send Ctrl+V
         // VK_CONTROL = 0x11, VK_V = 0x56
         println!("[platform:windows] simulate_paste not implemented");
-        Err("Windows paste simulation not yet implemented".to_string())
+        Err(AppError::platform("Windows paste simulation not yet implemented"))
     }
 }
@@ -9,6 +9,7 @@
 use once_cell::sync::Lazy;
 use std::sync::Mutex;
 
+use crate::error::AppResult;
 use crate::os::platform::{self, AppId};
 
 /// Thread-safe storage for the previously-focused application
@@ -16,7 +17,7 @@ static PREVIOUS_APP: Lazy<Mutex<Option<AppId>>> = Lazy::new(|| Mutex::new(None))
 
 /// Capture the frontmost app BEFORE showing Promptlight.
 /// This must be called before the launcher window is shown.
-pub fn capture_previous_app() -> Result<(), String> {
+pub fn capture_previous_app() -> AppResult<()> {
     let tracker = platform::create_focus_tracker();
 
     match tracker.capture_focused_app() {
@@ -49,6 +50,11 @@ pub fn get_previous_app() -> Option<AppId> {
     PREVIOUS_APP.lock().ok().and_then(|g| g.clone())
 }
 
+/// Get the stable key (bundle ID or process name) of the stored previous app
+pub fn get_previous_app_key() -> Option<String> {
+    get_previous_app().and_then(|app| app.context_key())
+}
+
 /// Clear the stored previous app
 pub fn clear_previous_app() {
     if let Ok(mut guard) = PREVIOUS_APP.lock() {
@@ -58,7 +64,7 @@ pub fn clear_previous_app() {
 
 /// Activate the previously captured app using native platform APIs.
 /// Returns Ok(true) if successful, Ok(false) if no previous app was stored.
-pub fn activate_previous_app() -> Result<bool, String> {
+pub fn activate_previous_app() -> AppResult<bool> {
     let app_id = match get_previous_app() {
         Some(id) => id,
         None => {
@@ -81,7 +87,7 @@ pub fn activate_previous_app() -> Result<bool, String> {
 
 /// Simulate Cmd+V (macOS) or Ctrl+V (Windows/Linux) paste keystroke.
 /// Requires Accessibility permission on macOS.
-pub fn simulate_paste() -> Result<(), String> {
+pub fn simulate_paste() -> AppResult<()> {
     println!("[previous_app] Simulating paste keystroke");
 
     let simulator = platform::create_input_simulator();
@@ -1,6 +1,7 @@
 use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
 
 use crate::data::settings::AppSettings;
+use crate::error::{AppError, AppResult};
 use crate::os::focus::get_key_window_screen_bounds;
 
 const WELCOME_WIDTH: f64 = 900.0;
@@ -21,13 +22,17 @@ pub fn is_welcome_visible(app: &AppHandle) -> bool {
 
 /// Open the welcome screen window
 #[tauri::command]
-pub async fn open_welcome_window(app: AppHandle) -> Result<(), String> {
+pub async fn open_welcome_window(app: AppHandle) -> AppResult<()> {
     let label = "welcome";
 
     // If window already exists, just show and focus it
     if let Some(window) = app.get_webview_window(label) {
-        window.show().map_err(|e| e.to_string())?;
-        window.set_focus().map_err(|e| e.to_string())?;
+        window
+            .show()
+            .map_err(|e| AppError::window("Failed to show welcome window", e))?;
+        window
+            .set_focus()
+            .map_err(|e| AppError::window("Failed to focus welcome window", e))?;
         return Ok(());
     }
 
@@ -58,7 +63,9 @@ pub async fn open_welcome_window(app: AppHandle) -> Result<(), String> {
         builder = builder.minimizable(false).maximizable(false);
     }
 
-    let window = builder.build().map_err(|e| e.to_string())?;
+    let window = builder
+        .build()
+        .map_err(|e| AppError::window("Failed to create welcome window", e))?;
 
     // Apply transparent background (required for rounded corners on macOS)
     #[cfg(target_os = "macos")]
@@ -91,7 +98,7 @@ pub async fn open_welcome_window(app: AppHandle) -> Result<(), String> {
 
 /// Close the welcome window and optionally save the "don't show again" preference
 #[tauri::command]
-pub fn close_welcome_window(app: AppHandle, dont_show_again: bool) -> Result<(), String> {
+pub fn close_welcome_window(app: AppHandle, dont_show_again: bool) -> AppResult<()> {
     // Save preference if "Don't show again" was checked
     if dont_show_again {
         let mut settings = AppSettings::load();
@@ -101,13 +108,19 @@ pub fn close_welcome_window(app: AppHandle, dont_show_again: bool) -> Result<(),
 
     // Close the window
     if let Some(window) = app.get_webview_window("welcome") {
-        window.close().map_err(|e| e.to_string())?;
+        window
+            .close()
+            .map_err(|e| AppError::window("Failed to close welcome window", e))?;
     }
 
     // Show the launcher window
     if let Some(launcher) = app.get_webview_window("launcher") {
-        launcher.show().map_err(|e| e.to_string())?;
-        launcher.set_focus().map_err(|e| e.to_string())?;
+        launcher
+            .show()
+            .map_err(|e| AppError::window("Failed to show launcher", e))?;
+        launcher
+            .set_focus()
+            .map_err(|e| AppError::window("Failed to focus launcher", e))?;
     }
 
     Ok(())
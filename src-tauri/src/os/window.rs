@@ -2,6 +2,7 @@ use serde::Deserialize;
 use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
 
 use crate::data::settings::AppSettings;
+use crate::error::{AppError, AppResult};
 
 /// Screen bounds passed from frontend for window positioning
 #[derive(Debug, Clone, Deserialize)]
@@ -14,7 +15,8 @@ pub struct ScreenBounds {
 
 /// Open the editor window, optionally loading a specific prompt
 /// If screen_bounds is provided, the window will be centered on that screen
-/// view parameter can be "prompts" (default) or "settings"
+/// view parameter can be "prompts" (default), "settings", or "import" (the
+/// prompt list, asking for files to import)
 /// always_on_top parameter overrides the setting (used when opening from launcher)
 #[tauri::command]
 pub async fn open_editor_window(
@@ -23,7 +25,7 @@ pub async fn open_editor_window(
     screen_bounds: Option<ScreenBounds>,
     view: Option<String>,
     always_on_top: Option<bool>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let label = "editor";
     let view_mode = view.as_deref().unwrap_or("prompts");
 
@@ -34,19 +36,35 @@ pub async fn open_editor_window(
     // If window already exists, show it and optionally emit event to load prompt or switch view
     if let Some(window) = app.get_webview_window(label) {
         // Update always-on-top state in case setting changed
-        window.set_always_on_top(is_always_on_top).map_err(|e| e.to_string())?;
+        window
+            .set_always_on_top(is_always_on_top)
+            .map_err(|e| AppError::window("Failed to set always-on-top", e))?;
 
-        window.show().map_err(|e| e.to_string())?;
-        window.set_focus().map_err(|e| e.to_string())?;
+        window
+            .show()
+            .map_err(|e| AppError::window("Failed to show editor", e))?;
+        window
+            .set_focus()
+            .map_err(|e| AppError::window("Failed to focus editor", e))?;
 
         // Emit event to load specific prompt if provided
         if let Some(id) = prompt_id {
-            window.emit("load-prompt", id).map_err(|e| e.to_string())?;
+            window
+                .emit("load-prompt", id)
+                .map_err(|e| AppError::window("Failed to load prompt in editor", e))?;
         }
 
         // Emit event to switch view if settings requested
         if view_mode == "settings" {
-            window.emit("switch-view", "settings").map_err(|e| e.to_string())?;
+            window
+                .emit("switch-view", "settings")
+                .map_err(|e| AppError::window("Failed to switch editor view", e))?;
+        }
+
+        if view_mode == "import" {
+            window
+                .emit("import-prompts", ())
+                .map_err(|e| AppError::window("Failed to start import in editor", e))?;
         }
         return Ok(());
     }
@@ -57,9 +75,10 @@ pub async fn open_editor_window(
         None => "index.html?window=editor".to_string(),
     };
 
-    // Add view parameter if settings
-    if view_mode == "settings" {
-        url.push_str("&view=settings");
+    // Add view parameter if settings or import
+    if view_mode == "settings" || view_mode == "import" {
+        url.push_str("&view=");
+        url.push_str(view_mode);
     }
 
     let window_width = 1000.0;
@@ -89,16 +108,20 @@ pub async fn open_editor_window(
         builder = builder.center();
     }
 
-    builder.build().map_err(|e| e.to_string())?;
+    builder
+        .build()
+        .map_err(|e| AppError::window("Failed to create editor window", e))?;
 
     Ok(())
 }
 
 /// Close the editor window if it exists
 #[tauri::command]
-pub fn close_editor_window(app: AppHandle) -> Result<(), String> {
+pub fn close_editor_window(app: AppHandle) -> AppResult<()> {
     if let Some(window) = app.get_webview_window("editor") {
-        window.close().map_err(|e| e.to_string())?;
+        window
+            .close()
+            .map_err(|e| AppError::window("Failed to close editor", e))?;
     }
     Ok(())
 }
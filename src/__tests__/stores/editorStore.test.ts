@@ -148,6 +148,19 @@ describe('editorStore', () => {
       expect(state.isSaving).toBe(false);
     });
 
+    it('should save tags and the pin', async () => {
+      getMockBackend().seedData([createTestPrompt()]);
+      await useEditorStore.getState().loadPrompt('1');
+
+      useEditorStore.getState().updateField('tags', ['review', 'email']);
+      useEditorStore.getState().updateField('pinned', true);
+      await useEditorStore.getState().save();
+
+      const saved = await getMockBackend().getPrompt('1');
+      expect(saved.tags).toEqual(['review', 'email']);
+      expect(saved.pinned).toBe(true);
+    });
+
     it('should reject save without name', async () => {
       useEditorStore.getState().createNew();
 
@@ -228,6 +241,56 @@ describe('editorStore', () => {
     });
   });
 
+  describe('smart folders', () => {
+    it('should list a saved search with the prompts matching it', async () => {
+      getMockBackend().seedData([
+        createTestPrompt({ id: '1', name: 'Code Review' }),
+        createTestPrompt({ id: '2', name: 'Draft Email', description: '', content: '' }),
+      ]);
+
+      expect(await useEditorStore.getState().saveSmartFolder({ name: 'Reviews', query: 'review' })).toBe(true);
+      expect(useEditorStore.getState().smartFolders).toEqual([
+        { name: 'Reviews', query: 'review', promptIds: ['1'] },
+      ]);
+
+      // Names are unique across folders
+      expect(await useEditorStore.getState().saveSmartFolder({ name: 'test', query: 'email' })).toBe(false);
+
+      await useEditorStore.getState().deleteSmartFolder('Reviews');
+      expect(useEditorStore.getState().smartFolders).toEqual([]);
+    });
+  });
+
+  describe('importFiles', () => {
+    it('should save each file as a prompt named after it', async () => {
+      const imported = await useEditorStore.getState().importFiles([
+        { name: 'code-review.md', content: 'Review this code' },
+        { name: '.md', content: 'No name' },
+      ]);
+
+      expect(imported).toBe(1);
+      const { prompts } = useEditorStore.getState();
+      expect(prompts.map((p) => p.name)).toEqual(['code-review']);
+      expect((await getMockBackend().getPrompt(prompts[0].id)).content).toBe('Review this code');
+    });
+  });
+
+  describe('requestImport', () => {
+    it('should show the prompt list until the request is handled', () => {
+      useEditorStore.setState({ currentView: 'settings', sidebarCollapsed: true });
+
+      useEditorStore.getState().requestImport();
+      expect(useEditorStore.getState()).toMatchObject({
+        currentView: 'prompts',
+        sidebarCollapsed: false,
+        importRequested: true,
+      });
+
+      useEditorStore.getState().clearImportRequest();
+      expect(useEditorStore.getState().importRequested).toBe(false);
+    });
+  });
+
   describe('reset', () => {
     it('should reset store to initial state', () => {
       useEditorStore.setState({
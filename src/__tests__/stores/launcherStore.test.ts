@@ -1,10 +1,11 @@
 import { describe, it, expect, beforeEach } from 'vitest';
-import { useLauncherStore } from '../../stores/launcherStore';
-import type { SearchResult, PromptMetadata } from '../../types';
+import { useLauncherStore, launcherResultKey } from '../../stores/launcherStore';
+import type { LauncherResult, PromptMetadata, TemplateVariable } from '../../types';
 
 // Helper to create mock search results
-function createMockResult(id: string, name: string): SearchResult {
+function createMockResult(id: string, name: string): LauncherResult {
   return {
+    kind: 'prompt',
     prompt: {
       id,
       name,
@@ -58,10 +59,26 @@ describe('launcherStore', () => {
 
       const state = useLauncherStore.getState();
       expect(state.results).toHaveLength(2);
+      expect(state.totalResults).toBe(2);
       expect(state.selectedIndex).toBe(0);
     });
   });
 
+  describe('appendResults', () => {
+    it('should append a page and keep the selection', () => {
+      const first = [createMockResult('1', 'Test 1'), createMockResult('2', 'Test 2')];
+      useLauncherStore.getState().setResults(first, 3);
+      useLauncherStore.setState({ selectedIndex: 1 });
+
+      useLauncherStore.getState().appendResults([createMockResult('2', 'Test 2'), createMockResult('3', 'Test 3')]);
+
+      const state = useLauncherStore.getState();
+      expect(state.results.map(launcherResultKey)).toEqual(['prompt:1', 'prompt:2', 'prompt:3']);
+      expect(state.totalResults).toBe(3);
+      expect(state.selectedIndex).toBe(1);
+    });
+  });
+
   describe('selectNext', () => {
     it('should increment selected index', () => {
       const results = [
@@ -140,6 +157,18 @@ describe('launcherStore', () => {
       expect(useLauncherStore.getState().mode).toBe('search');
       expect(useLauncherStore.getState().promotedPrompt).toBeNull();
     });
+
+    it('should not promote folders or actions', () => {
+      const results: LauncherResult[] = [
+        { kind: 'folder', name: 'coding', promptCount: 2, score: 1.0 },
+        { kind: 'action', action: 'newPrompt', title: 'New Prompt', score: 1.0 },
+      ];
+      for (const selectedIndex of [0, 1]) {
+        useLauncherStore.setState({ results, selectedIndex });
+        useLauncherStore.getState().promoteSelected();
+        expect(useLauncherStore.getState().mode).toBe('search');
+      }
+    });
   });
 
   describe('unpromote', () => {
@@ -178,6 +207,53 @@ describe('launcherStore', () => {
     });
   });
 
+  describe('startFill', () => {
+    const variables: TemplateVariable[] = [
+      { name: 'topic', type: 'text', required: true, ranges: [] },
+      { name: 'tone', type: 'choice', options: ['formal', 'casual'], default: 'casual', required: false, ranges: [] },
+    ];
+
+    it('should prefill values with defaults', () => {
+      useLauncherStore.getState().startFill({ id: '1', name: 'Test' }, variables, 'extra');
+
+      const state = useLauncherStore.getState();
+      expect(state.mode).toBe('fill');
+      expect(state.fillPrompt).toEqual({ id: '1', name: 'Test' });
+      expect(state.values).toEqual({ topic: '', tone: 'casual' });
+      expect(state.riderText).toBe('extra');
+    });
+
+    it('should prefill the last value entered over the default', () => {
+      useLauncherStore.getState().startFill({ id: '1', name: 'Test' }, [
+        { ...variables[0], recent: ['Rust', 'Go'] },
+        { ...variables[1], recent: ['formal'] },
+      ]);
+
+      expect(useLauncherStore.getState().values).toEqual({ topic: 'Rust', tone: 'formal' });
+    });
+
+    it('should clear the error when a value changes', () => {
+      useLauncherStore.getState().startFill({ id: '1', name: 'Test' }, variables);
+      useLauncherStore.getState().setFillError('Missing values for template variables: topic');
+      useLauncherStore.getState().setValue('topic', 'Rust');
+
+      const state = useLauncherStore.getState();
+      expect(state.values.topic).toBe('Rust');
+      expect(state.fillError).toBeNull();
+    });
+
+    it('should return to search mode when cancelled', () => {
+      useLauncherStore.getState().startFill({ id: '1', name: 'Test' }, variables);
+      useLauncherStore.getState().cancelFill();
+
+      const state = useLauncherStore.getState();
+      expect(state.mode).toBe('search');
+      expect(state.fillPrompt).toBeNull();
+      expect(state.variables).toEqual([]);
+      expect(state.values).toEqual({});
+    });
+  });
+
   describe('setLoading', () => {
     it('should set loading state', () => {
       useLauncherStore.getState().setLoading(true);
@@ -102,6 +102,17 @@ describe('settingsStore', () => {
     });
   });
 
+  describe('updateVariableSettings', () => {
+    it('should update variable settings and save them', async () => {
+      await useSettingsStore.getState().updateVariableSettings({ sensitive: ['password', 'pin'] });
+
+      const state = useSettingsStore.getState();
+      expect(state.settings.variables.sensitive).toEqual(['password', 'pin']);
+      expect((await getMockBackend().getSettings()).variables.sensitive).toEqual(['password', 'pin']);
+      expect(state.isSaving).toBe(false);
+    });
+  });
+
   describe('updateSyncSettings', () => {
     it('should update sync settings', async () => {
       await useSettingsStore.getState().updateSyncSettings({ lastSync: '2024-01-15' });
@@ -0,0 +1,58 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import { useLauncherStore } from '../../stores/launcherStore';
+import { pasteFilledPrompt, pasteFromLauncher } from '../../utils/paste';
+import { getMockBackend } from '../setup';
+import type { Prompt } from '../../types';
+
+const createTestPrompt = (overrides: Partial<Prompt> = {}): Prompt => ({
+  id: '1',
+  name: 'Test Prompt',
+  folder: 'test',
+  description: '',
+  filename: 'test.md',
+  useCount: 0,
+  lastUsed: null,
+  created: '2024-01-01',
+  updated: '2024-01-01',
+  content: 'Write about {{topic}}',
+  ...overrides,
+});
+
+/** Rows of the exported usage log, without the header */
+async function usageRows(): Promise<string[]> {
+  const csv = await getMockBackend().exportUsageCsv();
+  return csv.trim().split('\n').slice(1);
+}
+
+describe('paste', () => {
+  beforeEach(() => {
+    useLauncherStore.getState().reset();
+  });
+
+  it('records the use against the app the launcher was opened over', async () => {
+    getMockBackend().seedData([createTestPrompt()]);
+    getMockBackend().setPreviousApp('com.apple.Notes');
+
+    await pasteFromLauncher({ id: '1', name: 'Test Prompt' });
+    expect(useLauncherStore.getState().mode).toBe('fill');
+
+    useLauncherStore.getState().setValue('topic', 'Rust');
+    await pasteFilledPrompt();
+
+    const rows = await usageRows();
+    expect(rows).toHaveLength(1);
+    expect(rows[0]).toMatch(/,1,Test Prompt,test,paste,com\.apple\.Notes$/);
+    expect(getMockBackend().actionHistory).toContainEqual({ type: 'paste', text: 'Write about Rust' });
+  });
+
+  it('does not record a use when the values are rejected', async () => {
+    getMockBackend().seedData([createTestPrompt({ content: 'Count to {{count:number}}' })]);
+
+    await pasteFromLauncher({ id: '1', name: 'Test Prompt' });
+    useLauncherStore.getState().setValue('count', 'lots');
+    await pasteFilledPrompt();
+
+    expect(useLauncherStore.getState().fillError).toMatch(/count/);
+    expect(await usageRows()).toEqual([]);
+  });
+});
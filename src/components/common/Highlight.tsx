@@ -0,0 +1,34 @@
+import type { MatchRange } from '../../types';
+import styles from './Highlight.module.css';
+
+interface HighlightProps {
+  text: string;
+  /** Matched ranges in UTF-16 offsets, sorted and non-overlapping */
+  ranges?: MatchRange[];
+}
+
+/** Render text with the matched ranges highlighted */
+export function Highlight({ text, ranges }: HighlightProps) {
+  if (!ranges || ranges.length === 0) {
+    return <>{text}</>;
+  }
+
+  const parts: React.ReactNode[] = [];
+  let cursor = 0;
+  for (const { start, end } of ranges) {
+    if (start > cursor) {
+      parts.push(text.slice(cursor, start));
+    }
+    parts.push(
+      <mark key={start} className={styles.match}>
+        {text.slice(start, end)}
+      </mark>
+    );
+    cursor = end;
+  }
+  if (cursor < text.length) {
+    parts.push(text.slice(cursor));
+  }
+
+  return <>{parts}</>;
+}
@@ -1,6 +1,7 @@
 import { useEffect } from 'react';
 import { listen } from '@tauri-apps/api/event';
 import { useEditorStore, EditorView } from '../../stores/editorStore';
+import { useAuthStore } from '../../stores/authStore';
 import { ModeToggle } from './ModeToggle';
 import { Sidebar } from './Sidebar/Sidebar';
 import { PromptEditor } from './PromptEditor/PromptEditor';
@@ -10,7 +11,8 @@ import { useEditorKeyboard } from '../../hooks/useEditorKeyboard';
 import styles from './EditorWindow.module.css';
 
 export function EditorWindow() {
-  const { currentView, loadPrompts, loadPrompt, createNew, setSidebarCollapsed, setView } = useEditorStore();
+  const { currentView, loadPrompts, loadPrompt, createNew, setSidebarCollapsed, setView, requestImport } =
+    useEditorStore();
 
   // Initialize auto-save and keyboard shortcuts
   useAutoSave();
@@ -23,19 +25,21 @@ export function EditorWindow() {
     // Check URL parameters
     const params = new URLSearchParams(window.location.search);
     const promptId = params.get('id');
-    const viewParam = params.get('view') as EditorView | null;
+    const viewParam = params.get('view');
 
     // Set initial view from URL param
     if (viewParam === 'settings') {
       setView('settings');
+    } else if (viewParam === 'import') {
+      requestImport();
     }
 
     if (promptId) {
       // Editing specific prompt - collapse sidebar
       loadPrompt(promptId);
       setSidebarCollapsed(true);
-    } else if (viewParam !== 'settings') {
-      // New prompt (only if not opening settings)
+    } else if (!viewParam) {
+      // New prompt (only if not opening settings or import)
       createNew();
     }
 
@@ -49,11 +53,30 @@ export function EditorWindow() {
       setView(event.payload);
     });
 
+    // Listen for new-prompt requests (e.g. `promptlight --new-prompt`)
+    const unlistenNew = listen('new-prompt', () => {
+      setView('prompts');
+      createNew();
+    });
+
+    // Listen for import requests (the launcher's Import… action)
+    const unlistenImport = listen('import-prompts', () => {
+      requestImport();
+    });
+
+    // Signing out from the launcher
+    const unlistenSignedOut = listen('signed-out', () => {
+      useAuthStore.getState().checkAuth();
+    });
+
     return () => {
       unlistenPrompt.then((fn) => fn());
       unlistenView.then((fn) => fn());
+      unlistenNew.then((fn) => fn());
+      unlistenImport.then((fn) => fn());
+      unlistenSignedOut.then((fn) => fn());
     };
-  }, [loadPrompts, loadPrompt, createNew, setSidebarCollapsed, setView]);
+  }, [loadPrompts, loadPrompt, createNew, setSidebarCollapsed, setView, requestImport]);
 
   return (
     <div className={styles.container} data-testid="editor-window">
@@ -1,6 +1,6 @@
 import { useState, useCallback, useMemo } from 'react';
 import { backend } from '../../../services/backend';
-import { Copy, ClipboardPaste, Trash2, Check, X } from 'lucide-react';
+import { Copy, ClipboardPaste, Trash2, Check, X, Pin, PinOff } from 'lucide-react';
 import { useEditorStore } from '../../../stores/editorStore';
 import { useDerivedFolders } from '../../../hooks/useDerivedFolders';
 import { IconColorPicker } from '../../common/IconColorPicker';
@@ -78,7 +78,7 @@ export function EditorToolbar() {
 
     try {
       if (editedPrompt.id) {
-        await backend.recordUsage(editedPrompt.id);
+        await backend.recordUsage(editedPrompt.id, 'editorPaste');
       }
       await backend.pasteFromEditor(editedPrompt.content);
     } catch (error) {
@@ -170,6 +170,15 @@ export function EditorToolbar() {
       </div>
 
       <div className={styles.right}>
+        <IconButton
+          icon={editedPrompt.pinned ? PinOff : Pin}
+          onClick={() => updateField('pinned', !editedPrompt.pinned)}
+          className={editedPrompt.pinned ? styles.pinned : undefined}
+          aria-pressed={Boolean(editedPrompt.pinned)}
+          title={editedPrompt.pinned ? 'Unpin prompt' : 'Pin prompt to the top'}
+          data-testid="pin-button"
+        />
+
         {hasContent && (
           <div className={styles.actionGroup}>
             <IconButton
@@ -0,0 +1,51 @@
+import { useEffect, useState } from 'react';
+import { Link2 } from 'lucide-react';
+import { backend } from '../../../services/backend';
+import { useEditorStore } from '../../../stores/editorStore';
+import type { Includer } from '../../../types';
+import styles from './IncludedBy.module.css';
+
+/**
+ * The prompts that include the selected one (`{{> folder/name}}`), so the
+ * reach of an edit to a shared fragment is visible before making it
+ */
+export function IncludedBy() {
+  const { selectedPromptId, prompts, loadPrompt } = useEditorStore();
+  const [includers, setIncluders] = useState<Includer[]>([]);
+
+  // Reload when the prompts change too, since a save can add or drop an include
+  useEffect(() => {
+    if (!selectedPromptId) {
+      setIncluders([]);
+      return;
+    }
+    let cancelled = false;
+    backend
+      .getIncludedBy(selectedPromptId)
+      .then((found) => !cancelled && setIncluders(found))
+      .catch((error) => console.error('Failed to find includers:', error));
+    return () => {
+      cancelled = true;
+    };
+  }, [selectedPromptId, prompts]);
+
+  if (includers.length === 0) return null;
+
+  return (
+    <div className={styles.includedBy} data-testid="included-by">
+      <Link2 size={12} className={styles.icon} />
+      <span>Included in</span>
+      {includers.map(({ prompt, direct }) => (
+        <button
+          key={prompt.id}
+          type="button"
+          className={direct ? styles.includer : `${styles.includer} ${styles.indirect}`}
+          onClick={() => loadPrompt(prompt.id)}
+          title={`${prompt.folder}/${prompt.name}${direct ? '' : ' (through another included prompt)'}`}
+        >
+          {prompt.name}
+        </button>
+      ))}
+    </div>
+  );
+}
@@ -1,6 +1,7 @@
 import { useEditorStore } from '../../../stores/editorStore';
 import { InlineEdit } from '../../common/InlineEdit';
 import { MarkdownEditor } from './MarkdownEditor';
+import { IncludedBy } from './IncludedBy';
 import styles from './PromptForm.module.css';
 
 export function PromptForm() {
@@ -29,6 +30,49 @@ export function PromptForm() {
           variant="body"
           data-testid="prompt-description"
         />
+        <div className={styles.shortcuts}>
+          <InlineEdit
+            value={editedPrompt.keyword ?? ''}
+            onChange={(value) => updateField('keyword', value.trim() || undefined)}
+            placeholder="Keyword, e.g. ;rev"
+            variant="body"
+            className={styles.keyword}
+            data-testid="prompt-keyword"
+          />
+          <InlineEdit
+            value={(editedPrompt.aliases ?? []).join(', ')}
+            onChange={(value) =>
+              updateField(
+                'aliases',
+                value
+                  .split(',')
+                  .map((alias) => alias.trim())
+                  .filter(Boolean)
+              )
+            }
+            placeholder="Aliases, comma separated"
+            variant="body"
+            className={styles.aliases}
+            data-testid="prompt-aliases"
+          />
+          <InlineEdit
+            value={(editedPrompt.tags ?? []).join(', ')}
+            onChange={(value) =>
+              updateField(
+                'tags',
+                value
+                  .split(',')
+                  .map((tag) => tag.trim())
+                  .filter(Boolean)
+              )
+            }
+            placeholder="Tags, comma separated"
+            variant="body"
+            className={styles.tags}
+            data-testid="prompt-tags"
+          />
+        </div>
+        <IncludedBy />
       </div>
 
       <div className={styles.contentField} data-testid="prompt-content">
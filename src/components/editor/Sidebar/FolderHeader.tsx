@@ -1,4 +1,4 @@
-import { ChevronRight, ChevronDown, Folder } from 'lucide-react';
+import { ChevronRight, ChevronDown, Folder, Sparkles } from 'lucide-react';
 import styles from './FolderHeader.module.css';
 
 interface FolderHeaderProps {
@@ -9,6 +9,8 @@ interface FolderHeaderProps {
   isFiltering: boolean;
   onToggle: () => void;
   onContextMenu?: (e: React.MouseEvent) => void;
+  /** A smart folder (saved search) rather than a real one */
+  isSmart?: boolean;
 }
 
 export function FolderHeader({
@@ -19,6 +21,7 @@ export function FolderHeader({
   isFiltering,
   onToggle,
   onContextMenu,
+  isSmart = false,
 }: FolderHeaderProps) {
   const countDisplay = isFiltering
     ? `${filteredCount}/${totalCount}`
@@ -39,7 +42,7 @@ export function FolderHeader({
         {isCollapsed ? <ChevronRight size={14} /> : <ChevronDown size={14} />}
       </span>
       <span className={styles.folderIcon}>
-        <Folder size={14} />
+        {isSmart ? <Sparkles size={14} /> : <Folder size={14} />}
       </span>
       <span className={styles.name}>{name}</span>
       <span className={`${styles.count} ${isEmpty ? styles.empty : ''}`}>
@@ -15,6 +15,7 @@ interface FolderSectionProps {
   selectedPromptId: string | null;
   onSelectPrompt: (id: string) => void;
   onFolderContextMenu?: (e: React.MouseEvent) => void;
+  isSmart?: boolean;
 }
 
 export function FolderSection({
@@ -28,6 +29,7 @@ export function FolderSection({
   selectedPromptId,
   onSelectPrompt,
   onFolderContextMenu,
+  isSmart,
 }: FolderSectionProps) {
   return (
     <div className={styles.section}>
@@ -39,6 +41,7 @@ export function FolderSection({
         isFiltering={isFiltering}
         onToggle={onToggleCollapse}
         onContextMenu={onFolderContextMenu}
+        isSmart={isSmart}
       />
       <div
         className={`${styles.content} ${isCollapsed ? styles.collapsed : ''}`}
@@ -0,0 +1,116 @@
+import { useEffect, useRef, useState, useCallback } from 'react';
+import { Search, Sparkles, X } from 'lucide-react';
+import { useEditorStore } from '../../../stores/editorStore';
+import { SIDEBAR_CONFIG } from '../../../config/constants';
+import type { SmartFolder } from '../../../types';
+import styles from './NewFolderInput.module.css';
+
+interface NewSmartFolderInputProps {
+  /** The smart folder being edited; null to create one */
+  editing: SmartFolder | null;
+  onClose: () => void;
+}
+
+/**
+ * Inline form for a smart folder: a name and the search it saves
+ */
+export function NewSmartFolderInput({ editing, onClose }: NewSmartFolderInputProps) {
+  const { folders, smartFolders, saveSmartFolder } = useEditorStore();
+  const [name, setName] = useState(editing?.name ?? '');
+  const [query, setQuery] = useState(editing?.query ?? '');
+  const [saveError, setSaveError] = useState<string | null>(null);
+
+  const nameRef = useRef<HTMLInputElement>(null);
+
+  // Auto-focus on mount
+  useEffect(() => {
+    nameRef.current?.focus();
+  }, []);
+
+  const validate = useCallback(() => {
+    const trimmed = name.trim().toLowerCase();
+    if (trimmed.length < SIDEBAR_CONFIG.MIN_FOLDER_NAME_LENGTH) {
+      return 'Folder name is required';
+    }
+    if (trimmed.length > SIDEBAR_CONFIG.MAX_FOLDER_NAME_LENGTH) {
+      return `Maximum ${SIDEBAR_CONFIG.MAX_FOLDER_NAME_LENGTH} characters`;
+    }
+    const taken =
+      folders.includes(trimmed) ||
+      smartFolders.some((f) => f !== editing && f.name.toLowerCase() === trimmed);
+    if (taken) {
+      return 'Folder already exists';
+    }
+    if (!query.trim()) {
+      return 'Search is required';
+    }
+    return null;
+  }, [name, query, folders, smartFolders, editing]);
+
+  const handleSubmit = useCallback(async () => {
+    if (validate()) {
+      return;
+    }
+    const folder: SmartFolder = { ...editing, name: name.trim(), query: query.trim() };
+    delete folder.promptIds;
+    if (await saveSmartFolder(folder, editing?.name)) {
+      onClose();
+    } else {
+      setSaveError(useEditorStore.getState().error);
+    }
+  }, [validate, editing, name, query, saveSmartFolder, onClose]);
+
+  const handleKeyDown = useCallback(
+    (e: React.KeyboardEvent) => {
+      if (e.key === 'Enter') {
+        e.preventDefault();
+        handleSubmit();
+      } else if (e.key === 'Escape') {
+        e.preventDefault();
+        onClose();
+      }
+    },
+    [handleSubmit, onClose]
+  );
+
+  const validationError = validate();
+  const showError = (name.length > 0 || query.length > 0) && validationError;
+
+  return (
+    <div className={styles.container} data-testid="smart-folder-form">
+      <div className={styles.inputRow}>
+        <span className={styles.icon}>
+          <Sparkles size={14} />
+        </span>
+        <input
+          ref={nameRef}
+          type="text"
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+          onKeyDown={handleKeyDown}
+          placeholder="Smart folder name..."
+          className={styles.input}
+          maxLength={SIDEBAR_CONFIG.MAX_FOLDER_NAME_LENGTH}
+        />
+        <button type="button" onClick={onClose} className={styles.cancelButton} title="Cancel">
+          <X size={14} />
+        </button>
+      </div>
+      <div className={styles.inputRow}>
+        <span className={styles.icon}>
+          <Search size={14} />
+        </span>
+        <input
+          type="text"
+          value={query}
+          onChange={(e) => setQuery(e.target.value)}
+          onKeyDown={handleKeyDown}
+          placeholder="Search, e.g. unused:60d or folder:work review"
+          className={styles.input}
+        />
+      </div>
+      {(showError || saveError) && <div className={styles.errorText}>{saveError ?? validationError}</div>}
+      <div className={styles.hint}>Press Enter to save, Escape to cancel</div>
+    </div>
+  );
+}
@@ -1,22 +1,30 @@
-import { useMemo, useState, useCallback } from 'react';
-import { Plus, PanelLeftClose, Menu } from 'lucide-react';
+import { useMemo, useState, useCallback, useEffect, useRef } from 'react';
+import { Plus, PanelLeftClose, Menu, Download } from 'lucide-react';
 import { useEditorStore } from '../../../stores/editorStore';
 import { useDerivedFolders } from '../../../hooks/useDerivedFolders';
 import { SearchInput } from './SearchInput';
 import { FolderSection } from './FolderSection';
 import { NewFolderInput } from './NewFolderInput';
+import { NewSmartFolderInput } from './NewSmartFolderInput';
 import { FolderContextMenu } from './FolderContextMenu';
 import { FolderEditDialog } from './FolderEditDialog';
+import type { SmartFolder } from '../../../types';
 import styles from './Sidebar.module.css';
 
 interface ContextMenuState {
   folder: string;
   position: { x: number; y: number };
+  /** The folder is a smart folder */
+  smart?: boolean;
 }
 
+/** Key for a smart folder's collapsed state, apart from real folders */
+const smartFolderKey = (name: string) => `smart:${name}`;
+
 export function Sidebar() {
   const {
     prompts,
+    smartFolders,
     selectedPromptId,
     sidebarCollapsed,
     searchFilter,
@@ -31,18 +39,46 @@ export function Sidebar() {
     startEditingFolder,
     cancelEditingFolder,
     deleteFolder,
+    deleteSmartFolder,
+    importRequested,
+    clearImportRequest,
+    importFiles,
   } = useEditorStore();
 
   // Derive visible folders from prompts (hides empty folders)
   const folders = useDerivedFolders(prompts);
 
   const [contextMenu, setContextMenu] = useState<ContextMenuState | null>(null);
+  // Smart folder form: null when closed, 'new' to create one, or the folder being edited
+  const [smartFolderForm, setSmartFolderForm] = useState<SmartFolder | 'new' | null>(null);
+  const importInputRef = useRef<HTMLInputElement>(null);
+
+  // The launcher's Import… action opens the file picker
+  useEffect(() => {
+    if (importRequested && importInputRef.current) {
+      clearImportRequest();
+      importInputRef.current.click();
+    }
+  }, [importRequested, clearImportRequest]);
 
-  const handleFolderContextMenu = useCallback((folder: string, e: React.MouseEvent) => {
+  const handleImportChange = useCallback(async (e: React.ChangeEvent<HTMLInputElement>) => {
+    const input = e.currentTarget;
+    const files = await Promise.all(
+      Array.from(input.files ?? []).map(async (file) => ({ name: file.name, content: await file.text() }))
+    );
+    // Allow picking the same files again
+    input.value = '';
+    if (files.length > 0) {
+      await importFiles(files);
+    }
+  }, [importFiles]);
+
+  const handleFolderContextMenu = useCallback((folder: string, e: React.MouseEvent, smart = false) => {
     e.preventDefault();
     setContextMenu({
       folder,
       position: { x: e.clientX, y: e.clientY },
+      smart,
     });
   }, []);
 
@@ -51,16 +87,20 @@ export function Sidebar() {
   }, []);
 
   const handleRenameFolder = useCallback(() => {
-    if (contextMenu) {
+    if (contextMenu?.smart) {
+      setSmartFolderForm(smartFolders.find((f) => f.name === contextMenu.folder) ?? null);
+    } else if (contextMenu) {
       startEditingFolder(contextMenu.folder);
     }
-  }, [contextMenu, startEditingFolder]);
+  }, [contextMenu, smartFolders, startEditingFolder]);
 
   const handleDeleteFolder = useCallback(() => {
-    if (contextMenu) {
+    if (contextMenu?.smart) {
+      deleteSmartFolder(contextMenu.folder);
+    } else if (contextMenu) {
       deleteFolder(contextMenu.folder);
     }
-  }, [contextMenu, deleteFolder]);
+  }, [contextMenu, deleteFolder, deleteSmartFolder]);
 
   // Group prompts by folder with filtering
   const { promptsByFolder, folderCounts } = useMemo(() => {
@@ -107,6 +147,23 @@ export function Sidebar() {
     return { promptsByFolder: groups, folderCounts: counts };
   }, [prompts, folders, searchFilter]);
 
+  // Each smart folder's current prompts, in its query's order, with the same filtering
+  const smartFolderSections = useMemo(() => {
+    const byId = new Map(prompts.map((p) => [p.id, p]));
+    const filterLower = searchFilter.toLowerCase();
+    return smartFolders.map((folder) => {
+      const all = (folder.promptIds ?? []).flatMap((id) => byId.get(id) ?? []);
+      const filtered = searchFilter
+        ? all.filter(
+            (p) =>
+              p.name.toLowerCase().includes(filterLower) ||
+              p.description.toLowerCase().includes(filterLower)
+          )
+        : all;
+      return { folder, prompts: filtered, total: all.length };
+    });
+  }, [prompts, smartFolders, searchFilter]);
+
   const isFiltering = searchFilter.length > 0;
 
   if (sidebarCollapsed) {
@@ -145,6 +202,24 @@ export function Sidebar() {
               <Plus size={14} />
               New
             </button>
+            <button
+              className={styles.newButton}
+              onClick={() => importInputRef.current?.click()}
+              title="Import Markdown or text files as prompts"
+              data-testid="import-button"
+            >
+              <Download size={14} />
+              Import
+            </button>
+            <input
+              ref={importInputRef}
+              type="file"
+              accept=".md,.markdown,.txt"
+              multiple
+              hidden
+              onChange={handleImportChange}
+              data-testid="import-input"
+            />
             <button
               className={styles.collapseButton}
               onClick={toggleSidebar}
@@ -166,9 +241,25 @@ export function Sidebar() {
             <Plus size={14} />
             Add Folder
           </button>
+          <button
+            className={styles.addFolderButton}
+            onClick={() => setSmartFolderForm('new')}
+            title="Save a search as a smart folder"
+            data-testid="add-smart-folder-button"
+          >
+            <Plus size={14} />
+            Smart Folder
+          </button>
         </div>
 
         {isAddingFolder && <NewFolderInput />}
+        {smartFolderForm && (
+          <NewSmartFolderInput
+            key={smartFolderForm === 'new' ? '' : smartFolderForm.name}
+            editing={smartFolderForm === 'new' ? null : smartFolderForm}
+            onClose={() => setSmartFolderForm(null)}
+          />
+        )}
 
         <div className={styles.list} data-testid="prompt-list">
           {folders.length === 0 ? (
@@ -190,6 +281,22 @@ export function Sidebar() {
               />
             ))
           )}
+          {smartFolderSections.map(({ folder, prompts: folderPrompts, total }) => (
+            <FolderSection
+              key={smartFolderKey(folder.name)}
+              folderName={folder.name}
+              prompts={folderPrompts}
+              filteredCount={folderPrompts.length}
+              totalCount={total}
+              isCollapsed={collapsedFolders[smartFolderKey(folder.name)] || false}
+              isFiltering={isFiltering}
+              onToggleCollapse={() => toggleFolderCollapsed(smartFolderKey(folder.name))}
+              selectedPromptId={selectedPromptId}
+              onSelectPrompt={loadPrompt}
+              onFolderContextMenu={(e) => handleFolderContextMenu(folder.name, e, true)}
+              isSmart
+            />
+          ))}
         </div>
       </aside>
 
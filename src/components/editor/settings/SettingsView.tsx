@@ -1,9 +1,10 @@
 import { useEffect, useState } from 'react';
-import { Settings, Cloud, CloudOff, Power, LogOut, User, Keyboard, Palette, Sun, Moon, Monitor, Layers } from 'lucide-react';
+import { Settings, Cloud, CloudOff, Power, LogOut, User, Keyboard, Palette, Sun, Moon, Monitor, Layers, Search, List, History, Clock, Braces, Share2, EyeOff } from 'lucide-react';
 import { getVersion } from '@tauri-apps/api/app';
 import { getCurrentWindow } from '@tauri-apps/api/window';
-import { useSettingsStore, type AppearanceSettings } from '../../../stores/settingsStore';
+import { useSettingsStore, type AppearanceSettings, type TextRanking, type VariableSettings } from '../../../stores/settingsStore';
 import { useAuthStore } from '../../../stores/authStore';
+import { backend } from '../../../services/backend';
 import { HotkeyInput } from './HotkeyInput';
 import { ACCENT_COLORS, THEME_OPTIONS, type AccentColorName, type ThemeOption } from '../../../config/constants';
 import styles from './SettingsView.module.css';
@@ -26,6 +27,9 @@ export function SettingsView() {
     setEditorAlwaysOnTop,
     setTheme,
     setAccentColor,
+    setRankingProfile,
+    updateSearchSettings,
+    updateVariableSettings,
   } = useSettingsStore();
 
   const {
@@ -118,6 +122,14 @@ export function SettingsView() {
             onHotkeyChange={setHotkey}
             editorAlwaysOnTop={settings.general.editorAlwaysOnTop}
             onEditorAlwaysOnTopChange={setEditorAlwaysOnTop}
+            ranking={settings.search.ranking}
+            onRankingChange={setRankingProfile}
+            resultLimit={settings.search.resultLimit}
+            onResultLimitChange={(resultLimit) => updateSearchSettings({ resultLimit })}
+            historyEnabled={settings.search.historyEnabled}
+            onHistoryEnabledChange={(historyEnabled) => updateSearchSettings({ historyEnabled })}
+            variables={settings.variables}
+            onVariablesChange={updateVariableSettings}
             isSaving={isSaving}
           />
         )}
@@ -155,9 +167,24 @@ interface GeneralSectionProps {
   onHotkeyChange: (value: string | null) => void;
   editorAlwaysOnTop: boolean;
   onEditorAlwaysOnTopChange: (value: boolean) => void;
+  ranking: TextRanking;
+  onRankingChange: (value: TextRanking) => void;
+  resultLimit: number;
+  onResultLimitChange: (value: number) => void;
+  historyEnabled: boolean;
+  onHistoryEnabledChange: (value: boolean) => void;
+  variables: VariableSettings;
+  onVariablesChange: (updates: Partial<VariableSettings>) => void;
   isSaving: boolean;
 }
 
+const RANKING_OPTIONS: { value: TextRanking; label: string }[] = [
+  { value: 'classic', label: 'Classic' },
+  { value: 'bm25f', label: 'Relevance' },
+];
+
+const RESULT_LIMIT_OPTIONS = [10, 15, 25, 50];
+
 function GeneralSection({
   autoLaunch,
   onAutoLaunchChange,
@@ -165,8 +192,60 @@ function GeneralSection({
   onHotkeyChange,
   editorAlwaysOnTop,
   onEditorAlwaysOnTopChange,
+  ranking,
+  onRankingChange,
+  resultLimit,
+  onResultLimitChange,
+  historyEnabled,
+  onHistoryEnabledChange,
+  variables,
+  onVariablesChange,
   isSaving,
 }: GeneralSectionProps) {
+  const [picksCleared, setPicksCleared] = useState(false);
+  const [historyCleared, setHistoryCleared] = useState(false);
+  const [valuesCleared, setValuesCleared] = useState(false);
+  // Edited as text and saved on blur
+  const [sensitive, setSensitive] = useState(variables.sensitive.join(', '));
+
+  useEffect(() => {
+    setSensitive(variables.sensitive.join(', '));
+  }, [variables.sensitive]);
+
+  const handleSensitiveBlur = () => {
+    const names = sensitive.split(',').map((name) => name.trim()).filter(Boolean);
+    if (names.join(', ') !== variables.sensitive.join(', ')) {
+      onVariablesChange({ sensitive: names });
+    }
+  };
+
+  const handleClearValues = async () => {
+    try {
+      await backend.clearVariableHistory();
+      setValuesCleared(true);
+    } catch (e) {
+      console.error('Failed to clear variable values:', e);
+    }
+  };
+
+  const handleClearHistory = async () => {
+    try {
+      await backend.clearQueryHistory();
+      setHistoryCleared(true);
+    } catch (e) {
+      console.error('Failed to clear search history:', e);
+    }
+  };
+
+  const handleClearPicks = async () => {
+    try {
+      await backend.clearSelectionHistory();
+      setPicksCleared(true);
+    } catch (e) {
+      console.error('Failed to clear selection history:', e);
+    }
+  };
+
   return (
     <section className={styles.section}>
       <h3 className={styles.sectionTitle}>General</h3>
@@ -229,6 +308,180 @@ function GeneralSection({
           <span className={styles.toggleSlider} />
         </label>
       </div>
+
+      <div className={styles.settingRow}>
+        <div className={styles.settingInfo}>
+          <div className={styles.settingLabel}>
+            <Search size={16} />
+            Search ranking
+          </div>
+          <div className={styles.settingHint}>
+            Classic favors name and folder matches. Relevance weighs rare words and short fields higher.
+          </div>
+        </div>
+        <div className={styles.themeSelector}>
+          {RANKING_OPTIONS.map((option) => (
+            <button
+              key={option.value}
+              className={`${styles.themeOption} ${ranking === option.value ? styles.active : ''}`}
+              onClick={() => onRankingChange(option.value)}
+              disabled={isSaving}
+              data-testid={`ranking-${option.value}`}
+            >
+              <span>{option.label}</span>
+            </button>
+          ))}
+        </div>
+      </div>
+
+      <div className={styles.settingRow}>
+        <div className={styles.settingInfo}>
+          <div className={styles.settingLabel}>
+            <List size={16} />
+            Results per page
+          </div>
+          <div className={styles.settingHint}>
+            How many results the launcher loads at a time. More load as you move down the list.
+          </div>
+        </div>
+        <div className={styles.themeSelector}>
+          {RESULT_LIMIT_OPTIONS.map((limit) => (
+            <button
+              key={limit}
+              className={`${styles.themeOption} ${resultLimit === limit ? styles.active : ''}`}
+              onClick={() => onResultLimitChange(limit)}
+              disabled={isSaving}
+              data-testid={`result-limit-${limit}`}
+            >
+              <span>{limit}</span>
+            </button>
+          ))}
+        </div>
+      </div>
+
+      <div className={styles.settingRow}>
+        <div className={styles.settingInfo}>
+          <div className={styles.settingLabel}>
+            <History size={16} />
+            Learned picks
+          </div>
+          <div className={styles.settingHint}>
+            Prompts you pick for a search rank higher the next time you type it.
+          </div>
+        </div>
+        <button
+          className={styles.signOutButton}
+          onClick={handleClearPicks}
+          disabled={picksCleared}
+          data-testid="clear-selection-history"
+        >
+          {picksCleared ? 'Cleared' : 'Clear'}
+        </button>
+      </div>
+
+      <div className={styles.settingRow}>
+        <div className={styles.settingInfo}>
+          <div className={styles.settingLabel}>
+            <Clock size={16} />
+            Search history
+          </div>
+          <div className={styles.settingHint}>
+            Remember searches you picked a prompt from and suggest them as you type. Press → to complete.
+          </div>
+        </div>
+        <button
+          className={styles.signOutButton}
+          onClick={handleClearHistory}
+          disabled={historyCleared}
+          data-testid="clear-query-history"
+        >
+          {historyCleared ? 'Cleared' : 'Clear'}
+        </button>
+        <label className={styles.toggle}>
+          <input
+            type="checkbox"
+            checked={historyEnabled}
+            onChange={(e) => onHistoryEnabledChange(e.target.checked)}
+            disabled={isSaving}
+            data-testid="query-history-toggle"
+          />
+          <span className={styles.toggleSlider} />
+        </label>
+      </div>
+
+      <div className={styles.settingRow}>
+        <div className={styles.settingInfo}>
+          <div className={styles.settingLabel}>
+            <Braces size={16} />
+            Remember variable values
+          </div>
+          <div className={styles.settingHint}>
+            Fill in a prompt's variables with what you entered last time, and suggest earlier values.
+          </div>
+        </div>
+        <button
+          className={styles.signOutButton}
+          onClick={handleClearValues}
+          disabled={valuesCleared}
+          data-testid="clear-variable-history"
+        >
+          {valuesCleared ? 'Cleared' : 'Clear'}
+        </button>
+        <label className={styles.toggle}>
+          <input
+            type="checkbox"
+            checked={variables.rememberValues}
+            onChange={(e) => onVariablesChange({ rememberValues: e.target.checked })}
+            disabled={isSaving}
+            data-testid="remember-values-toggle"
+          />
+          <span className={styles.toggleSlider} />
+        </label>
+      </div>
+
+      <div className={styles.settingRow}>
+        <div className={styles.settingInfo}>
+          <div className={styles.settingLabel}>
+            <Share2 size={16} />
+            Share values across prompts
+          </div>
+          <div className={styles.settingHint}>
+            Suggest a value entered in one prompt for variables with the same name in others.
+          </div>
+        </div>
+        <label className={styles.toggle}>
+          <input
+            type="checkbox"
+            checked={variables.shareAcrossPrompts}
+            onChange={(e) => onVariablesChange({ shareAcrossPrompts: e.target.checked })}
+            disabled={isSaving || !variables.rememberValues}
+            data-testid="share-values-toggle"
+          />
+          <span className={styles.toggleSlider} />
+        </label>
+      </div>
+
+      <div className={styles.settingRow}>
+        <div className={styles.settingInfo}>
+          <div className={styles.settingLabel}>
+            <EyeOff size={16} />
+            Sensitive variables
+          </div>
+          <div className={styles.settingHint}>
+            Never remember variables whose names contain any of these, separated by commas.
+          </div>
+        </div>
+        <input
+          type="text"
+          className={styles.textInput}
+          value={sensitive}
+          onChange={(e) => setSensitive(e.target.value)}
+          onBlur={handleSensitiveBlur}
+          disabled={isSaving || !variables.rememberValues}
+          spellCheck={false}
+          data-testid="sensitive-variables-input"
+        />
+      </div>
     </section>
   );
 }
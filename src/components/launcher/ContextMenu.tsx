@@ -1,7 +1,9 @@
 import { useEffect, useRef } from 'react';
 import { backend } from '../../services/backend';
+import { useLauncherStore } from '../../stores/launcherStore';
 import { KEYBOARD_HINT_LABELS } from '../../config/constants';
 import { getCurrentScreenBounds } from '../../utils/screen';
+import { pasteFromLauncher } from '../../utils/paste';
 import styles from './ContextMenu.module.css';
 
 interface MenuItem {
@@ -65,15 +67,14 @@ export function ContextMenu({ x, y, onClose, promptId, promptName, promptContent
   const hasPrompt = !!promptId;
 
   const handlePaste = async () => {
-    if (!promptId || !promptContent) return;
-    await backend.recordUsage(promptId);
-    await backend.pasteAndDismiss(promptContent);
+    if (!promptId) return;
+    await pasteFromLauncher({ id: promptId, name: promptName ?? '' });
     onClose();
   };
 
   const handleCopyAsFile = async () => {
     if (!promptId || !promptName || !promptContent) return;
-    await backend.recordUsage(promptId);
+    await backend.recordUsage(promptId, 'copyFile', useLauncherStore.getState().query);
     await backend.copyAsMarkdownFile(promptName, promptContent);
     onClose();
   };
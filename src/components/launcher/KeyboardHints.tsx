@@ -5,6 +5,23 @@ import styles from './KeyboardHints.module.css';
 export function KeyboardHints() {
   const { mode, results } = useLauncherStore();
 
+  if (mode === 'fill') {
+    return (
+      <div className={styles.container}>
+        <div className={styles.group}>
+          <span className={styles.hint}>
+            <kbd>{KEYBOARD_HINT_LABELS.PASTE}</kbd> paste
+          </span>
+        </div>
+        <div className={styles.group}>
+          <span className={styles.hint}>
+            <kbd>{KEYBOARD_HINT_LABELS.DISMISS}</kbd> back
+          </span>
+        </div>
+      </div>
+    );
+  }
+
   if (mode === 'promoted') {
     return (
       <div className={styles.container}>
@@ -3,13 +3,14 @@ import { invoke } from '@tauri-apps/api/core';
 import { SearchBar } from './SearchBar';
 import { ResultsList } from './ResultsList';
 import { KeyboardHints } from './KeyboardHints';
+import { VariableForm } from './VariableForm';
 import { ContextMenu } from './ContextMenu';
 import { useLauncherStore } from '../../stores/launcherStore';
 import type { Prompt } from '../../types';
 import styles from './LauncherWindow.module.css';
 
 export function LauncherWindow() {
-  const { contextMenu, closeContextMenu, openContextMenu } = useLauncherStore();
+  const { mode, contextMenu, closeContextMenu, openContextMenu } = useLauncherStore();
 
   // Handle right-click on empty area
   const handleContextMenu = useCallback((e: React.MouseEvent) => {
@@ -34,7 +35,7 @@ export function LauncherWindow() {
   return (
     <div className={styles.container} onContextMenu={handleContextMenu} data-testid="launcher-window">
       <SearchBar />
-      <ResultsList />
+      {mode === 'fill' ? <VariableForm /> : <ResultsList />}
       <KeyboardHints />
       {contextMenu.isOpen && (
         <ContextMenuWrapper
@@ -6,13 +6,13 @@ interface PromptPillProps {
 }
 
 export function PromptPill({ name }: PromptPillProps) {
-  const { unpromote } = useLauncherStore();
+  const { mode, unpromote, cancelFill } = useLauncherStore();
 
   return (
     <button
       type="button"
       className={styles.pill}
-      onClick={unpromote}
+      onClick={mode === 'fill' ? cancelFill : unpromote}
       title="Click to unpromote"
     >
       {name}
@@ -1,25 +1,30 @@
 import { useRef, useEffect, useCallback } from 'react';
-import type { SearchResult } from '../../types';
+import type { LauncherAction, LauncherResult, SearchResult } from '../../types';
 import { useLauncherStore } from '../../stores/launcherStore';
 import { Icon } from '../common/Icon';
+import { Highlight } from '../common/Highlight';
 import { PROMPT_COLORS, DEFAULT_PROMPT_ICON, DEFAULT_PROMPT_COLOR } from '../../config/constants';
 import styles from './ResultItem.module.css';
 
+/** Icons for launcher actions */
+const ACTION_ICONS: Record<LauncherAction, string> = {
+  newPrompt: 'file-plus',
+  import: 'download',
+  syncNow: 'refresh-cw',
+  openSettings: 'settings',
+  signOut: 'user',
+};
+
 interface ResultItemProps {
-  result: SearchResult;
+  result: LauncherResult;
   isSelected: boolean;
   index: number;
 }
 
 export function ResultItem({ result, isSelected, index }: ResultItemProps) {
   const { setSelectedIndex, executeSelected, openContextMenu } = useLauncherStore();
-  const { prompt } = result;
   const itemRef = useRef<HTMLDivElement>(null);
 
-  const icon = prompt.icon || DEFAULT_PROMPT_ICON;
-  const color = prompt.color || DEFAULT_PROMPT_COLOR;
-  const colorValue = PROMPT_COLORS[color];
-
   // Scroll into view when selected
   useEffect(() => {
     if (isSelected && itemRef.current) {
@@ -36,12 +41,15 @@ export function ResultItem({ result, isSelected, index }: ResultItemProps) {
     setSelectedIndex(index);
   };
 
+  // Only prompts have a context menu
   const handleContextMenu = useCallback((e: React.MouseEvent) => {
     e.preventDefault();
     e.stopPropagation();
     setSelectedIndex(index);
-    openContextMenu(e.clientX, e.clientY, prompt.id, prompt.name);
-  }, [index, openContextMenu, prompt.id, prompt.name, setSelectedIndex]);
+    if (result.kind === 'prompt') {
+      openContextMenu(e.clientX, e.clientY, result.prompt.id, result.prompt.name);
+    }
+  }, [index, openContextMenu, result, setSelectedIndex]);
 
   return (
     <div
@@ -53,19 +61,81 @@ export function ResultItem({ result, isSelected, index }: ResultItemProps) {
       role="option"
       aria-selected={isSelected}
       data-testid="result-item"
+      data-kind={result.kind}
     >
+      {result.kind === 'prompt' && <PromptContent result={result} />}
+      {result.kind === 'folder' && (
+        <>
+          <span className={styles.icon}>
+            <Icon name="folder" size={18} />
+          </span>
+          <div className={styles.content}>
+            <div className={styles.name} data-testid="result-name">
+              <Highlight text={result.name} ranges={result.matches} />
+            </div>
+            <div className={styles.description}>
+              {result.promptCount === 1 ? '1 prompt' : `${result.promptCount} prompts`}
+            </div>
+          </div>
+          <div className={styles.meta}>
+            <span className={styles.kind}>Folder</span>
+          </div>
+        </>
+      )}
+      {result.kind === 'action' && (
+        <>
+          <span className={styles.icon}>
+            <Icon name={ACTION_ICONS[result.action]} size={18} />
+          </span>
+          <div className={styles.content}>
+            <div className={styles.name} data-testid="result-name">
+              <Highlight text={result.title} ranges={result.matches} />
+            </div>
+          </div>
+          <div className={styles.meta}>
+            <span className={styles.kind}>Action</span>
+          </div>
+        </>
+      )}
+    </div>
+  );
+}
+
+function PromptContent({ result }: { result: SearchResult }) {
+  const { prompt, matches, snippet } = result;
+  // A snippet explains matches the name and description don't show
+  const showSnippet = snippet && !matches?.name?.length && !matches?.description?.length;
+
+  const icon = prompt.icon || DEFAULT_PROMPT_ICON;
+  const color = prompt.color || DEFAULT_PROMPT_COLOR;
+  const colorValue = PROMPT_COLORS[color];
+
+  return (
+    <>
       <span className={styles.icon} style={{ color: colorValue }}>
         <Icon name={icon} size={18} />
       </span>
       <div className={styles.content}>
-        <div className={styles.name} data-testid="result-name">{prompt.name}</div>
-        {prompt.description && (
-          <div className={styles.description}>{prompt.description}</div>
+        <div className={styles.name} data-testid="result-name">
+          <Highlight text={prompt.name} ranges={matches?.name} />
+        </div>
+        {showSnippet ? (
+          <div className={`${styles.description} ${styles.snippet}`} data-testid="result-snippet">
+            <Highlight text={snippet.text} ranges={snippet.highlights} />
+          </div>
+        ) : (
+          prompt.description && (
+            <div className={styles.description}>
+              <Highlight text={prompt.description} ranges={matches?.description} />
+            </div>
+          )
         )}
       </div>
       <div className={styles.meta}>
-        <span className={styles.folder}>{prompt.folder}</span>
+        <span className={styles.folder}>
+          <Highlight text={prompt.folder} ranges={matches?.folder} />
+        </span>
       </div>
-    </div>
+    </>
   );
 }
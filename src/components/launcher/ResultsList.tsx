@@ -1,5 +1,5 @@
 import { ResultItem } from './ResultItem';
-import { useLauncherStore } from '../../stores/launcherStore';
+import { useLauncherStore, launcherResultKey } from '../../stores/launcherStore';
 import styles from './ResultsList.module.css';
 
 export function ResultsList() {
@@ -16,7 +16,7 @@ export function ResultsList() {
   if (results.length === 0) {
     return (
       <div className={styles.noResults} data-testid="empty-state">
-        {query ? `No prompts matching "${query}"` : 'No prompts yet'}
+        {emptyMessage(query)}
       </div>
     );
   }
@@ -25,7 +25,7 @@ export function ResultsList() {
     <div className={styles.container} data-testid="results-list">
       {results.map((result, index) => (
         <ResultItem
-          key={result.prompt.id}
+          key={launcherResultKey(result)}
           result={result}
           isSelected={index === selectedIndex}
           index={index}
@@ -34,3 +34,11 @@ export function ResultsList() {
     </div>
   );
 }
+
+function emptyMessage(query: string): string {
+  if (query.trimStart().startsWith('>')) {
+    const actionQuery = query.trimStart().slice(1).trim();
+    return actionQuery ? `No actions matching "${actionQuery}"` : 'No actions available';
+  }
+  return query ? `No prompts matching "${query}"` : 'No prompts yet';
+}
@@ -1,12 +1,13 @@
-import { useRef, useEffect } from 'react';
+import { useRef, useEffect, useState } from 'react';
 import { PromptPill } from './PromptPill';
 import { useLauncherStore } from '../../stores/launcherStore';
 import { useWindowEvents } from '../../hooks';
+import { backend } from '../../services/backend';
 import styles from './SearchBar.module.css';
 
 export function SearchBar() {
   const inputRef = useRef<HTMLInputElement>(null);
-  const { mode, query, promotedPrompt, riderText, setQuery, setRiderText } =
+  const { mode, query, promotedPrompt, fillPrompt, riderText, setQuery, setRiderText } =
     useLauncherStore();
 
   // Handle window focus events - focuses input and reloads prompts
@@ -19,8 +20,29 @@ export function SearchBar() {
     }
   }, [mode]);
 
+  // A past query that starts with what's typed, shown as a completion
+  const [completion, setCompletion] = useState<string | null>(null);
+  useEffect(() => {
+    setCompletion(null);
+    if (mode !== 'search' || !query.trim()) return;
+
+    let cancelled = false;
+    backend
+      .getQuerySuggestions(query, 1)
+      .then(([suggestion]) => {
+        if (cancelled || !suggestion) return;
+        if (suggestion.query.toLowerCase().startsWith(query.toLowerCase())) {
+          setCompletion(suggestion.query);
+        }
+      })
+      .catch((error) => console.error('Failed to load query suggestions:', error));
+    return () => {
+      cancelled = true;
+    };
+  }, [mode, query]);
+
   const handleChange = (e: React.ChangeEvent<HTMLInputElement>) => {
-    if (mode === 'promoted') {
+    if (mode !== 'search') {
       setRiderText(e.target.value);
     } else {
       setQuery(e.target.value);
@@ -29,33 +51,47 @@ export function SearchBar() {
 
   // Note: Enter key is handled by useKeyboardNav at the window level
   // to avoid duplicate execution. We only need to handle special input-specific keys here.
-  const handleKeyDown = (_e: React.KeyboardEvent) => {
-    // Reserved for future input-specific key handling
+  const handleKeyDown = (e: React.KeyboardEvent<HTMLInputElement>) => {
+    // Right arrow at the end of the input accepts the completion
+    const input = e.currentTarget;
+    if (e.key === 'ArrowRight' && completion && input.selectionStart === input.value.length) {
+      e.preventDefault();
+      setQuery(completion);
+    }
   };
 
-  const value = mode === 'promoted' ? riderText : query;
+  const value = mode === 'search' ? query : riderText;
   const placeholder =
-    mode === 'promoted' ? 'Add context...' : 'Search prompts...';
+    mode === 'search' ? 'Search prompts...' : 'Add context...';
 
   return (
     <div className={styles.container}>
       {mode === 'promoted' && promotedPrompt && (
         <PromptPill name={promotedPrompt.name} />
       )}
-      <input
-        ref={inputRef}
-        type="text"
-        className={styles.input}
-        value={value}
-        onChange={handleChange}
-        onKeyDown={handleKeyDown}
-        placeholder={placeholder}
-        autoComplete="off"
-        autoCorrect="off"
-        autoCapitalize="off"
-        spellCheck={false}
-        data-testid="search-input"
-      />
+      {mode === 'fill' && fillPrompt && <PromptPill name={fillPrompt.name} />}
+      <div className={styles.field}>
+        {completion && (
+          <div className={styles.completion} aria-hidden="true" data-testid="search-completion">
+            <span className={styles.completionTyped}>{query}</span>
+            {completion.slice(query.length)}
+          </div>
+        )}
+        <input
+          ref={inputRef}
+          type="text"
+          className={styles.input}
+          value={value}
+          onChange={handleChange}
+          onKeyDown={handleKeyDown}
+          placeholder={placeholder}
+          autoComplete="off"
+          autoCorrect="off"
+          autoCapitalize="off"
+          spellCheck={false}
+          data-testid="search-input"
+        />
+      </div>
       <img src="/app-icon.png" alt="" className={styles.appIcon} />
     </div>
   );
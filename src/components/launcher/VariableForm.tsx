@@ -0,0 +1,123 @@
+import { useEffect, useRef } from 'react';
+import { useLauncherStore } from '../../stores/launcherStore';
+import type { TemplateVariable } from '../../types';
+import styles from './VariableForm.module.css';
+
+/**
+ * Form for a prompt's `{{variables}}`, shown in place of the results before
+ * the prompt is pasted. Values recently entered are offered as suggestions.
+ * Enter pastes (Cmd+Enter in a multiline field), Esc returns to the results.
+ */
+export function VariableForm() {
+  const { variables, values, fillError, setValue } = useLauncherStore();
+  const formRef = useRef<HTMLFormElement>(null);
+
+  // Start in the first field
+  useEffect(() => {
+    formRef.current?.querySelector<HTMLElement>('input, textarea, select')?.focus();
+  }, []);
+
+  return (
+    <form
+      ref={formRef}
+      className={styles.form}
+      onSubmit={(e) => e.preventDefault()}
+      data-testid="variable-form"
+    >
+      {variables.map((variable) => (
+        <label key={variable.name} className={styles.field}>
+          <span className={styles.label}>
+            {variable.name}
+            {!variable.required && <span className={styles.optional}>optional</span>}
+          </span>
+          <VariableInput
+            variable={variable}
+            value={values[variable.name] ?? ''}
+            onChange={(value) => setValue(variable.name, value)}
+          />
+        </label>
+      ))}
+      {fillError && (
+        <div className={styles.error} role="alert" data-testid="variable-form-error">
+          {fillError}
+        </div>
+      )}
+    </form>
+  );
+}
+
+interface VariableInputProps {
+  variable: TemplateVariable;
+  value: string;
+  onChange: (value: string) => void;
+}
+
+function VariableInput({ variable, value, onChange }: VariableInputProps) {
+  const suggestions = variable.recent?.length ? `variable-${variable.name}-recent` : undefined;
+  const datalist = suggestions && (
+    <datalist id={suggestions}>
+      {variable.recent?.map((recent) => <option key={recent} value={recent} />)}
+    </datalist>
+  );
+
+  switch (variable.type) {
+    case 'multiline':
+      return (
+        <textarea
+          className={styles.input}
+          value={value}
+          rows={3}
+          onChange={(e) => onChange(e.target.value)}
+          data-testid={`variable-${variable.name}`}
+        />
+      );
+    case 'number':
+      return (
+        <>
+          <input
+            type="number"
+            className={styles.input}
+            value={value}
+            min={variable.min}
+            max={variable.max}
+            list={suggestions}
+            onChange={(e) => onChange(e.target.value)}
+            data-testid={`variable-${variable.name}`}
+          />
+          {datalist}
+        </>
+      );
+    case 'choice':
+      return (
+        <select
+          className={styles.input}
+          value={value}
+          onChange={(e) => onChange(e.target.value)}
+          data-testid={`variable-${variable.name}`}
+        >
+          {!variable.options.includes(value) && <option value={value}>{value || 'Choose…'}</option>}
+          {variable.options.map((option) => (
+            <option key={option} value={option}>
+              {option}
+            </option>
+          ))}
+        </select>
+      );
+    case 'text':
+      return (
+        <>
+          <input
+            type="text"
+            className={styles.input}
+            value={value}
+            list={suggestions}
+            onChange={(e) => onChange(e.target.value)}
+            autoComplete="off"
+            spellCheck={false}
+            data-testid={`variable-${variable.name}`}
+          />
+          {datalist}
+        </>
+      );
+  }
+}
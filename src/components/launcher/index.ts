@@ -4,3 +4,4 @@ export { ResultsList } from './ResultsList';
 export { ResultItem } from './ResultItem';
 export { PromptPill } from './PromptPill';
 export { KeyboardHints } from './KeyboardHints';
+export { VariableForm } from './VariableForm';
@@ -34,8 +34,8 @@ export const HOTKEYS = {
 export const SEARCH_CONFIG = {
   /** Debounce delay for search input (ms) */
   DEBOUNCE_MS: 50,
-  /** Maximum number of results to display */
-  MAX_RESULTS: 15,
+  /** Load the next page when the selection is this close to the last loaded result */
+  LOAD_MORE_THRESHOLD: 3,
   /** Minimum query length to trigger search */
   MIN_QUERY_LENGTH: 1,
 } as const;
@@ -330,6 +330,9 @@ export const ACCENT_COLORS = {
 export type AccentColorName = keyof typeof ACCENT_COLORS;
 export const DEFAULT_ACCENT_COLOR: AccentColorName = 'avocado';
 
+/** Default number of launcher results per page */
+export const DEFAULT_RESULT_LIMIT = 15;
+
 /** Theme options */
 export const THEME_OPTIONS = ['dark', 'light', 'auto'] as const;
 export type ThemeOption = (typeof THEME_OPTIONS)[number];
@@ -3,6 +3,13 @@ import { backend } from '../services/backend';
 import { useLauncherStore } from '../stores/launcherStore';
 import { HOTKEYS } from '../config/constants';
 import { getCurrentScreenBounds } from '../utils/screen';
+import { pasteFilledPrompt, pasteFromLauncher } from '../utils/paste';
+import type { LauncherResult, PromptMetadata } from '../types';
+
+/** The query that lists a folder's prompts (quoted when the name has spaces) */
+function folderQuery(name: string): string {
+  return /\s/.test(name) ? `folder:"${name}" ` : `folder:${name} `;
+}
 
 /**
  * Hook for keyboard navigation and actions
@@ -28,6 +35,20 @@ export function useKeyboardNav() {
     }
   }, []);
 
+  /** Drill into a folder, or run an action */
+  const openFolderOrAction = useCallback(async (selected: Exclude<LauncherResult, { kind: 'prompt' }>) => {
+    if (selected.kind === 'folder') {
+      useLauncherStore.getState().setQuery(folderQuery(selected.name));
+      return;
+    }
+    // The backend hides the launcher before running it
+    try {
+      await backend.executeAction(selected.action);
+    } catch (error) {
+      console.error('Failed to run action:', selected.action, error);
+    }
+  }, []);
+
   const paste = useCallback(async () => {
     // Get fresh state directly from store
     const state = useLauncherStore.getState();
@@ -36,49 +57,44 @@ export function useKeyboardNav() {
     console.log('Paste called - mode:', currentMode, 'results:', currentResults.length, 'index:', currentIndex);
 
     try {
-      let textToPaste = '';
+      if (currentMode === 'fill') {
+        await pasteFilledPrompt();
+        return;
+      }
 
-      if (currentMode === 'promoted' && currentPromoted) {
-        // Get the full prompt content
-        const prompt = await backend.getPrompt(currentPromoted.id);
-        textToPaste = currentRider
-          ? `${prompt.content} ${currentRider}`
-          : prompt.content;
+      let prompt: PromptMetadata;
+      let riderText = '';
 
-        // Record usage
-        await backend.recordUsage(currentPromoted.id);
+      if (currentMode === 'promoted' && currentPromoted) {
+        prompt = currentPromoted;
+        riderText = currentRider;
       } else {
         const selected = currentResults[currentIndex];
         if (!selected) {
           console.error('No prompt selected, selectedIndex:', currentIndex, 'results:', currentResults.length);
           return;
         }
+        if (selected.kind !== 'prompt') {
+          await openFolderOrAction(selected);
+          return;
+        }
 
-        console.log('Getting prompt:', selected.prompt.id);
-
-        // Get the full prompt content
-        const prompt = await backend.getPrompt(selected.prompt.id);
-
-        console.log('Got prompt content, length:', prompt.content.length);
-        textToPaste = prompt.content;
-
-        // Record usage
-        await backend.recordUsage(selected.prompt.id);
+        prompt = selected.prompt;
       }
 
-      console.log('Copying to clipboard, text length:', textToPaste.length);
-      // Paste and dismiss - reset will happen when window is shown again via focus handler
-      await backend.pasteAndDismiss(textToPaste);
-      console.log('Copied to clipboard successfully');
+      console.log('Pasting prompt:', prompt.id);
+      // Paste and dismiss (or ask for its variables first) - reset will happen
+      // when window is shown again via focus handler
+      await pasteFromLauncher(prompt, riderText);
     } catch (error) {
       console.error('Failed to paste:', error);
     }
-  }, []);
+  }, [openFolderOrAction]);
 
   const copyAsFile = useCallback(async () => {
     // Get fresh state directly from store
     const state = useLauncherStore.getState();
-    const { mode: currentMode, results: currentResults, selectedIndex: currentIndex, promotedPrompt: currentPromoted, riderText: currentRider } = state;
+    const { mode: currentMode, results: currentResults, selectedIndex: currentIndex, promotedPrompt: currentPromoted, riderText: currentRider, query } = state;
 
     console.log('CopyAsFile called - mode:', currentMode, 'results:', currentResults.length, 'index:', currentIndex);
 
@@ -95,10 +111,10 @@ export function useKeyboardNav() {
           : prompt.content;
 
         // Record usage
-        await backend.recordUsage(currentPromoted.id);
+        await backend.recordUsage(currentPromoted.id, 'copyFile', query);
       } else {
         const selected = currentResults[currentIndex];
-        if (!selected) {
+        if (selected?.kind !== 'prompt') {
           console.error('No prompt selected, selectedIndex:', currentIndex, 'results:', currentResults.length);
           return;
         }
@@ -113,7 +129,7 @@ export function useKeyboardNav() {
         content = prompt.content;
 
         // Record usage
-        await backend.recordUsage(selected.prompt.id);
+        await backend.recordUsage(selected.prompt.id, 'copyFile', query);
       }
 
       console.log('Copying as markdown file:', name);
@@ -131,11 +147,16 @@ export function useKeyboardNav() {
 
   useEffect(() => {
     const handleKeyDown = (e: KeyboardEvent) => {
-      // Escape always dismisses, regardless of focus
+      // Escape dismisses regardless of focus; while filling in variables it
+      // returns to the results instead
       if (e.key === HOTKEYS.DISMISS) {
         e.preventDefault();
         e.stopPropagation();
-        dismiss();
+        if (mode === 'fill') {
+          useLauncherStore.getState().cancelFill();
+        } else {
+          dismiss();
+        }
         return;
       }
 
@@ -157,6 +178,17 @@ export function useKeyboardNav() {
         return;
       }
 
+      // While filling in variables, Enter pastes; in a multiline field it's a
+      // newline unless Cmd is held
+      if (mode === 'fill') {
+        const inTextArea = e.target instanceof HTMLTextAreaElement;
+        if (e.key === HOTKEYS.SELECT && (!inTextArea || e.metaKey || e.ctrlKey)) {
+          e.preventDefault();
+          paste();
+        }
+        return;
+      }
+
       // Option+Enter - Copy as markdown file
       if (e.altKey && e.key === 'Enter') {
         e.preventDefault();
@@ -168,7 +200,7 @@ export function useKeyboardNav() {
       if (e.shiftKey && e.key === 'Enter') {
         e.preventDefault();
         const selected = useLauncherStore.getState().getSelectedResult();
-        if (selected) {
+        if (selected?.kind === 'prompt') {
           getCurrentScreenBounds().then((screenBounds) => {
             backend.openEditorWindow(selected.prompt.id, screenBounds);
           });
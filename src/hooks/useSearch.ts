@@ -8,8 +8,12 @@ import { SEARCH_CONFIG } from '../config/constants';
  * Always fetches fresh data from backend - no caching to ensure consistency
  */
 export function useSearch() {
-  const { query, mode, setResults, setLoading } = useLauncherStore();
+  const { query, mode, results, totalResults, selectedIndex, setResults, appendResults, setLoading } =
+    useLauncherStore();
   const debounceRef = useRef<number | null>(null);
+  // Query the loaded results belong to, so a late page never lands on newer results
+  const loadedQueryRef = useRef('');
+  const loadingMoreRef = useRef(false);
 
   const performSearch = useCallback(
     async (searchQuery: string) => {
@@ -17,8 +21,9 @@ export function useSearch() {
       const effectiveQuery = isEmptyQuery ? '' : searchQuery;
 
       try {
-        const results = await backend.searchPrompts(effectiveQuery);
-        setResults(results);
+        const page = await backend.searchLauncher(effectiveQuery);
+        loadedQueryRef.current = effectiveQuery;
+        setResults(page.results, page.total);
       } catch (error) {
         console.error('Search error:', error);
         setResults([]);
@@ -28,6 +33,27 @@ export function useSearch() {
     [setResults, setLoading]
   );
 
+  // Load the next page as the selection nears the end of the loaded results
+  useEffect(() => {
+    if (mode === 'promoted' || loadingMoreRef.current) return;
+    if (results.length >= totalResults) return;
+    if (selectedIndex < results.length - SEARCH_CONFIG.LOAD_MORE_THRESHOLD) return;
+
+    const pageQuery = loadedQueryRef.current;
+    loadingMoreRef.current = true;
+    backend
+      .searchPrompts(pageQuery, { offset: results.length })
+      .then((page) => {
+        if (loadedQueryRef.current === pageQuery) {
+          appendResults(page.results);
+        }
+      })
+      .catch((error) => console.error('Failed to load more results:', error))
+      .finally(() => {
+        loadingMoreRef.current = false;
+      });
+  }, [mode, results.length, totalResults, selectedIndex, appendResults]);
+
   useEffect(() => {
     // Don't search in promoted mode
     if (mode === 'promoted') return;
@@ -25,8 +25,8 @@ export function useWindowEvents(inputRef: React.RefObject<HTMLInputElement | nul
 
     // Always fetch fresh data from backend
     try {
-      const results = await backend.searchPrompts('');
-      setResults(results);
+      const page = await backend.searchLauncher('');
+      setResults(page.results, page.total);
     } catch (error) {
       console.error('Failed to load prompts on focus:', error);
     }
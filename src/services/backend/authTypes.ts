@@ -46,9 +46,41 @@ export interface AppearanceSettings {
   accentColor: AccentColorName;
 }
 
+/** Search settings */
+export interface SearchSettings {
+  ranking: 'classic' | 'bm25f';
+  /** How much each ranking signal counts (1 is the default, 0 turns it off) */
+  weights: {
+    text: number;
+    usage: number;
+    recency: number;
+    pinned: number;
+    appContext: number;
+    selection: number;
+  };
+  /** Results per page in the launcher */
+  resultLimit: number;
+  /** Whether launcher queries are remembered and offered as suggestions */
+  historyEnabled: boolean;
+}
+
+/** Template variable settings */
+export interface VariableSettings {
+  /** Whether values entered for variables are remembered and offered again */
+  rememberValues: boolean;
+  /** Values remembered per variable */
+  rememberedValues: number;
+  /** Whether a value entered in one prompt is offered for the same variable name in others */
+  shareAcrossPrompts: boolean;
+  /** Variables never remembered: those whose names contain one of these, ignoring case */
+  sensitive: string[];
+}
+
 /** Complete application settings */
 export interface AppSettings {
   general: GeneralSettings;
   sync: SyncSettings;
   appearance: AppearanceSettings;
+  search: SearchSettings;
+  variables: VariableSettings;
 }
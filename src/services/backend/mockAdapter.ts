@@ -13,9 +13,40 @@
 
 import type { BackendAdapter, ScreenBounds, TestAction } from './types';
 import type { AppSettings, AuthSession, User } from './authTypes';
-import type { Prompt, PromptMetadata, PromptIndex, SearchResult } from '../../types';
+import type {
+  DailyUsage,
+  Includer,
+  IndexPage,
+  LauncherAction,
+  LauncherPage,
+  LauncherResult,
+  PageOptions,
+  Prompt,
+  PromptIndex,
+  PromptMetadata,
+  PromptSort,
+  PromptUsage,
+  RecentQuery,
+  SearchOptions,
+  SearchPage,
+  SearchResult,
+  SmartFolder,
+  SortDirection,
+  TemplateVariable,
+  UsageAction,
+  VariableKind,
+} from '../../types';
 import { DEFAULT_THEME, DEFAULT_ACCENT_COLOR } from '../../config/constants';
 
+/** Launcher actions and their titles, in the order listed for a bare `>` */
+const LAUNCHER_ACTIONS: [LauncherAction, string][] = [
+  ['newPrompt', 'New Prompt'],
+  ['import', 'Import…'],
+  ['syncNow', 'Sync Now'],
+  ['openSettings', 'Open Settings'],
+  ['signOut', 'Sign Out'],
+];
+
 /** Generate a unique ID */
 function generateId(): string {
   return `prompt-${Date.now()}-${Math.random().toString(36).slice(2, 9)}`;
@@ -27,6 +58,168 @@ function toMetadata(prompt: Prompt): PromptMetadata {
   return metadata;
 }
 
+/** One recorded use (mirrors a usage.jsonl line) */
+interface MockUsageEvent {
+  promptId: string;
+  timestamp: string;
+  action: UsageAction;
+  /** App the prompt was pasted into */
+  app?: string;
+}
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+/** Local YYYY-MM-DD for a date */
+function localDate(date: Date): string {
+  const month = String(date.getMonth() + 1).padStart(2, '0');
+  const day = String(date.getDate()).padStart(2, '0');
+  return `${date.getFullYear()}-${month}-${day}`;
+}
+
+/** Quote a CSV field if needed */
+function csvField(value: string): string {
+  return /[",\r\n]/.test(value) ? `"${value.replace(/"/g, '""')}"` : value;
+}
+
+/** `{{name}}`, `{{name|default}}` and `{{name:kind}}` placeholders (a simpler take on the backend's parser) */
+const PLACEHOLDER = /\{\{([^{}\n]*)\}\}/g;
+
+/** `{{> folder/name}}` and `{{> id}}` includes */
+const INCLUDE = /\{\{\s*>\s*([^{}\n]+?)\s*\}\}/g;
+
+/** Deepest nesting of includes, as in the backend */
+const INCLUDE_MAX_DEPTH = 8;
+
+/** Built-in variables, by name (format arguments like `{{date:%Y}}` are ignored here) */
+const BUILTINS: Record<string, () => string> = {
+  clipboard: () => '',
+  date: () => localDate(new Date()),
+  time: () => new Date().toTimeString().slice(0, 5),
+  app: () => '',
+  library: () => 'Local',
+  uuid: () => crypto.randomUUID(),
+};
+
+/** The built-in a placeholder name refers to; only `date` and `time` take an argument */
+function builtinName(name: string): string | undefined {
+  const [base, ...arg] = name.split(':');
+  const builtin = base.trim();
+  if (!(builtin in BUILTINS)) return undefined;
+  return arg.length === 0 || builtin === 'date' || builtin === 'time' ? builtin : undefined;
+}
+
+/** The kind a `{{name:spec}}` declares, and the default after it */
+function parseKind(spec: string): { kind: VariableKind; defaultValue?: string } {
+  const [head, ...rest] = spec.split('|');
+  const defaultValue = rest.length > 0 ? rest.join('|').trim() : undefined;
+  const kind = head.trim();
+  if (kind === 'text' || kind === 'multiline') return { kind: { type: kind }, defaultValue };
+  if (kind === 'number') return { kind: { type: 'number' }, defaultValue };
+  const range = /^(-?[\d.]*)\.\.(-?[\d.]*)$/.exec(kind);
+  if (range) {
+    const [min, max] = [range[1], range[2]].map((b) => (b ? Number(b) : undefined));
+    return { kind: { type: 'number', min, max }, defaultValue };
+  }
+  // Not a kind, and a single word isn't a choice
+  if (defaultValue === undefined) return { kind: { type: 'text' } };
+  const options = spec.split('|').map((o) => o.trim()).filter(Boolean);
+  return { kind: { type: 'choice', options } };
+}
+
+/** The name, default and declared kind of a placeholder, as the backend's parser reads them */
+function parsePlaceholder(inner: string): { name: string; defaultValue?: string; kind?: VariableKind } {
+  const [head, ...rest] = inner.split('|');
+  let name = head.trim();
+  let defaultValue = rest.length > 0 ? rest.join('|').trim() : undefined;
+  let kind: VariableKind | undefined;
+  const colon = inner.indexOf(':');
+  if (name.includes(':') && builtinName(name) === undefined) {
+    name = inner.slice(0, colon).trim();
+    ({ kind, defaultValue } = parseKind(inner.slice(colon + 1)));
+  }
+  if (name.endsWith('?')) {
+    name = name.slice(0, -1).trimEnd();
+    defaultValue ??= '';
+  }
+  return { name, defaultValue, kind };
+}
+
+/** Why a value isn't one of a kind, if it isn't */
+function checkValue(kind: VariableKind, value: string): string | undefined {
+  if (kind.type === 'choice' && !kind.options.includes(value)) {
+    return `must be one of ${kind.options.join(', ')}`;
+  }
+  if (kind.type === 'number') {
+    const n = Number(value.trim());
+    if (value.trim() === '' || !Number.isFinite(n)) return 'must be a number';
+    if (kind.min !== undefined && n < kind.min) return `must be at least ${kind.min}`;
+    if (kind.max !== undefined && n > kind.max) return `must be at most ${kind.max}`;
+  }
+  return undefined;
+}
+
+/** A template's variables in order of appearance; the first default and kind declared win */
+function templateVariables(content: string): TemplateVariable[] {
+  const variables: TemplateVariable[] = [];
+  const declared = new Set<string>();
+  for (const match of content.matchAll(PLACEHOLDER)) {
+    // Includes are expanded before variables are read
+    if (match[1].trim().startsWith('>')) continue;
+    const { name, defaultValue, kind } = parsePlaceholder(match[1]);
+    if (!name) continue;
+    const start = match.index ?? 0;
+    const range = { start, end: start + match[0].length };
+    let variable = variables.find((v) => v.name === name);
+    if (variable) {
+      variable.ranges.push(range);
+      variable.default ??= defaultValue;
+    } else {
+      const builtin = builtinName(name) !== undefined;
+      variable = { name, default: defaultValue, builtin, type: 'text', required: false, ranges: [range] };
+      variables.push(variable);
+    }
+    if (kind && !declared.has(name)) {
+      declared.add(name);
+      Object.assign(variable, kind);
+    }
+  }
+  for (const variable of variables) {
+    variable.required = !variable.builtin && variable.default === undefined;
+  }
+  return variables;
+}
+
+/** Fill in a template like the backend's `render_prompt` */
+function renderTemplate(content: string, given: Record<string, string>): string {
+  const variables = templateVariables(content);
+  // An empty number or choice counts as not given
+  const values = Object.fromEntries(
+    Object.entries(given).filter(([name, value]) => {
+      const variable = variables.find((v) => v.name === name);
+      return value !== '' || !variable || variable.type === 'text' || variable.type === 'multiline';
+    })
+  );
+  const invalid = variables
+    .filter((v) => values[v.name] !== undefined)
+    .map((v) => [v.name, checkValue(v, values[v.name])])
+    .filter(([, reason]) => reason !== undefined);
+  if (invalid.length > 0) {
+    throw new Error(`Invalid values for template variables: ${invalid.map((i) => i.join(' ')).join('; ')}`);
+  }
+  const missing = variables.filter((v) => values[v.name] === undefined && !v.builtin && v.default === undefined);
+  if (missing.length > 0) {
+    throw new Error(`Missing values for template variables: ${missing.map((v) => v.name).join(', ')}`);
+  }
+  const builtins = new Map(
+    variables.filter((v) => v.builtin).map((v) => [v.name, BUILTINS[builtinName(v.name) ?? '']() || v.default || ''])
+  );
+  return content.replace(PLACEHOLDER, (placeholder, inner: string) => {
+    const variable = variables.find((v) => v.name === parsePlaceholder(inner).name);
+    if (!variable) return placeholder;
+    return values[variable.name] ?? builtins.get(variable.name) ?? variable.default ?? '';
+  });
+}
+
 /** Default settings */
 const defaultSettings: AppSettings = {
   general: {
@@ -43,16 +236,37 @@ const defaultSettings: AppSettings = {
     theme: DEFAULT_THEME,
     accentColor: DEFAULT_ACCENT_COLOR,
   },
+  search: {
+    ranking: 'classic',
+    weights: { text: 1, usage: 1, recency: 1, pinned: 1, appContext: 1, selection: 1 },
+    resultLimit: 15,
+    historyEnabled: true,
+  },
+  variables: {
+    rememberValues: true,
+    rememberedValues: 5,
+    shareAcrossPrompts: false,
+    sensitive: ['password', 'secret', 'token'],
+  },
 };
 
 export class MockAdapter implements BackendAdapter {
   // In-memory storage
   private prompts: Map<string, Prompt> = new Map();
   private folders: Set<string> = new Set(['uncategorized']);
+  private smartFolders: SmartFolder[] = [];
   private settings: AppSettings = { ...defaultSettings };
   private autoStartEnabled = false;
   private currentUser: User | null = null;
   private hotkeyPaused = false;
+  private usageEvents: MockUsageEvent[] = [];
+  /** Most recent first */
+  private recentQueries: RecentQuery[] = [];
+  /** Values entered for variables, most recent first, by `promptId/name` and (shared) by name */
+  private recentValues: Map<string, string[]> = new Map();
+  private sharedValues: Map<string, string[]> = new Map();
+  /** App the launcher was opened over; uses are logged against it until a paste hands focus back */
+  private previousApp: string | null = null;
 
   // Action tracking for test assertions
   private _actionHistory: TestAction[] = [];
@@ -76,6 +290,11 @@ export class MockAdapter implements BackendAdapter {
     return this.hotkeyPaused;
   }
 
+  /** Set the app the launcher was opened over (for tests) */
+  setPreviousApp(app: string | null): void {
+    this.previousApp = app;
+  }
+
   /** Clear action history */
   clearActionHistory(): void {
     this._actionHistory = [];
@@ -123,10 +342,16 @@ export class MockAdapter implements BackendAdapter {
     this.prompts.clear();
     this.folders.clear();
     this.folders.add('uncategorized');
+    this.smartFolders = [];
     this.settings = { ...defaultSettings };
     this.autoStartEnabled = false;
     this.currentUser = null;
     this.hotkeyPaused = false;
+    this.usageEvents = [];
+    this.previousApp = null;
+    this.recentQueries = [];
+    this.recentValues.clear();
+    this.sharedValues.clear();
     this._actionHistory = [];
     this._errorInjections.clear();
   }
@@ -145,6 +370,38 @@ export class MockAdapter implements BackendAdapter {
       prompts,
       folders: Array.from(this.folders),
       folderMeta: {},
+      smartFolders: this.smartFolders.map((folder) => ({
+        ...folder,
+        promptIds: this.matchPrompts(folder.query).map((r) => r.prompt.id),
+      })),
+    };
+  }
+
+  async getIndexPage(
+    sort: PromptSort = 'name',
+    direction: SortDirection = 'asc',
+    page: PageOptions = {}
+  ): Promise<IndexPage> {
+    const prompts = Array.from(this.prompts.values()).map(toMetadata);
+    const key = (p: PromptMetadata): string | number => {
+      switch (sort) {
+        case 'name':
+          return p.name.toLowerCase();
+        case 'useCount':
+          return p.useCount;
+        case 'lastUsed':
+          return p.lastUsed ?? '';
+        default:
+          return p[sort];
+      }
+    };
+    const sign = direction === 'asc' ? 1 : -1;
+    prompts.sort((a, b) => (key(a) < key(b) ? -sign : key(a) > key(b) ? sign : 0));
+
+    const offset = page.offset ?? 0;
+    return {
+      prompts: prompts.slice(offset, offset + (page.limit ?? 100)),
+      total: prompts.length,
     };
   }
 
@@ -156,10 +413,136 @@ export class MockAdapter implements BackendAdapter {
     return { ...prompt };
   }
 
+  async extractVariables(promptId: string): Promise<TemplateVariable[]> {
+    const prompt = await this.getPrompt(promptId);
+    const variables = templateVariables(prompt.content);
+    // Variables only found in included prompts have no ranges in this one
+    for (const variable of templateVariables(this.expandIncludes(prompt))) {
+      const own = variables.find((v) => v.name === variable.name);
+      if (!own) {
+        variables.push({ ...variable, ranges: [] });
+      } else {
+        if (own.default === undefined && variable.default !== undefined) {
+          own.default = variable.default;
+          own.required = variable.required;
+        }
+        // A kind declared in an included prompt applies if this one declares none
+        if (own.type === 'text') {
+          Object.assign(own, variable, { ranges: own.ranges, default: own.default, required: own.required });
+        }
+      }
+    }
+    const { rememberValues, rememberedValues, shareAcrossPrompts } = this.settings.variables;
+    if (rememberValues) {
+      for (const variable of variables) {
+        if (variable.builtin || this.isSensitiveVariable(variable.name)) continue;
+        const recent = shareAcrossPrompts
+          ? this.sharedValues.get(variable.name)
+          : this.recentValues.get(`${promptId}/${variable.name}`);
+        if (recent?.length) variable.recent = recent.slice(0, rememberedValues);
+      }
+    }
+    return variables;
+  }
+
+  /** Whether values of a variable are never remembered, as in the backend's settings */
+  private isSensitiveVariable(name: string): boolean {
+    const lower = name.toLowerCase();
+    return this.settings.variables.sensitive.some((s) => s.trim() && lower.includes(s.trim().toLowerCase()));
+  }
+
+  /** Remember the values a prompt was pasted with, like the backend's `remember_values` */
+  private rememberValues(promptId: string, values: Record<string, string>): void {
+    const { rememberValues, rememberedValues } = this.settings.variables;
+    if (!rememberValues) return;
+    const push = (map: Map<string, string[]>, key: string, value: string) => {
+      const previous = (map.get(key) ?? []).filter((v) => v !== value);
+      map.set(key, [value, ...previous].slice(0, rememberedValues));
+    };
+    for (const [name, value] of Object.entries(values)) {
+      if (!value.trim() || this.isSensitiveVariable(name)) continue;
+      push(this.recentValues, `${promptId}/${name}`, value);
+      push(this.sharedValues, name, value);
+    }
+  }
+
+  async clearVariableHistory(): Promise<void> {
+    this.recentValues.clear();
+    this.sharedValues.clear();
+    this._actionHistory.push({ type: 'clear_variable_history' });
+  }
+
+  async renderPrompt(promptId: string, values: Record<string, string> = {}): Promise<string> {
+    const prompt = await this.getPrompt(promptId);
+    return renderTemplate(this.expandIncludes(prompt), values);
+  }
+
+  async getIncludedBy(promptId: string): Promise<Includer[]> {
+    const includes = (prompt: Prompt) =>
+      [...prompt.content.matchAll(INCLUDE)].map((match) => this.resolveInclude(match[1])?.id);
+    const found: Includer[] = [];
+    const seen = new Set([promptId]);
+    const pending = [promptId];
+    for (let current = pending.pop(); current !== undefined; current = pending.pop()) {
+      for (const prompt of this.prompts.values()) {
+        if (!seen.has(prompt.id) && includes(prompt).includes(current)) {
+          seen.add(prompt.id);
+          found.push({ prompt: toMetadata(prompt), direct: current === promptId });
+          pending.push(prompt.id);
+        }
+      }
+    }
+    return found.sort((a, b) => Number(b.direct) - Number(a.direct) || a.prompt.name.localeCompare(b.prompt.name));
+  }
+
+  /** The prompt an include names: by id, or by `folder/name` (ignoring case) */
+  private resolveInclude(target: string): Prompt | undefined {
+    const byId = this.prompts.get(target);
+    if (byId) return byId;
+    const slash = target.lastIndexOf('/');
+    if (slash < 0) return undefined;
+    const folder = target.slice(0, slash).trim().toLowerCase();
+    const name = target.slice(slash + 1).trim().toLowerCase();
+    return [...this.prompts.values()].find((p) => p.folder.toLowerCase() === folder && p.name.toLowerCase() === name);
+  }
+
+  /** A prompt's content with its includes expanded, recursively */
+  private expandIncludes(prompt: Prompt, stack: string[] = []): string {
+    if (stack.includes(prompt.id)) {
+      throw new Error(`Prompt includes itself: ${[...stack, prompt.id].join(' → ')}`);
+    }
+    if (stack.length > INCLUDE_MAX_DEPTH) {
+      throw new Error('Includes are nested too deeply');
+    }
+    return prompt.content.replace(INCLUDE, (_, target: string) => {
+      const included = this.resolveInclude(target);
+      if (!included) {
+        throw new Error(`Included prompt not found: ${target}`);
+      }
+      return this.expandIncludes(included, [...stack, prompt.id]);
+    });
+  }
+
   async savePrompt(prompt: Prompt): Promise<PromptMetadata> {
     const now = new Date().toISOString();
     const isNew = !prompt.id;
 
+    const keyword = prompt.keyword?.trim().toLowerCase();
+    const taken = Array.from(this.prompts.values()).find(
+      (p) => p.id !== prompt.id && keyword && p.keyword?.toLowerCase() === keyword
+    );
+    if (taken) {
+      throw new Error(`Keyword "${prompt.keyword}" is already used by "${taken.name}"`);
+    }
+
+    const invalid = templateVariables(prompt.content)
+      .filter((v) => !v.builtin && v.default)
+      .map((v) => [v.name, checkValue(v, v.default ?? '')])
+      .filter(([, reason]) => reason !== undefined);
+    if (invalid.length > 0) {
+      throw new Error(`Invalid defaults for template variables: ${invalid.map((i) => i.join(' ')).join('; ')}`);
+    }
+
     const savedPrompt: Prompt = {
       ...prompt,
       id: prompt.id || generateId(),
@@ -182,7 +565,80 @@ export class MockAdapter implements BackendAdapter {
     this.prompts.delete(id);
   }
 
-  async searchPrompts(query: string): Promise<SearchResult[]> {
+  async saveSmartFolder(folder: SmartFolder, previousName?: string): Promise<void> {
+    const name = folder.name.trim();
+    const query = folder.query.trim();
+    if (!name || !query) {
+      throw new Error('Smart folder name and query are required');
+    }
+    const same = (a: string, b: string) => a.toLowerCase() === b.toLowerCase();
+    const others = this.smartFolders.filter((f) => !previousName || !same(f.name, previousName));
+    if (previousName && others.length === this.smartFolders.length) {
+      throw new Error('Folder does not exist');
+    }
+    if (others.some((f) => same(f.name, name)) || Array.from(this.folders).some((f) => same(f, name))) {
+      throw new Error('A folder with that name already exists');
+    }
+    const saved = { name, query, icon: folder.icon, color: folder.color };
+    const index = previousName ? this.smartFolders.findIndex((f) => same(f.name, previousName)) : -1;
+    this.smartFolders = index >= 0 ? this.smartFolders.map((f, i) => (i === index ? saved : f)) : [...others, saved];
+  }
+
+  async deleteSmartFolder(name: string): Promise<void> {
+    this.smartFolders = this.smartFolders.filter((f) => f.name.toLowerCase() !== name.toLowerCase());
+  }
+
+  async searchPrompts(query: string, page: SearchOptions = {}): Promise<SearchPage> {
+    const results = this.withKeywordFirst(query, this.matchPrompts(query));
+    const offset = page.offset ?? 0;
+    const limit = page.limit ?? this.settings.search.resultLimit;
+    return { results: results.slice(offset, offset + limit), total: results.length };
+  }
+
+  async searchLauncher(query: string, page: SearchOptions = {}): Promise<LauncherPage> {
+    const offset = page.offset ?? 0;
+    const limit = page.limit ?? this.settings.search.resultLimit;
+    const actionQuery = query.trimStart().startsWith('>') ? query.trimStart().slice(1) : null;
+
+    let results: LauncherResult[];
+    if (actionQuery !== null) {
+      results = this.matchActions(actionQuery);
+    } else {
+      const prompts: LauncherResult[] = this.withKeywordFirst(query, this.matchPrompts(query)).map((r) => ({
+        kind: 'prompt',
+        ...r,
+      }));
+      const normalized = query.toLowerCase().trim();
+      const others: LauncherResult[] = [];
+      if (normalized && !normalized.includes(':')) {
+        for (const name of this.folders) {
+          if (!name.toLowerCase().includes(normalized)) continue;
+          const promptCount = Array.from(this.prompts.values()).filter((p) => p.folder === name).length;
+          const score = name.toLowerCase().startsWith(normalized) ? 1.0 : 0.8;
+          others.push({ kind: 'folder', name, promptCount, score });
+        }
+        others.push(...this.matchActions(query));
+      }
+      results = [...prompts, ...others].sort((a, b) => b.score - a.score);
+    }
+    return { results: results.slice(offset, offset + limit), total: results.length };
+  }
+
+  private matchActions(query: string): LauncherResult[] {
+    const normalized = query.toLowerCase().trim();
+    return LAUNCHER_ACTIONS.filter(([action]) => this.currentUser || (action !== 'syncNow' && action !== 'signOut'))
+      .filter(([, title]) => title.toLowerCase().includes(normalized))
+      .map(([action, title]) => ({ kind: 'action', action, title, score: 1.0 }));
+  }
+
+  async executeAction(action: LauncherAction): Promise<void> {
+    this._actionHistory.push({ type: 'execute_action', action });
+    if (action === 'signOut') {
+      this.currentUser = null;
+    }
+  }
+
+  private matchPrompts(query: string): SearchResult[] {
     const normalizedQuery = query.toLowerCase().trim();
     const prompts = Array.from(this.prompts.values());
 
@@ -216,13 +672,108 @@ export class MockAdapter implements BackendAdapter {
       .sort((a, b) => b.score - a.score);
   }
 
-  async recordUsage(id: string): Promise<void> {
+  /** An exact keyword match goes first */
+  private withKeywordFirst(query: string, results: SearchResult[]): SearchResult[] {
+    const keyword = query.trim().toLowerCase();
+    const hit = Array.from(this.prompts.values()).find((p) => keyword && p.keyword?.toLowerCase() === keyword);
+    if (!hit) return results;
+    return [{ prompt: toMetadata(hit), score: 10000 }, ...results.filter((r) => r.prompt.id !== hit.id)];
+  }
+
+  async recordUsage(id: string, action: UsageAction = 'paste', query?: string): Promise<void> {
     const prompt = this.prompts.get(id);
     if (prompt) {
+      const timestamp = new Date().toISOString();
       prompt.useCount += 1;
-      prompt.lastUsed = new Date().toISOString();
+      prompt.lastUsed = timestamp;
+      this.usageEvents.push({ promptId: id, timestamp, action, app: this.previousApp ?? undefined });
+    }
+    const trimmed = query?.trim();
+    if (trimmed && this.settings.search.historyEnabled) {
+      const previous = this.recentQueries.find((q) => q.query.toLowerCase() === trimmed.toLowerCase());
+      this.recentQueries = [
+        { query: trimmed, lastUsed: new Date().toISOString(), count: (previous?.count ?? 0) + 1 },
+        ...this.recentQueries.filter((q) => q !== previous),
+      ];
+    }
+    this._actionHistory.push({ type: 'record_usage', id, action, query });
+  }
+
+  async clearSelectionHistory(): Promise<void> {
+    this._actionHistory.push({ type: 'clear_selection_history' });
+  }
+
+  async getQuerySuggestions(input: string, limit = 5): Promise<RecentQuery[]> {
+    if (!this.settings.search.historyEnabled) return [];
+    const typed = input.trim().toLowerCase();
+    if (!typed) return this.recentQueries.slice(0, limit);
+    const others = this.recentQueries.filter((q) => q.query.toLowerCase() !== typed);
+    const prefixed = others.filter((q) => q.query.toLowerCase().startsWith(typed));
+    const containing = others.filter((q) => !prefixed.includes(q) && q.query.toLowerCase().includes(typed));
+    return [...prefixed, ...containing].slice(0, limit);
+  }
+
+  async clearQueryHistory(): Promise<void> {
+    this.recentQueries = [];
+    this._actionHistory.push({ type: 'clear_query_history' });
+  }
+
+  // ============ Usage Analytics ============
+
+  async getTopPrompts(days: number, limit = 10): Promise<PromptUsage[]> {
+    const since = Date.now() - days * DAY_MS;
+    const usage = new Map<string, PromptUsage>();
+    for (const event of this.usageEvents) {
+      const prompt = this.prompts.get(event.promptId);
+      if (!prompt || Date.parse(event.timestamp) < since) continue;
+      const entry = usage.get(event.promptId) ?? { prompt: toMetadata(prompt), count: 0, lastUsed: '' };
+      entry.count += 1;
+      if (event.timestamp > entry.lastUsed) entry.lastUsed = event.timestamp;
+      usage.set(event.promptId, entry);
     }
-    this._actionHistory.push({ type: 'record_usage', id });
+    return [...usage.values()]
+      .sort((a, b) => b.count - a.count || b.lastUsed.localeCompare(a.lastUsed))
+      .slice(0, limit);
+  }
+
+  async getDailyUsage(days: number): Promise<DailyUsage[]> {
+    const counts = new Map<string, number>();
+    for (const event of this.usageEvents) {
+      const date = localDate(new Date(event.timestamp));
+      counts.set(date, (counts.get(date) ?? 0) + 1);
+    }
+    const result: DailyUsage[] = [];
+    for (let offset = Math.max(days, 1) - 1; offset >= 0; offset--) {
+      const date = localDate(new Date(Date.now() - offset * DAY_MS));
+      result.push({ date, count: counts.get(date) ?? 0 });
+    }
+    return result;
+  }
+
+  async getNeverUsedPrompts(): Promise<PromptMetadata[]> {
+    return Array.from(this.prompts.values())
+      .filter((p) => p.useCount === 0)
+      .map(toMetadata);
+  }
+
+  async getUnusedPrompts(days: number): Promise<PromptMetadata[]> {
+    const cutoff = Date.now() - days * DAY_MS;
+    return Array.from(this.prompts.values())
+      .filter((p) => {
+        const last = p.useCount > 0 && p.lastUsed ? p.lastUsed : p.created;
+        return Date.parse(last) < cutoff;
+      })
+      .map(toMetadata);
+  }
+
+  async exportUsageCsv(): Promise<string> {
+    const rows = this.usageEvents.map((event) => {
+      const prompt = this.prompts.get(event.promptId);
+      return [event.timestamp, event.promptId, prompt?.name ?? '', prompt?.folder ?? '', event.action, event.app ?? '']
+        .map(csvField)
+        .join(',');
+    });
+    return ['timestamp,prompt_id,prompt_name,folder,action,app', ...rows].join('\n') + '\n';
   }
 
   // ============ Folder Operations ============
@@ -281,6 +832,7 @@ export class MockAdapter implements BackendAdapter {
 
   async pasteAndDismiss(text: string): Promise<void> {
     this._actionHistory.push({ type: 'paste', text });
+    this.previousApp = null;
     this.onPaste?.(text);
     this.onDismiss?.();
 
@@ -292,6 +844,12 @@ export class MockAdapter implements BackendAdapter {
     }
   }
 
+  async pastePrompt(promptId: string, values?: Record<string, string>, riderText?: string): Promise<void> {
+    const text = await this.renderPrompt(promptId, values);
+    await this.pasteAndDismiss(riderText ? `${text} ${riderText}` : text);
+    this.rememberValues(promptId, values ?? {});
+  }
+
   async copyToClipboard(text: string): Promise<void> {
     this._actionHistory.push({ type: 'copy_to_clipboard', text });
     this.onCopyToClipboard?.(text);
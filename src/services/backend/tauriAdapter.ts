@@ -8,7 +8,26 @@
 import { invoke } from '@tauri-apps/api/core';
 import type { BackendAdapter, ScreenBounds } from './types';
 import type { AppSettings, AuthSession } from './authTypes';
-import type { Prompt, PromptMetadata, PromptIndex, SearchResult } from '../../types';
+import type {
+  DailyUsage,
+  Includer,
+  IndexPage,
+  LauncherAction,
+  LauncherPage,
+  PageOptions,
+  Prompt,
+  PromptIndex,
+  PromptMetadata,
+  PromptSort,
+  PromptUsage,
+  RecentQuery,
+  SearchOptions,
+  SearchPage,
+  SmartFolder,
+  SortDirection,
+  TemplateVariable,
+  UsageAction,
+} from '../../types';
 
 export class TauriAdapter implements BackendAdapter {
   // ============ Data Operations ============
@@ -17,6 +36,10 @@ export class TauriAdapter implements BackendAdapter {
     return invoke<PromptIndex>('get_index');
   }
 
+  async getIndexPage(sort?: PromptSort, direction?: SortDirection, page?: PageOptions): Promise<IndexPage> {
+    return invoke<IndexPage>('get_index_page', { sort, direction, ...page });
+  }
+
   async getPrompt(id: string): Promise<Prompt> {
     return invoke<Prompt>('get_prompt', { id });
   }
@@ -25,16 +48,82 @@ export class TauriAdapter implements BackendAdapter {
     return invoke<PromptMetadata>('save_prompt', { prompt });
   }
 
+  async extractVariables(promptId: string): Promise<TemplateVariable[]> {
+    return invoke<TemplateVariable[]>('extract_variables', { promptId });
+  }
+
+  async renderPrompt(promptId: string, values?: Record<string, string>): Promise<string> {
+    return invoke<string>('render_prompt', { promptId, values });
+  }
+
+  async getIncludedBy(promptId: string): Promise<Includer[]> {
+    return invoke<Includer[]>('get_included_by', { promptId });
+  }
+
   async deletePrompt(id: string): Promise<void> {
     return invoke('delete_prompt', { id });
   }
 
-  async searchPrompts(query: string): Promise<SearchResult[]> {
-    return invoke<SearchResult[]>('search_prompts', { query });
+  async saveSmartFolder(folder: SmartFolder, previousName?: string): Promise<void> {
+    return invoke('save_smart_folder', { folder, previousName });
+  }
+
+  async deleteSmartFolder(name: string): Promise<void> {
+    return invoke('delete_smart_folder', { name });
   }
 
-  async recordUsage(id: string): Promise<void> {
-    return invoke('record_usage', { id });
+  async searchPrompts(query: string, options?: SearchOptions): Promise<SearchPage> {
+    return invoke<SearchPage>('search_prompts', { query, ...options });
+  }
+
+  async searchLauncher(query: string, options?: SearchOptions): Promise<LauncherPage> {
+    return invoke<LauncherPage>('search_launcher', { query, ...options });
+  }
+
+  async executeAction(action: LauncherAction): Promise<void> {
+    return invoke('execute_action', { action });
+  }
+
+  async recordUsage(id: string, action: UsageAction = 'paste', query?: string): Promise<void> {
+    return invoke('record_usage', { id, action, query });
+  }
+
+  async clearSelectionHistory(): Promise<void> {
+    return invoke('clear_selection_history');
+  }
+
+  async getQuerySuggestions(input: string, limit?: number): Promise<RecentQuery[]> {
+    return invoke<RecentQuery[]>('get_query_suggestions', { input, limit });
+  }
+
+  async clearQueryHistory(): Promise<void> {
+    return invoke('clear_query_history');
+  }
+
+  async clearVariableHistory(): Promise<void> {
+    return invoke('clear_variable_history');
+  }
+
+  // ============ Usage Analytics ============
+
+  async getTopPrompts(days: number, limit?: number): Promise<PromptUsage[]> {
+    return invoke<PromptUsage[]>('get_top_prompts', { days, limit });
+  }
+
+  async getDailyUsage(days: number): Promise<DailyUsage[]> {
+    return invoke<DailyUsage[]>('get_daily_usage', { days });
+  }
+
+  async getNeverUsedPrompts(): Promise<PromptMetadata[]> {
+    return invoke<PromptMetadata[]>('get_never_used_prompts');
+  }
+
+  async getUnusedPrompts(days: number): Promise<PromptMetadata[]> {
+    return invoke<PromptMetadata[]>('get_unused_prompts', { days });
+  }
+
+  async exportUsageCsv(): Promise<string> {
+    return invoke<string>('export_usage_csv');
   }
 
   // ============ Folder Operations ============
@@ -75,6 +164,10 @@ export class TauriAdapter implements BackendAdapter {
     return invoke('paste_and_dismiss', { text });
   }
 
+  async pastePrompt(promptId: string, values?: Record<string, string>, riderText?: string): Promise<void> {
+    return invoke('paste_prompt', { promptId, values, riderText });
+  }
+
   async copyToClipboard(text: string): Promise<void> {
     return invoke('copy_to_clipboard', { text });
   }
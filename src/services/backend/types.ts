@@ -5,7 +5,26 @@
  * us to swap between TauriAdapter (real backend) and MockAdapter (in-memory mock).
  */
 
-import type { Prompt, PromptMetadata, PromptIndex, SearchResult } from '../../types';
+import type {
+  DailyUsage,
+  Includer,
+  IndexPage,
+  LauncherAction,
+  LauncherPage,
+  PageOptions,
+  Prompt,
+  PromptIndex,
+  PromptMetadata,
+  PromptSort,
+  PromptUsage,
+  RecentQuery,
+  SearchOptions,
+  SearchPage,
+  SmartFolder,
+  SortDirection,
+  TemplateVariable,
+  UsageAction,
+} from '../../types';
 import type { AppSettings, AuthSession } from './authTypes';
 
 export interface ScreenBounds {
@@ -25,20 +44,83 @@ export interface BackendAdapter {
   /** Get the full prompt index (all prompts and folders) */
   getIndex(): Promise<PromptIndex>;
 
+  /** Get one page of prompt metadata, sorted (default: by name, ascending) */
+  getIndexPage(sort?: PromptSort, direction?: SortDirection, page?: PageOptions): Promise<IndexPage>;
+
   /** Get a single prompt by ID with full content */
   getPrompt(id: string): Promise<Prompt>;
 
   /** Save a prompt (create or update) */
   savePrompt(prompt: Prompt): Promise<PromptMetadata>;
 
+  /** The `{{variables}}` of a prompt, with defaults and positions */
+  extractVariables(promptId: string): Promise<TemplateVariable[]>;
+
+  /**
+   * A prompt's content with its includes expanded and its variables filled
+   * in from `values` or their defaults. Rejects if a variable has neither,
+   * or an include is missing or cyclic.
+   */
+  renderPrompt(promptId: string, values?: Record<string, string>): Promise<string>;
+
+  /** The prompts that include a prompt, directly first, then through other prompts */
+  getIncludedBy(promptId: string): Promise<Includer[]>;
+
   /** Delete a prompt by ID */
   deletePrompt(id: string): Promise<void>;
 
-  /** Search prompts by query string */
-  searchPrompts(query: string): Promise<SearchResult[]>;
+  /** Save a query as a smart folder, or update the one named `previousName` */
+  saveSmartFolder(folder: SmartFolder, previousName?: string): Promise<void>;
+
+  /** Delete a smart folder (its prompts are untouched) */
+  deleteSmartFolder(name: string): Promise<void>;
+
+  /** Search prompts by query string, one page at a time */
+  searchPrompts(query: string, options?: SearchOptions): Promise<SearchPage>;
+
+  /**
+   * Launcher search: prompts plus matching folders and app actions, one page
+   * at a time. A query starting with `>` searches actions only.
+   */
+  searchLauncher(query: string, options?: SearchOptions): Promise<LauncherPage>;
+
+  /** Run an app action picked in the launcher */
+  executeAction(action: LauncherAction): Promise<void>;
+
+  /**
+   * Record usage of a prompt (increments useCount, appends to the usage log).
+   * `query` is the launcher query it was picked from, so it ranks higher for it.
+   */
+  recordUsage(id: string, action?: UsageAction, query?: string): Promise<void>;
+
+  /** Forget which prompts were picked for which launcher queries */
+  clearSelectionHistory(): Promise<void>;
 
-  /** Record usage of a prompt (increments useCount) */
-  recordUsage(id: string): Promise<void>;
+  /** Past launcher queries to offer for a partial (or empty) input */
+  getQuerySuggestions(input: string, limit?: number): Promise<RecentQuery[]>;
+
+  /** Forget all recent launcher queries */
+  clearQueryHistory(): Promise<void>;
+
+  /** Forget all values entered for template variables */
+  clearVariableHistory(): Promise<void>;
+
+  // ============ Usage Analytics ============
+
+  /** Most-used prompts over the last `days` days */
+  getTopPrompts(days: number, limit?: number): Promise<PromptUsage[]>;
+
+  /** Uses per day over the last `days` days, oldest first */
+  getDailyUsage(days: number): Promise<DailyUsage[]>;
+
+  /** Prompts that have never been used */
+  getNeverUsedPrompts(): Promise<PromptMetadata[]>;
+
+  /** Prompts not used in the last `days` days */
+  getUnusedPrompts(days: number): Promise<PromptMetadata[]>;
+
+  /** Export the usage log as CSV text */
+  exportUsageCsv(): Promise<string>;
 
   // ============ Folder Operations ============
 
@@ -71,6 +153,12 @@ export interface BackendAdapter {
   /** Paste text to the previously focused app and dismiss window */
   pasteAndDismiss(text: string): Promise<void>;
 
+  /**
+   * Render a prompt (see `renderPrompt`), append `riderText` after a space,
+   * then paste like `pasteAndDismiss`. Nothing is pasted if rendering fails.
+   */
+  pastePrompt(promptId: string, values?: Record<string, string>, riderText?: string): Promise<void>;
+
   /** Copy text to clipboard */
   copyToClipboard(text: string): Promise<void>;
 
@@ -138,7 +226,11 @@ export type TestAction =
   | { type: 'close_welcome'; dontShowAgain: boolean }
   | { type: 'copy_to_clipboard'; text: string }
   | { type: 'copy_as_file'; name: string; content: string }
-  | { type: 'record_usage'; id: string }
+  | { type: 'record_usage'; id: string; action: UsageAction; query?: string }
+  | { type: 'clear_selection_history' }
+  | { type: 'clear_query_history' }
+  | { type: 'clear_variable_history' }
+  | { type: 'execute_action'; action: LauncherAction }
   | { type: 'set_hotkey'; hotkey: string | null }
   | { type: 'sync_to_cloud' }
   | { type: 'sign_in' }
@@ -1,5 +1,6 @@
 import { create } from 'zustand';
 import { backend } from '../services/backend';
+import { getErrorMessage } from '../utils/errors';
 import { useEditorStore } from './editorStore';
 
 /** User information from Firebase Auth */
@@ -93,7 +94,7 @@ export const useAuthStore = create<AuthStore>((set) => ({
     } catch (error) {
       console.error('Sign in failed:', error);
       set({
-        error: String(error),
+        error: getErrorMessage(error),
         isSigningIn: false,
       });
     }
@@ -108,7 +109,7 @@ export const useAuthStore = create<AuthStore>((set) => ({
       set({ user: null, isLoading: false });
     } catch (error) {
       console.error('Sign out failed:', error);
-      set({ error: String(error), isLoading: false });
+      set({ error: getErrorMessage(error), isLoading: false });
     }
   },
 
@@ -1,6 +1,7 @@
 import { create } from 'zustand';
 import { backend } from '../services/backend';
-import type { Prompt, PromptMetadata, FolderMetadata } from '../types';
+import { getErrorMessage } from '../utils/errors';
+import type { Prompt, PromptMetadata, FolderMetadata, SmartFolder } from '../types';
 import { DEFAULT_PROMPT_ICON, DEFAULT_PROMPT_COLOR } from '../config/constants';
 import { getLastColorFromStorage } from '../hooks/useIconPickerPreferences';
 
@@ -16,6 +17,8 @@ interface EditorState {
   folders: string[];
   /** Folder metadata (icon, color) */
   folderMeta: Record<string, FolderMetadata>;
+  /** Saved searches, with the prompts currently matching them */
+  smartFolders: SmartFolder[];
   /** Currently selected prompt ID in sidebar */
   selectedPromptId: string | null;
   /** Sidebar collapsed state */
@@ -42,6 +45,15 @@ interface EditorState {
   newFolderName: string;
   /** Folder being edited (for rename dialog) */
   editingFolder: string | null;
+  /** Whether the sidebar should ask for files to import (the launcher's Import… action) */
+  importRequested: boolean;
+}
+
+/** A text file to import as a prompt */
+export interface ImportFile {
+  /** File name; the prompt is named after it, without the extension */
+  name: string;
+  content: string;
 }
 
 interface EditorActions {
@@ -89,8 +101,18 @@ interface EditorActions {
   renameFolder: (oldName: string, newName: string) => Promise<boolean>;
   /** Delete a folder */
   deleteFolder: (name: string) => Promise<boolean>;
+  /** Save a smart folder, or update the one named `previousName` */
+  saveSmartFolder: (folder: SmartFolder, previousName?: string) => Promise<boolean>;
+  /** Delete a smart folder */
+  deleteSmartFolder: (name: string) => Promise<boolean>;
   /** Set the current view */
   setView: (view: EditorView) => void;
+  /** Show the prompt list and ask for files to import */
+  requestImport: () => void;
+  /** The import request was handled */
+  clearImportRequest: () => void;
+  /** Save files as new prompts in the first folder; returns how many were imported */
+  importFiles: (files: ImportFile[]) => Promise<number>;
 }
 
 type EditorStore = EditorState & EditorActions;
@@ -100,6 +122,7 @@ const initialState: EditorState = {
   prompts: [],
   folders: ['uncategorized'],
   folderMeta: {},
+  smartFolders: [],
   selectedPromptId: null,
   sidebarCollapsed: false,
   editedPrompt: null,
@@ -113,6 +136,7 @@ const initialState: EditorState = {
   isAddingFolder: false,
   newFolderName: '',
   editingFolder: null,
+  importRequested: false,
 };
 
 const emptyPrompt: Prompt = {
@@ -140,10 +164,11 @@ export const useEditorStore = create<EditorStore>((set, get) => ({
         prompts: index.prompts,
         folders: index.folders.length > 0 ? index.folders : ['uncategorized'],
         folderMeta: index.folderMeta || {},
+        smartFolders: index.smartFolders || [],
       });
     } catch (error) {
       console.error('Failed to load prompts:', error);
-      set({ error: String(error) });
+      set({ error: getErrorMessage(error) });
     }
   },
 
@@ -161,7 +186,7 @@ export const useEditorStore = create<EditorStore>((set, get) => ({
     } catch (error) {
       console.error('Failed to load prompt:', error);
       set({
-        error: String(error),
+        error: getErrorMessage(error),
         isLoading: false,
       });
     }
@@ -230,7 +255,7 @@ export const useEditorStore = create<EditorStore>((set, get) => ({
     } catch (error) {
       console.error('Failed to save prompt:', error);
       set({
-        error: String(error),
+        error: getErrorMessage(error),
         isSaving: false,
         autoSaveStatus: 'error',
       });
@@ -260,7 +285,7 @@ export const useEditorStore = create<EditorStore>((set, get) => ({
       return true;
     } catch (error) {
       console.error('Failed to delete prompt:', error);
-      set({ error: String(error) });
+      set({ error: getErrorMessage(error) });
       return false;
     }
   },
@@ -334,7 +359,7 @@ export const useEditorStore = create<EditorStore>((set, get) => ({
       return true;
     } catch (error) {
       console.error('Failed to create folder:', error);
-      set({ error: String(error) });
+      set({ error: getErrorMessage(error) });
       return false;
     }
   },
@@ -374,7 +399,7 @@ export const useEditorStore = create<EditorStore>((set, get) => ({
       return true;
     } catch (error) {
       console.error('Failed to create folder:', error);
-      set({ error: String(error) });
+      set({ error: getErrorMessage(error) });
       return false;
     }
   },
@@ -402,7 +427,7 @@ export const useEditorStore = create<EditorStore>((set, get) => ({
       return true;
     } catch (error) {
       console.error('Failed to rename folder:', error);
-      set({ error: String(error) });
+      set({ error: getErrorMessage(error) });
       return false;
     }
   },
@@ -414,7 +439,31 @@ export const useEditorStore = create<EditorStore>((set, get) => ({
       return true;
     } catch (error) {
       console.error('Failed to delete folder:', error);
-      set({ error: String(error) });
+      set({ error: getErrorMessage(error) });
+      return false;
+    }
+  },
+
+  saveSmartFolder: async (folder, previousName) => {
+    try {
+      await backend.saveSmartFolder(folder, previousName);
+      await get().loadPrompts();
+      return true;
+    } catch (error) {
+      console.error('Failed to save smart folder:', error);
+      set({ error: getErrorMessage(error) });
+      return false;
+    }
+  },
+
+  deleteSmartFolder: async (name) => {
+    try {
+      await backend.deleteSmartFolder(name);
+      await get().loadPrompts();
+      return true;
+    } catch (error) {
+      console.error('Failed to delete smart folder:', error);
+      set({ error: getErrorMessage(error) });
       return false;
     }
   },
@@ -422,6 +471,35 @@ export const useEditorStore = create<EditorStore>((set, get) => ({
   setView: (view) => {
     set({ currentView: view });
   },
+
+  requestImport: () => {
+    set({ currentView: 'prompts', sidebarCollapsed: false, importRequested: true });
+  },
+
+  clearImportRequest: () => {
+    set({ importRequested: false });
+  },
+
+  importFiles: async (files) => {
+    const { folders } = get();
+    const folder = folders[0] || 'uncategorized';
+    let imported = 0;
+
+    for (const file of files) {
+      const name = file.name.replace(/\.[^.]+$/, '').trim();
+      if (!name) continue;
+      try {
+        await backend.savePrompt({ ...emptyPrompt, name, folder, content: file.content });
+        imported++;
+      } catch (error) {
+        console.error('Failed to import file:', file.name, error);
+        set({ error: getErrorMessage(error) });
+      }
+    }
+
+    await get().loadPrompts();
+    return imported;
+  },
 }));
 
 // Expose store on window for E2E tests
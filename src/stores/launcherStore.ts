@@ -1,23 +1,33 @@
 import { create } from 'zustand';
-import type { LauncherState, SearchResult, ContextMenuState } from '../types';
+import type { LauncherState, LauncherResult, ContextMenuState, TemplateVariable } from '../types';
 
 interface LauncherActions {
   /** Set the search query */
   setQuery: (query: string) => void;
-  /** Set search results */
-  setResults: (results: SearchResult[]) => void;
+  /** Set search results; `total` defaults to the number of results */
+  setResults: (results: LauncherResult[], total?: number) => void;
+  /** Append the next page of results, keeping the selection */
+  appendResults: (results: LauncherResult[]) => void;
   /** Select the next result */
   selectNext: () => void;
   /** Select the previous result */
   selectPrevious: () => void;
   /** Set selected index directly */
   setSelectedIndex: (index: number) => void;
-  /** Promote the selected prompt */
+  /** Promote the selected prompt (folders and actions can't be promoted) */
   promoteSelected: () => void;
   /** Unpromote and return to search mode */
   unpromote: () => void;
   /** Set rider text */
   setRiderText: (text: string) => void;
+  /** Ask for a prompt's variables, prefilled with their last values or defaults */
+  startFill: (prompt: { id: string; name: string }, variables: TemplateVariable[], riderText?: string) => void;
+  /** Set the value entered for a variable */
+  setValue: (name: string, value: string) => void;
+  /** Show why submitted values were rejected */
+  setFillError: (error: string | null) => void;
+  /** Stop filling in variables and return to search mode */
+  cancelFill: () => void;
   /** Execute the selected/promoted prompt */
   executeSelected: () => void;
   /** Set the execute handler */
@@ -29,7 +39,7 @@ interface LauncherActions {
   /** Get the final text to paste */
   getFinalText: () => string;
   /** Get currently selected result */
-  getSelectedResult: () => SearchResult | null;
+  getSelectedResult: () => LauncherResult | null;
   /** Open context menu */
   openContextMenu: (x: number, y: number, promptId?: string, promptName?: string) => void;
   /** Close context menu */
@@ -38,6 +48,18 @@ interface LauncherActions {
 
 type LauncherStore = LauncherState & LauncherActions;
 
+/** Identifies a launcher result across pages */
+export function launcherResultKey(result: LauncherResult): string {
+  switch (result.kind) {
+    case 'prompt':
+      return `prompt:${result.prompt.id}`;
+    case 'folder':
+      return `folder:${result.name}`;
+    case 'action':
+      return `action:${result.action}`;
+  }
+}
+
 const initialContextMenu: ContextMenuState = {
   isOpen: false,
   x: 0,
@@ -50,9 +72,14 @@ const initialState: LauncherState = {
   mode: 'search',
   query: '',
   results: [],
+  totalResults: 0,
   selectedIndex: 0,
   promotedPrompt: null,
   riderText: '',
+  fillPrompt: null,
+  variables: [],
+  values: {},
+  fillError: null,
   isLoading: false,
   contextMenu: initialContextMenu,
 };
@@ -64,8 +91,14 @@ export const useLauncherStore = create<LauncherStore>((set, get) => ({
     set({ query, selectedIndex: 0 });
   },
 
-  setResults: (results) => {
-    set({ results, selectedIndex: 0 });
+  setResults: (results, total) => {
+    set({ results, totalResults: total ?? results.length, selectedIndex: 0 });
+  },
+
+  appendResults: (more) => {
+    const { results } = get();
+    const seen = new Set(results.map(launcherResultKey));
+    set({ results: [...results, ...more.filter((r) => !seen.has(launcherResultKey(r)))] });
   },
 
   selectNext: () => {
@@ -90,7 +123,7 @@ export const useLauncherStore = create<LauncherStore>((set, get) => ({
   promoteSelected: () => {
     const { results, selectedIndex } = get();
     const selected = results[selectedIndex];
-    if (!selected) return;
+    if (selected?.kind !== 'prompt') return;
 
     set({
       mode: 'promoted',
@@ -111,6 +144,36 @@ export const useLauncherStore = create<LauncherStore>((set, get) => ({
     set({ riderText });
   },
 
+  startFill: (fillPrompt, variables, riderText = '') => {
+    set({
+      mode: 'fill',
+      fillPrompt,
+      variables,
+      values: Object.fromEntries(variables.map((v) => [v.name, v.recent?.[0] ?? v.default ?? ''])),
+      riderText,
+      fillError: null,
+    });
+  },
+
+  setValue: (name, value) => {
+    set({ values: { ...get().values, [name]: value }, fillError: null });
+  },
+
+  setFillError: (fillError) => {
+    set({ fillError });
+  },
+
+  cancelFill: () => {
+    set({
+      mode: 'search',
+      fillPrompt: null,
+      variables: [],
+      values: {},
+      fillError: null,
+      riderText: '',
+    });
+  },
+
   _executeHandler: null as (() => void) | null,
 
   executeSelected: () => {
@@ -144,7 +207,7 @@ export const useLauncherStore = create<LauncherStore>((set, get) => ({
     }
 
     const selected = results[selectedIndex];
-    if (!selected) return '';
+    if (selected?.kind !== 'prompt') return '';
 
     return selected.prompt.name;
   },
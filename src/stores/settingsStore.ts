@@ -1,11 +1,13 @@
 import { create } from 'zustand';
 import { backend } from '../services/backend';
+import { getErrorMessage } from '../utils/errors';
 import {
   ACCENT_COLORS,
   type AccentColorName,
   type ThemeOption,
   DEFAULT_THEME,
   DEFAULT_ACCENT_COLOR,
+  DEFAULT_RESULT_LIMIT,
 } from '../config/constants';
 
 /** General application settings */
@@ -31,11 +33,66 @@ export interface AppearanceSettings {
   accentColor: AccentColorName;
 }
 
+/** Text relevance algorithm used to rank search results */
+export type TextRanking = 'classic' | 'bm25f';
+
+/** How much each ranking signal counts (1 is the default, 0 turns it off) */
+export interface RankingWeights {
+  text: number;
+  usage: number;
+  recency: number;
+  pinned: number;
+  appContext: number;
+  selection: number;
+}
+
+export const DEFAULT_RANKING_WEIGHTS: RankingWeights = {
+  text: 1,
+  usage: 1,
+  recency: 1,
+  pinned: 1,
+  appContext: 1,
+  selection: 1,
+};
+
+/** Search settings */
+export interface SearchSettings {
+  ranking: TextRanking;
+  /** Ranking signal weights, editable in settings.json */
+  weights: RankingWeights;
+  /** Results per page in the launcher */
+  resultLimit: number;
+  /** Remember launcher queries and offer them as suggestions */
+  historyEnabled: boolean;
+}
+
+/** Template variable settings */
+export interface VariableSettings {
+  /** Remember values entered for variables and offer them again */
+  rememberValues: boolean;
+  /** Values remembered per variable, editable in settings.json */
+  rememberedValues: number;
+  /** Offer a value entered in one prompt for the same variable name in others */
+  shareAcrossPrompts: boolean;
+  /** Never remember variables whose names contain one of these, ignoring case */
+  sensitive: string[];
+}
+
+/** Default template variable settings */
+export const DEFAULT_VARIABLE_SETTINGS: VariableSettings = {
+  rememberValues: true,
+  rememberedValues: 5,
+  shareAcrossPrompts: false,
+  sensitive: ['password', 'secret', 'token'],
+};
+
 /** Complete application settings */
 export interface AppSettings {
   general: GeneralSettings;
   sync: SyncSettings;
   appearance: AppearanceSettings;
+  search: SearchSettings;
+  variables: VariableSettings;
 }
 
 interface SettingsState {
@@ -60,6 +117,10 @@ interface SettingsActions {
   updateSyncSettings: (updates: Partial<SyncSettings>) => Promise<void>;
   /** Update appearance settings */
   updateAppearanceSettings: (updates: Partial<AppearanceSettings>) => Promise<void>;
+  /** Update search settings */
+  updateSearchSettings: (updates: Partial<SearchSettings>) => Promise<void>;
+  /** Update template variable settings */
+  updateVariableSettings: (updates: Partial<VariableSettings>) => Promise<void>;
   /** Set auto-launch enabled/disabled */
   setAutoLaunch: (enabled: boolean) => Promise<void>;
   /** Set global hotkey (null to disable) */
@@ -70,6 +131,8 @@ interface SettingsActions {
   setTheme: (theme: ThemeOption) => Promise<void>;
   /** Set accent color */
   setAccentColor: (accentColor: AccentColorName) => Promise<void>;
+  /** Set search ranking profile */
+  setRankingProfile: (ranking: TextRanking) => Promise<void>;
   /** Clear error */
   clearError: () => void;
 }
@@ -91,6 +154,13 @@ const defaultSettings: AppSettings = {
     theme: DEFAULT_THEME,
     accentColor: DEFAULT_ACCENT_COLOR,
   },
+  search: {
+    ranking: 'classic',
+    weights: DEFAULT_RANKING_WEIGHTS,
+    resultLimit: DEFAULT_RESULT_LIMIT,
+    historyEnabled: true,
+  },
+  variables: DEFAULT_VARIABLE_SETTINGS,
 };
 
 /** Apply theme and accent color to document root */
@@ -139,6 +209,13 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
           theme: settings.appearance?.theme ?? DEFAULT_THEME,
           accentColor: settings.appearance?.accentColor ?? DEFAULT_ACCENT_COLOR,
         },
+        search: {
+          ranking: settings.search?.ranking ?? 'classic',
+          weights: { ...DEFAULT_RANKING_WEIGHTS, ...settings.search?.weights },
+          resultLimit: settings.search?.resultLimit ?? DEFAULT_RESULT_LIMIT,
+          historyEnabled: settings.search?.historyEnabled ?? true,
+        },
+        variables: { ...DEFAULT_VARIABLE_SETTINGS, ...settings.variables },
       };
       // Apply appearance immediately
       applyAppearance(normalizedSettings.appearance.theme, normalizedSettings.appearance.accentColor);
@@ -149,7 +226,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
       });
     } catch (error) {
       console.error('Failed to load settings:', error);
-      set({ error: String(error), isLoading: false });
+      set({ error: getErrorMessage(error), isLoading: false });
     }
   },
 
@@ -166,7 +243,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
       set({ settings: newSettings, isSaving: false });
     } catch (error) {
       console.error('Failed to save settings:', error);
-      set({ error: String(error), isSaving: false });
+      set({ error: getErrorMessage(error), isSaving: false });
     }
   },
 
@@ -197,7 +274,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
       }
     } catch (error) {
       console.error('Failed to save settings:', error);
-      set({ error: String(error), isSaving: false });
+      set({ error: getErrorMessage(error), isSaving: false });
     }
   },
 
@@ -220,7 +297,41 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
       console.error('Failed to save settings:', error);
       // Revert appearance on error
       applyAppearance(settings.appearance.theme, settings.appearance.accentColor);
-      set({ error: String(error), isSaving: false });
+      set({ error: getErrorMessage(error), isSaving: false });
+    }
+  },
+
+  updateSearchSettings: async (updates) => {
+    const { settings } = get();
+    const newSettings: AppSettings = {
+      ...settings,
+      search: { ...settings.search, ...updates },
+    };
+
+    set({ isSaving: true, error: null });
+    try {
+      await backend.saveSettings(newSettings);
+      set({ settings: newSettings, isSaving: false });
+    } catch (error) {
+      console.error('Failed to save settings:', error);
+      set({ error: getErrorMessage(error), isSaving: false });
+    }
+  },
+
+  updateVariableSettings: async (updates) => {
+    const { settings } = get();
+    const newSettings: AppSettings = {
+      ...settings,
+      variables: { ...settings.variables, ...updates },
+    };
+
+    set({ isSaving: true, error: null });
+    try {
+      await backend.saveSettings(newSettings);
+      set({ settings: newSettings, isSaving: false });
+    } catch (error) {
+      console.error('Failed to save settings:', error);
+      set({ error: getErrorMessage(error), isSaving: false });
     }
   },
 
@@ -234,6 +345,11 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
     await updateAppearanceSettings({ accentColor });
   },
 
+  setRankingProfile: async (ranking) => {
+    const { updateSearchSettings } = get();
+    await updateSearchSettings({ ranking });
+  },
+
   setAutoLaunch: async (enabled) => {
     set({ isSaving: true, error: null });
     try {
@@ -241,7 +357,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
       set({ systemAutoLaunch: enabled, isSaving: false });
     } catch (error) {
       console.error('Failed to set auto-launch:', error);
-      set({ error: String(error), isSaving: false });
+      set({ error: getErrorMessage(error), isSaving: false });
     }
   },
 
@@ -259,7 +375,7 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
       set({ settings: newSettings, isSaving: false });
     } catch (error) {
       console.error('Failed to set hotkey:', error);
-      set({ error: String(error), isSaving: false });
+      set({ error: getErrorMessage(error), isSaving: false });
     }
   },
 
@@ -15,6 +15,12 @@ export interface PromptMetadata {
   updated: string;
   icon?: PromptIconName;
   color?: PromptColorName;
+  tags?: string[];
+  pinned?: boolean;
+  /** Short unique code that selects the prompt when typed exactly (e.g. `;rev`) */
+  keyword?: string;
+  /** Other names the prompt is found by */
+  aliases?: string[];
 }
 
 /**
@@ -24,12 +30,172 @@ export interface Prompt extends PromptMetadata {
   content: string;
 }
 
+/**
+ * A matched span of text, in UTF-16 offsets (string indices)
+ */
+export interface MatchRange {
+  start: number;
+  end: number;
+}
+
+/**
+ * The kind of value a template variable takes, declared as `{{name:kind}}`
+ */
+export type VariableKind =
+  | { type: 'text' }
+  | { type: 'multiline' }
+  | { type: 'number'; min?: number; max?: number }
+  | { type: 'choice'; options: string[] };
+
+/**
+ * A `{{variable}}` of a prompt's content
+ */
+export type TemplateVariable = VariableKind & {
+  name: string;
+  /** From `{{name|default}}`; used when no value is given */
+  default?: string;
+  /** A built-in such as `{{clipboard}}` or `{{date:%Y}}`, filled in when pasted */
+  builtin?: boolean;
+  /** Whether a value must be given (not built-in, optional or defaulted) */
+  required: boolean;
+  /** Each placeholder of the variable in the content, in UTF-16 offsets */
+  ranges: MatchRange[];
+  /** Values recently entered for it, most recent first */
+  recent?: string[];
+};
+
+/**
+ * A prompt that includes another (`{{> folder/name}}` or `{{> id}}`)
+ */
+export interface Includer {
+  prompt: PromptMetadata;
+  /** False when it includes it through another included prompt */
+  direct: boolean;
+}
+
+/**
+ * Ranges of each field that matched the search query
+ */
+export interface SearchMatches {
+  name?: MatchRange[];
+  folder?: MatchRange[];
+  description?: MatchRange[];
+}
+
+/**
+ * Excerpt of a prompt's content around its best content match
+ */
+export interface Snippet {
+  /** Single-line excerpt, with ellipses where content was cut */
+  text: string;
+  /** Matched words within `text`, in UTF-16 offsets */
+  highlights: MatchRange[];
+}
+
 /**
  * Search result with score
  */
 export interface SearchResult {
   prompt: PromptMetadata;
   score: number;
+  matches?: SearchMatches;
+  /** Present when the query matched the prompt's content */
+  snippet?: Snippet;
+  /** Each ranking signal's part of the score, when searched with `explain` */
+  explanation?: ScoreContribution[];
+}
+
+/** Something a search result's score is made of */
+export type RankingSignal = 'text' | 'usage' | 'recency' | 'pinned' | 'appContext' | 'selection' | 'keyword';
+
+/** One ranking signal's part of a score */
+export interface ScoreContribution {
+  signal: RankingSignal;
+  /** The signal's value: text points, otherwise 0..1 */
+  value: number;
+  /** Points added to the score */
+  points: number;
+}
+
+/**
+ * One page of search results
+ */
+export interface SearchPage {
+  results: SearchResult[];
+  /** Number of matching prompts across all pages */
+  total: number;
+}
+
+/** An app action the launcher can run */
+export type LauncherAction = 'newPrompt' | 'syncNow' | 'openSettings' | 'signOut' | 'import';
+
+/** A folder matching a launcher query; picking it narrows the search to it */
+export interface FolderResult {
+  name: string;
+  promptCount: number;
+  score: number;
+  matches?: MatchRange[];
+}
+
+/** An action matching a launcher query */
+export interface ActionResult {
+  action: LauncherAction;
+  title: string;
+  score: number;
+  /** Ranges of the title that matched (none if a keyword matched) */
+  matches?: MatchRange[];
+}
+
+/**
+ * One launcher search result: a prompt, a folder or an action
+ */
+export type LauncherResult =
+  | ({ kind: 'prompt' } & SearchResult)
+  | ({ kind: 'folder' } & FolderResult)
+  | ({ kind: 'action' } & ActionResult);
+
+/**
+ * One page of launcher search results
+ */
+export interface LauncherPage {
+  results: LauncherResult[];
+  /** Number of results across all pages */
+  total: number;
+}
+
+/**
+ * Which slice of a list to fetch
+ */
+export interface PageOptions {
+  offset?: number;
+  /** Defaults to the result limit in settings (search) or 100 (index) */
+  limit?: number;
+}
+
+/** A past launcher query, offered as a suggestion */
+export interface RecentQuery {
+  query: string;
+  lastUsed: string;
+  /** Times a prompt was picked for it */
+  count: number;
+}
+
+export interface SearchOptions extends PageOptions {
+  /** Include each result's score by ranking signal, for debugging */
+  explain?: boolean;
+}
+
+export type PromptSort = 'name' | 'created' | 'updated' | 'useCount' | 'lastUsed';
+
+export type SortDirection = 'asc' | 'desc';
+
+/**
+ * One page of the prompt list
+ */
+export interface IndexPage {
+  prompts: PromptMetadata[];
+  /** Number of prompts in the library */
+  total: number;
 }
 
 /**
@@ -48,4 +214,42 @@ export interface PromptIndex {
   prompts: PromptMetadata[];
   folders: string[];
   folderMeta?: Record<string, FolderMetadata>;
+  /** Saved searches listed next to the folders */
+  smartFolders?: SmartFolder[];
+}
+
+/**
+ * A saved search shown as a folder. Its prompts aren't stored; the backend
+ * runs the query each time the index is read.
+ */
+export interface SmartFolder {
+  name: string;
+  /** Launcher query, filters included (e.g. `unused:60d`, `folder:work review`) */
+  query: string;
+  icon?: PromptIconName;
+  color?: PromptColorName;
+  /** Ids of the prompts matching the query, best first (filled in when read) */
+  promptIds?: string[];
+}
+
+/**
+ * How a prompt was used
+ */
+export type UsageAction = 'paste' | 'copyFile' | 'editorPaste';
+
+/**
+ * A prompt's use count over an analytics window
+ */
+export interface PromptUsage {
+  prompt: PromptMetadata;
+  count: number;
+  lastUsed: string;
+}
+
+/**
+ * Number of uses on one local day (YYYY-MM-DD)
+ */
+export interface DailyUsage {
+  date: string;
+  count: number;
 }
@@ -1,9 +1,9 @@
-import type { PromptMetadata, SearchResult } from './prompt';
+import type { LauncherResult, PromptMetadata, TemplateVariable } from './prompt';
 
 /**
- * Launcher mode - either searching or promoted
+ * Launcher mode - searching, promoted, or filling in a prompt's variables
  */
-export type LauncherMode = 'search' | 'promoted';
+export type LauncherMode = 'search' | 'promoted' | 'fill';
 
 /**
  * Context menu state
@@ -29,14 +29,24 @@ export interface LauncherState {
   mode: LauncherMode;
   /** Search query text */
   query: string;
-  /** Filtered search results */
-  results: SearchResult[];
+  /** Filtered search results: prompts, folders and actions (the pages loaded so far) */
+  results: LauncherResult[];
+  /** Number of matching results across all pages */
+  totalResults: number;
   /** Currently selected result index */
   selectedIndex: number;
   /** Promoted prompt (when mode is 'promoted') */
   promotedPrompt: PromptMetadata | null;
   /** Rider text to append to promoted prompt */
   riderText: string;
+  /** Prompt whose variables are being filled in (when mode is 'fill') */
+  fillPrompt: { id: string; name: string } | null;
+  /** Variables of the prompt being filled in, built-ins left out */
+  variables: TemplateVariable[];
+  /** Values entered for them, by name */
+  values: Record<string, string>;
+  /** Why the last submitted values were rejected */
+  fillError: string | null;
   /** Loading state for search */
   isLoading: boolean;
   /** Context menu state */
@@ -0,0 +1,55 @@
+/**
+ * Helpers for structured errors returned by Tauri commands
+ */
+
+/** Stable error codes emitted by the backend (mirrors `ErrorCode` in error.rs) */
+export type ErrorCode =
+  | 'NotFound'
+  | 'AlreadyExists'
+  | 'InvalidInput'
+  | 'Io'
+  | 'Parse'
+  | 'NotAuthenticated'
+  | 'AuthExpired'
+  | 'AuthFailed'
+  | 'Network'
+  | 'Remote'
+  | 'SyncRejected'
+  | 'LibraryLocked'
+  | 'PermissionDenied'
+  | 'Clipboard'
+  | 'Window'
+  | 'Hotkey'
+  | 'Platform'
+  | 'Internal';
+
+/** Error payload rejected by Tauri commands */
+export interface AppError {
+  code: ErrorCode;
+  message: string;
+  /** Identifying details such as `promptId`, `folder` or `path` */
+  context?: Record<string, string>;
+}
+
+/** Check whether a caught value is a structured backend error */
+export function isAppError(error: unknown): error is AppError {
+  return (
+    typeof error === 'object' &&
+    error !== null &&
+    typeof (error as AppError).code === 'string' &&
+    typeof (error as AppError).message === 'string'
+  );
+}
+
+/** Check whether a caught value is a backend error with the given code */
+export function hasErrorCode(error: unknown, code: ErrorCode): boolean {
+  return isAppError(error) && error.code === code;
+}
+
+/**
+ * Get a display message for any caught value.
+ * Structured backend errors use their message; everything else is stringified.
+ */
+export function getErrorMessage(error: unknown): string {
+  return isAppError(error) ? error.message : String(error);
+}
@@ -0,0 +1,50 @@
+import { backend } from '../services/backend';
+import { useLauncherStore } from '../stores/launcherStore';
+import { getErrorMessage, isAppError } from './errors';
+
+/**
+ * Paste a prompt from the launcher. A prompt with `{{variables}}` to fill in
+ * opens the launcher's variable form instead, prefilled with their defaults,
+ * and is pasted once that's submitted (see `pasteFilledPrompt`). Built-ins
+ * (`{{date}}`, `{{clipboard}}`, ...) are filled in when pasted.
+ */
+export async function pasteFromLauncher(prompt: { id: string; name: string }, riderText = ''): Promise<void> {
+  const variables = (await backend.extractVariables(prompt.id)).filter((v) => !v.builtin);
+  if (variables.length > 0) {
+    useLauncherStore.getState().startFill(prompt, variables, riderText);
+    return;
+  }
+  await pasteWithValues(prompt.id, {}, riderText);
+}
+
+/**
+ * Paste the prompt being filled in with the values entered. Values the
+ * backend rejects (a number out of range, a missing required variable) are
+ * shown on the form rather than pasted.
+ */
+export async function pasteFilledPrompt(): Promise<void> {
+  const { fillPrompt, values, riderText, setFillError } = useLauncherStore.getState();
+  if (!fillPrompt) return;
+  try {
+    // Check the values first, so a rejected form isn't recorded as a use
+    await backend.renderPrompt(fillPrompt.id, values);
+    await pasteWithValues(fillPrompt.id, values, riderText);
+  } catch (error) {
+    setFillError(fillErrorMessage(error));
+  }
+}
+
+async function pasteWithValues(promptId: string, values: Record<string, string>, riderText: string): Promise<void> {
+  const { query } = useLauncherStore.getState();
+  // Record before pasting: the use is logged against the app the launcher was
+  // opened over, which is forgotten once focus is handed back to it
+  await backend.recordUsage(promptId, 'paste', query);
+  await backend.pastePrompt(promptId, values, riderText);
+}
+
+/** The error's message, with the variables it names */
+function fillErrorMessage(error: unknown): string {
+  if (error instanceof Error) return error.message;
+  const variables = isAppError(error) ? error.context?.variables : undefined;
+  return variables ? `${getErrorMessage(error)}: ${variables}` : getErrorMessage(error);
+}